@@ -5,13 +5,14 @@ use enigo::Key;
 use streamduck_core::core::button::{Button, Component, parse_button_to_component, parse_unique_button_to_component};
 use streamduck_core::core::UniqueButton;
 use streamduck_core::modules::components::{ComponentDefinition, map_ui_values, map_ui_values_ref, UIField, UIFieldType, UIFieldValue, UIValue};
-use streamduck_core::thread::rendering::{ButtonBackground, ButtonText, RendererComponentBuilder};
+use streamduck_core::thread::rendering::{ButtonBackground, ButtonText, RendererComponentBuilder, TextFill};
 use streamduck_core::thread::util::TextAlignment;
 
 pub fn add_definition(map: &mut HashMap<String, ComponentDefinition>) {
     map.insert("key_sequence".to_string(), ComponentDefinition {
         display_name: "Key Sequence".to_string(),
         description: "Performs a sequence of keystrokes defined by component's parameters".to_string(),
+        requires: vec![],
         default_looks: RendererComponentBuilder::new()
             .background(ButtonBackground::Solid((50, 50, 50, 255)))
             .add_text(ButtonText {
@@ -21,8 +22,14 @@ pub fn add_definition(map: &mut HashMap<String, ComponentDefinition>) {
                 alignment: TextAlignment::Center,
                 padding: 0,
                 offset: (0.0, 0.0),
-                color: (255, 255, 255, 255),
-                shadow: None
+                fill: TextFill::Solid((255, 255, 255, 255)),
+                shadow: None,
+                auto_fit: false,
+                max_width: 0,
+                max_height: 0,
+                letter_spacing: 0.0,
+                blink: None,
+                truncate: None
             })
             .build()
     });
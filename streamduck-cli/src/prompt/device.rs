@@ -4,23 +4,25 @@ use crate::prompt::ClientRef;
 use crate::prompt::utils::print_table;
 
 pub fn device_list(client: ClientRef) {
-    let list: Vec<(bool, bool, String, String)> = client.device_list().expect("Failed to get device list")
+    let list: Vec<(bool, bool, String, String, String)> = client.device_list().expect("Failed to get device list")
         .into_iter()
-        .map(|d| (d.online, d.managed, d.device_type.to_string(), d.serial_number))
+        .map(|d| (d.online, d.managed, d.device_type.to_string(), d.serial_number, d.nickname))
         .collect();
 
     let mut table: Vec<Vec<&str>> = vec![
         vec!["Online"],
         vec!["Managed"],
         vec!["Type"],
-        vec!["Serial"]
+        vec!["Serial"],
+        vec!["Nickname"]
     ];
 
-    for (online, managed, ty, serial) in &list {
+    for (online, managed, ty, serial, nickname) in &list {
         table[0].push(if *online { "Yes" } else { "No" });
         table[1].push(if *managed { "Yes" } else { "No" });
         table[2].push(ty.as_str());
         table[3].push(serial.as_str());
+        table[4].push(nickname.as_str());
     }
 
     print_table(table, "-", "|");
@@ -4,7 +4,7 @@ use image::{DynamicImage, GenericImageView, Rgba};
 use image::imageops::FilterType;
 use image::io::Reader;
 use palette::{Hsl, IntoColor, Srgb};
-use streamduck_client::daemon::daemon_data::assets::{AddImageResult, ListImagesResult, RemoveImageResult};
+use streamduck_client::daemon::daemon_data::assets::{AddImageResult, ListImagesResult, RemoveImageResult, SocketImage};
 use crate::prompt::ClientRef;
 
 fn check_for_true_color() -> bool {
@@ -85,6 +85,18 @@ pub fn show_image(data: String, size: u32) {
     println!("Failed to display image");
 }
 
+fn print_images(images: impl Iterator<Item = (String, SocketImage)>, size: u32) {
+    for (identifier, image) in images {
+        println!("{}", if image.animated { "GIF" } else { "Image" });
+        println!("Identifier: {}", identifier);
+        if let Some(metadata) = image.metadata {
+            println!("Metadata: {}x{} {}, {} bytes", metadata.width, metadata.height, metadata.format, metadata.byte_size);
+        }
+        show_image(image.image_blob, size);
+        println!()
+    }
+}
+
 pub fn list_images(client: ClientRef, current_sn: &str, mut args: Split<&str>) {
     if !current_sn.is_empty() {
         let size = if let Some(size) = args.next() {
@@ -101,14 +113,8 @@ pub fn list_images(client: ClientRef, current_sn: &str, mut args: Split<&str>) {
 
         match result {
             ListImagesResult::DeviceNotFound => println!("image list: Device not found"),
-            ListImagesResult::Images(images) => {
-                for (identifier, image) in images {
-                    println!("{}", if image.animated { "GIF" } else { "Image" });
-                    println!("Identifier: {}", identifier);
-                    show_image(image.image_blob, size);
-                    println!()
-                }
-            }
+            ListImagesResult::Images(images) => print_images(images.into_iter(), size),
+            ListImagesResult::Page { images, .. } => print_images(images.into_iter(), size),
         }
     } else {
         println!("image list: No device is selected");
@@ -125,6 +131,8 @@ pub fn add_image(client: ClientRef, current_sn: &str, args: Split<&str>) {
             match result {
                 AddImageResult::DeviceNotFound => println!("image add: Device not found"),
                 AddImageResult::InvalidData => println!("image add: Invalid image data"),
+                AddImageResult::TooLarge => println!("image add: Image is too large"),
+                AddImageResult::InvalidImage => println!("image add: Data is not a supported image"),
                 AddImageResult::Added(identifier) => println!("image add: Added under identifier '{}'", identifier),
             }
         } else {
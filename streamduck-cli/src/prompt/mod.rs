@@ -112,6 +112,7 @@ pub fn prompt(client: Arc<dyn SDSyncRequestClient>) {
                                     if drop == "drop" {
                                         match client.drop_stack_to_root(&current_sn).expect("Failed to drop stack") {
                                             DropStackToRootResult::DeviceNotFound => println!("back drop: Device not found"),
+                                            DropStackToRootResult::Locked => println!("back drop: Device is locked"),
                                             DropStackToRootResult::Dropped => println!("back drop: Dropped to root screen")
                                         }
                                     } else {
@@ -121,6 +122,7 @@ pub fn prompt(client: Arc<dyn SDSyncRequestClient>) {
                                     match client.pop_screen(&current_sn).expect("Failed to pop screen") {
                                         PopScreenResult::DeviceNotFound => println!("back: Device not found"),
                                         PopScreenResult::OnlyOneRemaining => println!("back: Only one remaining"),
+                                        PopScreenResult::Locked => println!("back: Device is locked"),
                                         PopScreenResult::Popped => println!("back: Popped screen"),
                                     }
                                 }
@@ -0,0 +1,1026 @@
+//! Asynchronous client built on Tokio, can be used alongside synchronous clients
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::task::{Context, Poll};
+use futures::Stream;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, BufReader};
+use tokio::sync::Mutex;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::sync::oneshot;
+
+use streamduck_core::core::button::Button;
+use streamduck_core::core::RawButtonPanel;
+use streamduck_core::modules::components::{ComponentDefinition, UIPathValue};
+use streamduck_core::modules::events::SDGlobalEvent;
+use streamduck_core::modules::PluginMetadata;
+use streamduck_daemon::daemon_data::assets::{AddImage, AddImageResult, ListFonts, ListImages, ListImagesResult, MeasureText, MeasureTextResult, RemoveImage, RemoveImageResult};
+use streamduck_daemon::daemon_data::buttons::{AddComponent, AddComponentResult, AddComponentToKeys, AddComponentToKeysResult, AddComponentValue, AddComponentValueResult, ClearButton, ClearButtonResult, ClipboardStatusResult, CopyButton, CopyButtonResult, GetAllComponentValues, GetAllComponentValuesResult, GetButton, GetButtonResult, GetClipboardButtonResult, GetComponentValues, GetComponentValuesResult, ListButtonTemplates, NewButton, NewButtonFromComponent, NewButtonFromComponentResult, NewButtonFromTemplate, NewButtonFromTemplateResult, NewButtonResult, PasteButton, PasteButtonResult, RemoveButtonTemplate, RemoveButtonTemplateResult, RemoveComponent, RemoveComponentResult, RemoveComponentValue, RemoveComponentValueResult, SaveButtonAsTemplate, SaveButtonAsTemplateResult, SetButton, SetButtonResult, SetComponentValue, SetComponentValueForKeys, SetComponentValueForKeysResult, SetComponentValueResult, SetComponentValues, SetComponentValuesResult};
+use streamduck_daemon::daemon_data::config::{CloneDeviceConfig, CloneDeviceConfigResult, ExportDeviceBundle, ExportDeviceBundleResult, ExportDeviceConfig, ExportDeviceConfigResult, GetConfigPaths, GetDeviceConfig, GetDeviceConfigResult, ImportDeviceBundle, ImportDeviceBundleResult, ImportDeviceConfig, ImportDeviceConfigResult, ListDeviceConfigsResult, ReloadDeviceConfig, ReloadDeviceConfigResult, ReloadDeviceConfigsResult, SaveDeviceConfig, SaveDeviceConfigResult, SaveDeviceConfigsResult};
+use streamduck_daemon::daemon_data::devices::{AddDevice, AddDeviceResult, Device, DeviceLayout, DumpRenderCache, DumpRenderCacheResult, GetBrightness, GetBrightnessResult, GetDevice, GetDeviceLayout, GetDeviceLayoutResult, GetDeviceResult, GetPressedKeys, GetPressedKeysResult, GetRecentEvents, GetRecentEventsResult, GetRenderStats, GetRenderStatsResult, ListDeviceKinds, ListDevices, RemoveDevice, RemoveDeviceResult, SetBrightness, SetBrightnessResult, SetDeviceLocked, SetDeviceLockedResult, SetDeviceNickname, SetDeviceNicknameResult, SetRenderCacheDisabled, SetRenderCacheDisabledResult};
+use streamduck_daemon::daemon_data::modules::{AddModuleValue, AddModuleValueResult, GetComponentDefinition, GetComponentDefinitionResult, GetComponentOwner, GetComponentOwnerResult, GetModuleCompatibility, GetModuleCompatibilityResult, GetModuleValues, GetModuleValuesResult, ListComponents, ListModuleStatus, ListModules, ModuleStatus, RemoveModuleValue, RemoveModuleValueResult, SetModuleValue, SetModuleValueResult};
+use streamduck_daemon::daemon_data::ops::{CommitAllChanges, CommitAllChangesResult, CommitChangesToConfig, CommitChangesToConfigResult, DoButtonAction, DoButtonActionResult};
+use streamduck_daemon::daemon_data::panels::{DropStackToRoot, DropStackToRootResult, ExampleLayout, ExportScreen, ExportScreenResult, ForciblyPopScreen, ForciblyPopScreenResult, GetButtonImages, GetButtonImagesResult, GetComponentRender, GetComponentRenderResult, GetCurrentScreen, GetCurrentScreenResult, GetDeckComposite, GetDeckCompositeResult, GetLastRendered, GetLastRenderedResult, GetStack, GetStackNames, GetStackNamesResult, GetStackResult, ImportScreen, ImportScreenResult, ListExampleLayouts, LoadExampleLayout, LoadExampleLayoutResult, PopScreen, PopScreenResult, PushScreen, PushScreenResult, RenderPreview, RenderPreviewResult, ReplaceScreen, ReplaceScreenResult, ResetStack, ResetStackResult};
+use streamduck_daemon::daemon_data::{BatchItem, BatchRequest, BatchResponse, PingResult, SocketAPIVersion};
+use streamduck_core::async_trait;
+use streamduck_core::socket::{parse_packet_to_data, send_packet_as_is, SocketData, SocketPacket};
+
+use crate::{check_version_compatibility, SDClientError};
+
+/// Trait that combines both types of asynchronous clients
+pub trait SDAsyncClient: SDAsyncRequestClient + SDAsyncEventClient {}
+
+/// Trait that defines asynchronous event client
+pub trait SDAsyncEventClient: Send + Sync {
+    /// Creates a stream of events from daemon, stream receives all events that arrive after its creation
+    fn event_stream(&self) -> EventStream;
+}
+
+/// Trait that defines asynchronous request client, mirrors [SDSyncRequestClient](crate::SDSyncRequestClient).
+///
+/// All requests are implemented on top of [SDAsyncRequestClient::send_packet]
+#[async_trait]
+pub trait SDAsyncRequestClient: Send + Sync {
+    // Version
+    /// Retrieves version of the daemon socket API
+    async fn version(&self) -> Result<String, SDClientError> {
+        let response: SocketAPIVersion = request_without_data(self).await?;
+
+        Ok(response.version)
+    }
+    /// Checks if daemon is alive, returns time and uptime of the daemon
+    async fn ping(&self) -> Result<PingResult, SDClientError> {
+        let response: PingResult = request_without_data(self).await?;
+
+        Ok(response)
+    }
+    /// Sends multiple requests at once, daemon processes them in order and responds with results in the same order
+    async fn send_batch(&self, requests: Vec<BatchItem>) -> Result<BatchResponse, SDClientError> {
+        let response: BatchResponse = request(self, &BatchRequest {
+            requests
+        }).await?;
+
+        Ok(response)
+    }
+
+    // Device management
+    /// Device list
+    async fn device_list(&self) -> Result<Vec<Device>, SDClientError> {
+        let response: ListDevices = request_without_data(self).await?;
+
+        Ok(response.devices)
+    }
+    /// Lists layouts of all supported device kinds
+    async fn list_device_kinds(&self) -> Result<Vec<DeviceLayout>, SDClientError> {
+        let response: ListDeviceKinds = request_without_data(self).await?;
+
+        Ok(response.kinds)
+    }
+    /// Adds device to managed list
+    async fn get_device(&self, serial_number: &str) -> Result<GetDeviceResult, SDClientError> {
+        let response: GetDeviceResult = request(self, &GetDevice {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Gets layout information of a device
+    async fn get_device_layout(&self, serial_number: &str) -> Result<GetDeviceLayoutResult, SDClientError> {
+        let response: GetDeviceLayoutResult = request(self, &GetDeviceLayout {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Gets render statistics of a device
+    async fn get_render_stats(&self, serial_number: &str) -> Result<GetRenderStatsResult, SDClientError> {
+        let response: GetRenderStatsResult = request(self, &GetRenderStats {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Gets render cache contents of the device without image data, for debugging render hashes
+    async fn dump_render_cache(&self, serial_number: &str) -> Result<DumpRenderCacheResult, SDClientError> {
+        let response: DumpRenderCacheResult = request(self, &DumpRenderCache {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Gets keys that are currently held down on a device
+    async fn get_pressed_keys(&self, serial_number: &str) -> Result<GetPressedKeysResult, SDClientError> {
+        let response: GetPressedKeysResult = request(self, &GetPressedKeys {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Gets recent events of a device, limit of 0 returns all logged events
+    async fn get_recent_events(&self, serial_number: &str, limit: usize) -> Result<GetRecentEventsResult, SDClientError> {
+        let response: GetRecentEventsResult = request(self, &GetRecentEvents {
+            serial_number: serial_number.to_string(),
+            limit
+        }).await?;
+
+        Ok(response)
+    }
+    /// Adds device to managed list
+    async fn add_device(&self, serial_number: &str) -> Result<AddDeviceResult, SDClientError> {
+        let response: AddDeviceResult = request(self, &AddDevice {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Removes device from managed list
+    async fn remove_device(&self, serial_number: &str) -> Result<RemoveDeviceResult, SDClientError> {
+        let response: RemoveDeviceResult = request(self, &RemoveDevice {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+
+    // Device configuration
+    /// Reloads all device configs, all changes will be lost executing this
+    async fn reload_device_configs(&self) -> Result<ReloadDeviceConfigsResult, SDClientError> {
+        let response: ReloadDeviceConfigsResult = request_without_data(self).await?;
+
+        Ok(response)
+    }
+    /// Reloads device config for specific device, all changes will be lost executing this
+    async fn reload_device_config(&self, serial_number: &str) -> Result<ReloadDeviceConfigResult, SDClientError> {
+        let response: ReloadDeviceConfigResult = request(self, &ReloadDeviceConfig {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Saves all device configs
+    async fn save_device_configs(&self) -> Result<SaveDeviceConfigsResult, SDClientError> {
+        let response: SaveDeviceConfigsResult = request_without_data(self).await?;
+
+        Ok(response)
+    }
+    /// Saves device config for specific device
+    async fn save_device_config(&self, serial_number: &str) -> Result<SaveDeviceConfigResult, SDClientError> {
+        let response: SaveDeviceConfigResult = request(self, &SaveDeviceConfig {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+
+    /// Gets device config for a device
+    async fn get_device_config(&self, serial_number: &str) -> Result<GetDeviceConfigResult, SDClientError> {
+        let response: GetDeviceConfigResult = request(self, &GetDeviceConfig {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Lists device configs that are stored on filesystem
+    async fn list_device_configs(&self) -> Result<ListDeviceConfigsResult, SDClientError> {
+        let response: ListDeviceConfigsResult = request_without_data(self).await?;
+
+        Ok(response)
+    }
+
+    /// Imports device config from string
+    async fn import_device_config(&self, serial_number: &str, config: String) -> Result<ImportDeviceConfigResult, SDClientError> {
+        let response: ImportDeviceConfigResult = request(self, &ImportDeviceConfig {
+            serial_number: serial_number.to_string(),
+            config
+        }).await?;
+
+        Ok(response)
+    }
+    /// Exports device config into string
+    async fn export_device_config(&self, serial_number: &str) -> Result<ExportDeviceConfigResult, SDClientError> {
+        let response: ExportDeviceConfigResult = request(self, &ExportDeviceConfig {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Exports device config as a bundle with embedded images and font references, that can be imported on another machine
+    async fn export_device_bundle(&self, serial_number: &str) -> Result<ExportDeviceBundleResult, SDClientError> {
+        let response: ExportDeviceBundleResult = request(self, &ExportDeviceBundle {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Imports device bundle into device, reports fonts that the bundle uses but are not installed
+    async fn import_device_bundle(&self, serial_number: &str, bundle: String) -> Result<ImportDeviceBundleResult, SDClientError> {
+        let response: ImportDeviceBundleResult = request(self, &ImportDeviceBundle {
+            serial_number: serial_number.to_string(),
+            bundle
+        }).await?;
+
+        Ok(response)
+    }
+    /// Copies device config of one device onto another device, existing config of target device only gets overwritten if force is set
+    async fn clone_device_config(&self, from_serial: &str, to_serial: &str, force: bool) -> Result<CloneDeviceConfigResult, SDClientError> {
+        let response: CloneDeviceConfigResult = request(self, &CloneDeviceConfig {
+            from_serial: from_serial.to_string(),
+            to_serial: to_serial.to_string(),
+            force
+        }).await?;
+
+        Ok(response)
+    }
+    /// Gets resolved paths of config file and folders used by the daemon
+    async fn get_config_paths(&self) -> Result<GetConfigPaths, SDClientError> {
+        let response: GetConfigPaths = request_without_data(self).await?;
+
+        Ok(response)
+    }
+
+
+    /// Sets device brightness, usually 0-100, but different for each device
+    async fn set_brightness(&self, serial_number: &str, brightness: u8) -> Result<SetBrightnessResult, SDClientError> {
+        let response: SetBrightnessResult = request(self, &SetBrightness {
+            serial_number: serial_number.to_string(),
+            brightness
+        }).await?;
+
+        Ok(response)
+    }
+    /// Locks or unlocks a device, locked devices reject changes to their layout
+    async fn set_device_locked(&self, serial_number: &str, locked: bool) -> Result<SetDeviceLockedResult, SDClientError> {
+        let response: SetDeviceLockedResult = request(self, &SetDeviceLocked {
+            serial_number: serial_number.to_string(),
+            locked
+        }).await?;
+
+        Ok(response)
+    }
+    /// Sets user assigned name of a device, empty name removes it
+    async fn set_device_nickname(&self, serial_number: &str, nickname: &str) -> Result<SetDeviceNicknameResult, SDClientError> {
+        let response: SetDeviceNicknameResult = request(self, &SetDeviceNickname {
+            serial_number: serial_number.to_string(),
+            nickname: nickname.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Disables or enables render cache of a device, useful for checking if caching causes stale images
+    async fn set_render_cache_disabled(&self, serial_number: &str, disabled: bool) -> Result<SetRenderCacheDisabledResult, SDClientError> {
+        let response: SetRenderCacheDisabledResult = request(self, &SetRenderCacheDisabled {
+            serial_number: serial_number.to_string(),
+            disabled
+        }).await?;
+
+        Ok(response)
+    }
+    /// Gets device brightness that was last set
+    async fn get_brightness(&self, serial_number: &str) -> Result<GetBrightnessResult, SDClientError> {
+        let response: GetBrightnessResult = request(self, &GetBrightness {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+
+    /// Lists saved images on device
+    async fn list_images(&self, serial_number: &str) -> Result<ListImagesResult, SDClientError> {
+        let response: ListImagesResult = request(self, &ListImages {
+            serial_number: serial_number.to_string(),
+            offset: 0,
+            limit: None
+        }).await?;
+
+        Ok(response)
+    }
+    /// Lists page of images sorted by identifier, response contains total amount of images
+    /// Daemons without paging support respond with full image list instead
+    async fn list_images_page(&self, serial_number: &str, offset: usize, limit: usize) -> Result<ListImagesResult, SDClientError> {
+        let response: ListImagesResult = request(self, &ListImages {
+            serial_number: serial_number.to_string(),
+            offset,
+            limit: Some(limit)
+        }).await?;
+
+        Ok(response)
+    }
+    /// Adds new image to device config
+    async fn add_image(&self, serial_number: &str, image_data: &str) -> Result<AddImageResult, SDClientError> {
+        let response: AddImageResult = request(self, &AddImage {
+            serial_number: serial_number.to_string(),
+            image_data: image_data.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Removes image from device config
+    async fn remove_image(&self, serial_number: &str, identifier: &str) -> Result<RemoveImageResult, SDClientError> {
+        let response: RemoveImageResult = request(self, &RemoveImage {
+            serial_number: serial_number.to_string(),
+            image_identifier: identifier.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+
+    /// Gets names of fonts currently loaded by daemon
+    async fn list_fonts(&self) -> Result<Vec<String>, SDClientError> {
+        let response: ListFonts = request_without_data(self).await?;
+
+        Ok(response.font_names)
+    }
+    /// Measures size of text rendered with specified font and scale
+    async fn measure_text(&self, text: &str, font: &str, scale: (f32, f32), letter_spacing: f32, max_width: Option<u32>) -> Result<MeasureTextResult, SDClientError> {
+        let response: MeasureTextResult = request(self, &MeasureText {
+            text: text.to_string(),
+            font: font.to_string(),
+            scale,
+            letter_spacing,
+            max_width
+        }).await?;
+
+        Ok(response)
+    }
+
+    // Module management
+    /// Lists all modules loaded by daemon
+    async fn list_modules(&self) -> Result<Vec<PluginMetadata>, SDClientError> {
+        let response: ListModules = request_without_data(self).await?;
+
+        Ok(response.modules)
+    }
+    /// Lists all modules loaded by daemon together with plugins that failed to load and reasons why they failed
+    async fn list_module_status(&self) -> Result<Vec<ModuleStatus>, SDClientError> {
+        let response: ListModuleStatus = request_without_data(self).await?;
+
+        Ok(response.modules)
+    }
+    /// Lists all components that were introduced by modules
+    async fn list_components(&self) -> Result<HashMap<String, HashMap<String, ComponentDefinition>>, SDClientError> {
+        let response: ListComponents = request_without_data(self).await?;
+
+        Ok(response.components)
+    }
+    /// Gets definition of a single component, mapped by name of the module that defines it
+    async fn get_component_definition(&self, component_name: &str) -> Result<GetComponentDefinitionResult, SDClientError> {
+        let response: GetComponentDefinitionResult = request(self, &GetComponentDefinition {
+            component_name: component_name.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Gets metadata of modules that define a component
+    async fn get_component_owner(&self, component_name: &str) -> Result<GetComponentOwnerResult, SDClientError> {
+        let response: GetComponentOwnerResult = request(self, &GetComponentOwner {
+            component_name: component_name.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Checks compatibility of features used by a module with the daemon
+    async fn get_module_compatibility(&self, module_name: &str) -> Result<GetModuleCompatibilityResult, SDClientError> {
+        let response: GetModuleCompatibilityResult = request(self, &GetModuleCompatibility {
+            module_name: module_name.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+
+    /// Gets module settings
+    async fn get_module_values(&self, module_name: &str) -> Result<GetModuleValuesResult, SDClientError> {
+        let response: GetModuleValuesResult = request(self, &GetModuleValues {
+            module_name: module_name.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Adds element to module setting
+    async fn add_module_value(&self, module_name: &str, path: &str) -> Result<AddModuleValueResult, SDClientError> {
+        let response: AddModuleValueResult = request(self, &AddModuleValue {
+            module_name: module_name.to_string(),
+            path: path.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Removes element from module setting
+    async fn remove_module_value(&self, module_name: &str, path: &str, index: usize) -> Result<RemoveModuleValueResult, SDClientError> {
+        let response: RemoveModuleValueResult = request(self, &RemoveModuleValue {
+            module_name: module_name.to_string(),
+            path: path.to_string(),
+            index
+        }).await?;
+
+        Ok(response)
+    }
+    /// Sets module settings
+    async fn set_module_value(&self, module_name: &str, value: UIPathValue) -> Result<SetModuleValueResult, SDClientError> {
+        let response: SetModuleValueResult = request(self, &SetModuleValue {
+            module_name: module_name.to_string(),
+            value
+        }).await?;
+
+        Ok(response)
+    }
+
+    // Panel management
+    /// Gets stack of a device
+    async fn get_stack(&self, serial_number: &str) -> Result<GetStackResult, SDClientError> {
+        let response: GetStackResult = request(self, &GetStack {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Gets stack names of a device
+    async fn get_stack_names(&self, serial_number: &str) -> Result<GetStackNamesResult, SDClientError> {
+        let response: GetStackNamesResult = request(self, &GetStackNames {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Gets current screen of a device
+    async fn get_current_screen(&self, serial_number: &str) -> Result<GetCurrentScreenResult, SDClientError> {
+        let response: GetCurrentScreenResult = request(self, &GetCurrentScreen {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Gets current images rendered on a device
+    async fn get_button_images(&self, serial_number: &str) -> Result<GetButtonImagesResult, SDClientError> {
+        let response: GetButtonImagesResult = request(self, &GetButtonImages {
+            serial_number: serial_number.to_string(),
+            raw: false,
+            keys: None
+        }).await?;
+
+        Ok(response)
+    }
+    /// Gets images of buttons on current screen of a device as raw RGBA pixels
+    async fn get_button_images_raw(&self, serial_number: &str) -> Result<GetButtonImagesResult, SDClientError> {
+        let response: GetButtonImagesResult = request(self, &GetButtonImages {
+            serial_number: serial_number.to_string(),
+            raw: true,
+            keys: None
+        }).await?;
+
+        Ok(response)
+    }
+    /// Gets images of specified buttons on current screen of a device, useful for requesting images in smaller chunks
+    async fn get_button_images_chunk(&self, serial_number: &str, keys: &[u8], raw: bool) -> Result<GetButtonImagesResult, SDClientError> {
+        let response: GetButtonImagesResult = request(self, &GetButtonImages {
+            serial_number: serial_number.to_string(),
+            raw,
+            keys: Some(keys.to_vec())
+        }).await?;
+
+        Ok(response)
+    }
+    /// Gets image that was last rendered on the key without rendering it again, as base64 png
+    async fn get_last_rendered(&self, serial_number: &str, key: u8) -> Result<GetLastRenderedResult, SDClientError> {
+        let response: GetLastRenderedResult = request(self, &GetLastRendered {
+            serial_number: serial_number.to_string(),
+            key
+        }).await?;
+
+        Ok(response)
+    }
+    /// Renders only what modules of the component draw on the button, as base64 png with transparent background
+    async fn get_component_render(&self, serial_number: &str, key: u8, component_name: &str) -> Result<GetComponentRenderResult, SDClientError> {
+        let response: GetComponentRenderResult = request(self, &GetComponentRender {
+            serial_number: serial_number.to_string(),
+            key,
+            component_name: component_name.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Renders provided button like it would appear on the device, without adding it to the device
+    async fn render_preview(&self, serial_number: &str, key: u8, button: Button) -> Result<RenderPreviewResult, SDClientError> {
+        let response: RenderPreviewResult = request(self, &RenderPreview {
+            serial_number: serial_number.to_string(),
+            key,
+            button
+        }).await?;
+
+        Ok(response)
+    }
+    /// Gets image of the whole device with current button images arranged like on the device
+    async fn get_deck_composite(&self, serial_number: &str) -> Result<GetDeckCompositeResult, SDClientError> {
+        let response: GetDeckCompositeResult = request(self, &GetDeckComposite {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+
+    /// Gets a button from current screen of a device
+    async fn get_button(&self, serial_number: &str, key: u8) -> Result<GetButtonResult, SDClientError> {
+        let response: GetButtonResult = request(self, &GetButton {
+            serial_number: serial_number.to_string(),
+            key
+        }).await?;
+
+        Ok(response)
+    }
+    /// Sets a button on current screen of a device
+    async fn set_button(&self, serial_number: &str, key: u8, button: Button) -> Result<SetButtonResult, SDClientError> {
+        let response: SetButtonResult = request(self, &SetButton {
+            serial_number: serial_number.to_string(),
+            key,
+            button
+        }).await?;
+
+        Ok(response)
+    }
+    /// Clears a button from current screen of a device
+    async fn clear_button(&self, serial_number: &str, key: u8) -> Result<ClearButtonResult, SDClientError> {
+        let response: ClearButtonResult = request(self, &ClearButton {
+            serial_number: serial_number.to_string(),
+            key
+        }).await?;
+
+        Ok(response)
+    }
+
+    /// Returns status of clipboard on daemon
+    async fn clipboard_status(&self) -> Result<ClipboardStatusResult, SDClientError> {
+        let response: ClipboardStatusResult = request_without_data(self).await?;
+
+        Ok(response)
+    }
+    /// Gets button that is currently in clipboard
+    async fn get_clipboard_button(&self) -> Result<GetClipboardButtonResult, SDClientError> {
+        let response: GetClipboardButtonResult = request_without_data(self).await?;
+
+        Ok(response)
+    }
+    /// Copies button into daemon's clipboard
+    async fn copy_button(&self, serial_number: &str, key: u8) -> Result<CopyButtonResult, SDClientError> {
+        let response: CopyButtonResult = request(self, &CopyButton {
+            serial_number: serial_number.to_string(),
+            key
+        }).await?;
+
+        Ok(response)
+    }
+    /// Pastes button from daemon's clipboard
+    async fn paste_button(&self, serial_number: &str, key: u8) -> Result<PasteButtonResult, SDClientError> {
+        let response: PasteButtonResult = request(self, &PasteButton {
+            serial_number: serial_number.to_string(),
+            key
+        }).await?;
+
+        Ok(response)
+    }
+    /// Lists names of button templates
+    async fn list_button_templates(&self) -> Result<Vec<String>, SDClientError> {
+        let response: ListButtonTemplates = request_without_data(self).await?;
+
+        Ok(response.templates)
+    }
+    /// Saves a button as a template
+    async fn save_button_as_template(&self, serial_number: &str, key: u8, template_name: &str) -> Result<SaveButtonAsTemplateResult, SDClientError> {
+        let response: SaveButtonAsTemplateResult = request(self, &SaveButtonAsTemplate {
+            serial_number: serial_number.to_string(),
+            key,
+            template_name: template_name.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Removes a button template
+    async fn remove_button_template(&self, template_name: &str) -> Result<RemoveButtonTemplateResult, SDClientError> {
+        let response: RemoveButtonTemplateResult = request(self, &RemoveButtonTemplate {
+            template_name: template_name.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Creates a new button from a template
+    async fn new_button_from_template(&self, serial_number: &str, key: u8, template_name: &str) -> Result<NewButtonFromTemplateResult, SDClientError> {
+        let response: NewButtonFromTemplateResult = request(self, &NewButtonFromTemplate {
+            serial_number: serial_number.to_string(),
+            key,
+            template_name: template_name.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+
+    /// Creates a new empty button on current screen of a device
+    async fn new_button(&self, serial_number: &str, key: u8) -> Result<NewButtonResult, SDClientError> {
+        let response: NewButtonResult = request(self, &NewButton {
+            serial_number: serial_number.to_string(),
+            key
+        }).await?;
+
+        Ok(response)
+    }
+    /// Creates a button from component on current screen of a device
+    async fn new_button_from_component(&self, serial_number: &str, key: u8, component_name: &str) -> Result<NewButtonFromComponentResult, SDClientError> {
+        let response: NewButtonFromComponentResult = request(self, &NewButtonFromComponent {
+            serial_number: serial_number.to_string(),
+            key,
+            component_name: component_name.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+
+    /// Adds component on a button
+    async fn add_component(&self, serial_number: &str, key: u8, component_name: &str) -> Result<AddComponentResult, SDClientError> {
+        let response: AddComponentResult = request(self, &AddComponent {
+            serial_number: serial_number.to_string(),
+            key,
+            component_name: component_name.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Adds component onto buttons on many keys at once
+    async fn add_component_to_keys(&self, serial_number: &str, keys: Vec<u8>, component_name: &str) -> Result<AddComponentToKeysResult, SDClientError> {
+        let response: AddComponentToKeysResult = request(self, &AddComponentToKeys {
+            serial_number: serial_number.to_string(),
+            keys,
+            component_name: component_name.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Gets component values on a button
+    async fn get_component_values(&self, serial_number: &str, key: u8, component_name: &str) -> Result<GetComponentValuesResult, SDClientError> {
+        let response: GetComponentValuesResult = request(self, &GetComponentValues {
+            serial_number: serial_number.to_string(),
+            key,
+            component_name: component_name.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Gets values of all components on a button
+    async fn get_all_component_values(&self, serial_number: &str, key: u8) -> Result<GetAllComponentValuesResult, SDClientError> {
+        let response: GetAllComponentValuesResult = request(self, &GetAllComponentValues {
+            serial_number: serial_number.to_string(),
+            key
+        }).await?;
+
+        Ok(response)
+    }
+    /// Adds element to component value
+    async fn add_component_value(&self, serial_number: &str, key: u8, component_name: &str, path: &str) -> Result<AddComponentValueResult, SDClientError> {
+        let response: AddComponentValueResult = request(self, &AddComponentValue {
+            serial_number: serial_number.to_string(),
+            key,
+            component_name: component_name.to_string(),
+            path: path.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Removes element from component value
+    async fn remove_component_value(&self, serial_number: &str, key: u8, component_name: &str, path: &str, index: usize) -> Result<RemoveComponentValueResult, SDClientError> {
+        let response: RemoveComponentValueResult = request(self, &RemoveComponentValue {
+            serial_number: serial_number.to_string(),
+            key,
+            component_name: component_name.to_string(),
+            path: path.to_string(),
+            index
+        }).await?;
+
+        Ok(response)
+    }
+    /// Sets value on component value
+    async fn set_component_value(&self, serial_number: &str, key: u8, component_name: &str, value: UIPathValue) -> Result<SetComponentValueResult, SDClientError> {
+        let response: SetComponentValueResult = request(self, &SetComponentValue {
+            serial_number: serial_number.to_string(),
+            key,
+            component_name: component_name.to_string(),
+            value
+        }).await?;
+
+        Ok(response)
+    }
+    /// Sets same component value on buttons on many keys at once
+    async fn set_component_value_for_keys(&self, serial_number: &str, keys: Vec<u8>, component_name: &str, value: UIPathValue) -> Result<SetComponentValueForKeysResult, SDClientError> {
+        let response: SetComponentValueForKeysResult = request(self, &SetComponentValueForKeys {
+            serial_number: serial_number.to_string(),
+            keys,
+            component_name: component_name.to_string(),
+            value
+        }).await?;
+
+        Ok(response)
+    }
+    /// Sets multiple values on component at once
+    async fn set_component_values(&self, serial_number: &str, key: u8, component_name: &str, values: Vec<UIPathValue>) -> Result<SetComponentValuesResult, SDClientError> {
+        let response: SetComponentValuesResult = request(self, &SetComponentValues {
+            serial_number: serial_number.to_string(),
+            key,
+            component_name: component_name.to_string(),
+            values
+        }).await?;
+
+        Ok(response)
+    }
+    /// Removes component from a button
+    async fn remove_component(&self, serial_number: &str, key: u8, component_name: &str) -> Result<RemoveComponentResult, SDClientError> {
+        let response: RemoveComponentResult = request(self, &RemoveComponent {
+            serial_number: serial_number.to_string(),
+            key,
+            component_name: component_name.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+
+    /// Pushes a new screen on a device
+    async fn push_screen(&self, serial_number: &str, screen: RawButtonPanel) -> Result<PushScreenResult, SDClientError> {
+        let response: PushScreenResult = request(self, &PushScreen {
+            serial_number: serial_number.to_string(),
+            screen
+        }).await?;
+
+        Ok(response)
+    }
+    /// Exports a screen at specified stack index
+    async fn export_screen(&self, serial_number: &str, stack_index: usize) -> Result<ExportScreenResult, SDClientError> {
+        let response: ExportScreenResult = request(self, &ExportScreen {
+            serial_number: serial_number.to_string(),
+            stack_index
+        }).await?;
+
+        Ok(response)
+    }
+    /// Imports a screen exported with export_screen and pushes it into the stack
+    async fn import_screen(&self, serial_number: &str, screen: &str) -> Result<ImportScreenResult, SDClientError> {
+        let response: ImportScreenResult = request(self, &ImportScreen {
+            serial_number: serial_number.to_string(),
+            screen: screen.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Pops a screen from a device
+    async fn pop_screen(&self, serial_number: &str) -> Result<PopScreenResult, SDClientError> {
+        let response: PopScreenResult = request(self, &PopScreen {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Pops a screen from a device, even if it's only one remaining
+    async fn forcibly_pop_screen(&self, serial_number: &str) -> Result<ForciblyPopScreenResult, SDClientError> {
+        let response: ForciblyPopScreenResult = request(self, &ForciblyPopScreen {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Replaces current screen on a device
+    async fn replace_screen(&self, serial_number: &str, screen: RawButtonPanel) -> Result<ReplaceScreenResult, SDClientError> {
+        let response: ReplaceScreenResult = request(self, &ReplaceScreen {
+            serial_number: serial_number.to_string(),
+            screen
+        }).await?;
+
+        Ok(response)
+    }
+    /// Resets stack and sets provided screen as root screen
+    async fn reset_stack(&self, serial_number: &str, screen: RawButtonPanel) -> Result<ResetStackResult, SDClientError> {
+        let response: ResetStackResult = request(self, &ResetStack {
+            serial_number: serial_number.to_string(),
+            screen
+        }).await?;
+
+        Ok(response)
+    }
+    /// Resets stack of a device with one of the example layouts built into the daemon
+    async fn load_example_layout(&self, serial_number: &str, name: &str) -> Result<LoadExampleLayoutResult, SDClientError> {
+        let response: LoadExampleLayoutResult = request(self, &LoadExampleLayout {
+            serial_number: serial_number.to_string(),
+            name: name.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Lists example layouts built into the daemon
+    async fn list_example_layouts(&self) -> Result<Vec<ExampleLayout>, SDClientError> {
+        let response: ListExampleLayouts = request_without_data(self).await?;
+
+        Ok(response.layouts)
+    }
+    /// Drops stack to root screen
+    async fn drop_stack_to_root(&self, serial_number: &str) -> Result<DropStackToRootResult, SDClientError> {
+        let response: DropStackToRootResult = request(self, &DropStackToRoot {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+
+    /// Commits all changes to stack to device config, should be called after each change/sequence of changes, otherwise all changes will be lost on reconnect
+    async fn commit_changes(&self, serial_number: &str) -> Result<CommitChangesToConfigResult, SDClientError> {
+        let response: CommitChangesToConfigResult = request(self, &CommitChangesToConfig {
+            serial_number: serial_number.to_string()
+        }).await?;
+
+        Ok(response)
+    }
+    /// Commits changes of all connected devices to their device configs, optionally saving the configs
+    async fn commit_all_changes(&self, save: bool) -> Result<CommitAllChangesResult, SDClientError> {
+        let response: CommitAllChangesResult = request(self, &CommitAllChanges {
+            save
+        }).await?;
+
+        Ok(response)
+    }
+
+    /// Simulate a press on a button on current screen for a device
+    async fn do_button_action(&self, serial_number: &str, key: u8) -> Result<DoButtonActionResult, SDClientError> {
+        let response: DoButtonActionResult = request(self, &DoButtonAction {
+            serial_number: serial_number.to_string(),
+            key
+        }).await?;
+
+        Ok(response)
+    }
+
+    /// Sends a custom packet to daemon and returns response, for use with plugins that utilize socket functionality
+    async fn send_packet(&self, packet: SocketPacket) -> Result<SocketPacket, SDClientError>;
+    /// Sends a custom packet to daemon and returns response, for use with plugins that utilize socket functionality
+    async fn send_packet_without_response(&self, packet: SocketPacket) -> Result<(), SDClientError>;
+}
+
+/// Sends request to daemon and parses the response
+async fn request<Req, Res, C>(client: &C, request: &Req) -> Result<Res, SDClientError>
+    where
+        Req: SocketData + Serialize + Sync,
+        Res: SocketData + DeserializeOwned,
+        C: SDAsyncRequestClient + ?Sized
+{
+    let packet = client.send_packet(SocketPacket {
+        ty: Req::NAME.to_string(),
+        requester: None,
+        data: Some(serde_json::to_value(request)?)
+    }).await?;
+
+    Ok(parse_packet_to_data(&packet)?)
+}
+
+/// Sends request without any data to daemon and parses the response
+async fn request_without_data<Res, C>(client: &C) -> Result<Res, SDClientError>
+    where
+        Res: SocketData + DeserializeOwned,
+        C: SDAsyncRequestClient + ?Sized
+{
+    let packet = client.send_packet(SocketPacket {
+        ty: Res::NAME.to_string(),
+        requester: None,
+        data: None
+    }).await?;
+
+    Ok(parse_packet_to_data(&packet)?)
+}
+
+/// Stream of events coming from daemon
+pub struct EventStream {
+    receiver: UnboundedReceiver<SDGlobalEvent>
+}
+
+impl Stream for EventStream {
+    type Item = SDGlobalEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+type PendingRequests = Arc<StdMutex<HashMap<String, oneshot::Sender<SocketPacket>>>>;
+type EventSenders = Arc<StdMutex<Vec<UnboundedSender<SDGlobalEvent>>>>;
+
+/// Tokio based Streamduck client, uses unix domain socket on Unix and named pipe on Windows.
+///
+/// Responses are matched to requests by a background task, so requests can be sent concurrently
+pub struct AsyncClient {
+    writer: Mutex<Box<dyn AsyncWrite + Send + Unpin>>,
+    pending: PendingRequests,
+    event_senders: EventSenders
+}
+
+impl AsyncClient {
+    fn from_connection<T: AsyncRead + AsyncWrite + Send + 'static>(connection: T) -> AsyncClient {
+        let (reader, writer) = tokio::io::split(connection);
+
+        let client = AsyncClient {
+            writer: Mutex::new(Box::new(writer)),
+            pending: Default::default(),
+            event_senders: Default::default()
+        };
+
+        tokio::spawn(read_packets(reader, client.pending.clone(), client.event_senders.clone()));
+
+        client
+    }
+
+    async fn make_client<T: AsyncRead + AsyncWrite + Send + 'static>(connection: T) -> Result<AsyncClient, SDClientError> {
+        let client = AsyncClient::from_connection(connection);
+
+        check_version_compatibility(&client.version().await?)?;
+
+        Ok(client)
+    }
+
+    /// Initializes client using unix domain socket, fails if daemon uses incompatible socket API version
+    #[cfg(target_family = "unix")]
+    pub async fn new() -> Result<Arc<dyn SDAsyncClient>, SDClientError> {
+        let connection = tokio::net::UnixStream::connect(streamduck_daemon::UNIX_SOCKET_PATH).await?;
+
+        Ok(Arc::new(AsyncClient::make_client(connection).await?))
+    }
+
+    /// Initializes client using named pipe, fails if daemon uses incompatible socket API version
+    #[cfg(target_family = "windows")]
+    pub async fn new() -> Result<Arc<dyn SDAsyncClient>, SDClientError> {
+        let connection = tokio::net::windows::named_pipe::ClientOptions::new().open(streamduck_daemon::WINDOWS_PIPE_NAME)?;
+
+        Ok(Arc::new(AsyncClient::make_client(connection).await?))
+    }
+}
+
+/// Reads packets from daemon, passing responses to requests that are waiting for them and events to event streams
+async fn read_packets<R: AsyncRead + Unpin>(reader: R, pending: PendingRequests, event_senders: EventSenders) {
+    let mut reader = BufReader::new(reader);
+
+    loop {
+        let mut byte_array = vec![];
+
+        match reader.read_until(0x4, &mut byte_array).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+
+        let packet = String::from_utf8(byte_array).ok()
+            .and_then(|line| serde_json::from_str::<SocketPacket>(line.replace("\u{0004}", "").trim()).ok());
+
+        if let Some(packet) = packet {
+            let waiting_request = packet.requester.as_ref()
+                .and_then(|requester| pending.lock().unwrap().remove(requester));
+
+            if let Some(sender) = waiting_request {
+                sender.send(packet).ok();
+            } else if let Some(data) = packet.data {
+                if let Ok(event) = serde_json::from_value::<SDGlobalEvent>(data) {
+                    event_senders.lock().unwrap().retain(|sender| sender.send(event.clone()).is_ok());
+                }
+            }
+        }
+    }
+
+    // Dropping senders of pending requests, so they fail instead of waiting forever
+    pending.lock().unwrap().clear();
+    event_senders.lock().unwrap().clear();
+}
+
+impl SDAsyncClient for AsyncClient {}
+
+impl SDAsyncEventClient for AsyncClient {
+    fn event_stream(&self) -> EventStream {
+        let (sender, receiver) = unbounded_channel();
+        self.event_senders.lock().unwrap().push(sender);
+
+        EventStream {
+            receiver
+        }
+    }
+}
+
+#[async_trait]
+impl SDAsyncRequestClient for AsyncClient {
+    async fn send_packet(&self, mut packet: SocketPacket) -> Result<SocketPacket, SDClientError> {
+        let id = rand::thread_rng().sample_iter(&Alphanumeric).take(20).map(char::from).collect::<String>();
+        packet.requester = Some(id.clone());
+
+        let (sender, receiver) = oneshot::channel();
+        self.pending.lock().unwrap().insert(id.clone(), sender);
+
+        if let Err(err) = send_packet_as_is(self.writer.lock().await.as_mut(), packet).await {
+            self.pending.lock().unwrap().remove(&id);
+            return Err(err.into());
+        }
+
+        receiver.await.map_err(|_| SDClientError::Custom("Connection to daemon was closed".to_string()))
+    }
+
+    async fn send_packet_without_response(&self, packet: SocketPacket) -> Result<(), SDClientError> {
+        send_packet_as_is(self.writer.lock().await.as_mut(), packet).await?;
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::io::Error;
 use std::string::FromUtf8Error;
 use std::sync::Arc;
+use std::time::Duration;
 
 use streamduck_core::core::button::Button;
 use streamduck_core::core::RawButtonPanel;
@@ -9,14 +10,16 @@ use streamduck_core::modules::components::{ComponentDefinition, UIPathValue};
 use streamduck_core::modules::events::SDGlobalEvent;
 use streamduck_core::modules::PluginMetadata;
 use streamduck_core::socket::{SocketError, SocketPacket};
+use streamduck_core::versions::SOCKET_API;
 pub use streamduck_daemon as daemon;
-use streamduck_daemon::daemon_data::assets::{AddImageResult, ListImagesResult, RemoveImageResult};
-use streamduck_daemon::daemon_data::buttons::{AddComponentResult, AddComponentValueResult, ClearButtonResult, ClipboardStatusResult, CopyButtonResult, GetButtonResult, GetComponentValuesResult, NewButtonFromComponentResult, NewButtonResult, PasteButtonResult, RemoveComponentResult, RemoveComponentValueResult, SetButtonResult, SetComponentValueResult};
-use streamduck_daemon::daemon_data::config::{ExportDeviceConfigResult, GetDeviceConfigResult, ImportDeviceConfigResult, ReloadDeviceConfigResult, ReloadDeviceConfigsResult, SaveDeviceConfigResult, SaveDeviceConfigsResult};
-use streamduck_daemon::daemon_data::devices::{AddDeviceResult, Device, GetDeviceResult, RemoveDeviceResult, SetBrightnessResult};
-use streamduck_daemon::daemon_data::modules::{AddModuleValueResult, GetModuleValuesResult, RemoveModuleValueResult, SetModuleValueResult};
-use streamduck_daemon::daemon_data::ops::{CommitChangesToConfigResult, DoButtonActionResult};
-use streamduck_daemon::daemon_data::panels::{DropStackToRootResult, ForciblyPopScreenResult, GetButtonImagesResult, GetCurrentScreenResult, GetStackNamesResult, GetStackResult, PopScreenResult, PushScreenResult, ReplaceScreenResult, ResetStackResult};
+use streamduck_daemon::daemon_data::{BatchItem, BatchResponse, PingResult};
+use streamduck_daemon::daemon_data::assets::{AddImageResult, ListImagesResult, MeasureTextResult, RemoveImageResult};
+use streamduck_daemon::daemon_data::buttons::{AddComponentResult, AddComponentToKeysResult, AddComponentValueResult, ClearButtonResult, ClipboardStatusResult, CopyButtonResult, GetAllComponentValuesResult, GetButtonResult, GetClipboardButtonResult, GetComponentValuesResult, NewButtonFromComponentResult, NewButtonFromTemplateResult, NewButtonResult, PasteButtonResult, RemoveButtonTemplateResult, RemoveComponentResult, RemoveComponentValueResult, SaveButtonAsTemplateResult, SetButtonResult, SetComponentValueForKeysResult, SetComponentValueResult, SetComponentValuesResult};
+use streamduck_daemon::daemon_data::config::{CloneDeviceConfigResult, ExportDeviceBundleResult, ExportDeviceConfigResult, GetConfigPaths, GetDeviceConfigResult, ImportDeviceBundleResult, ImportDeviceConfigResult, ListDeviceConfigsResult, ReloadDeviceConfigResult, ReloadDeviceConfigsResult, SaveDeviceConfigResult, SaveDeviceConfigsResult};
+use streamduck_daemon::daemon_data::devices::{AddDeviceResult, Device, DeviceLayout, DumpRenderCacheResult, GetBrightnessResult, GetDeviceLayoutResult, GetDeviceResult, GetPressedKeysResult, GetRecentEventsResult, GetRenderStatsResult, RemoveDeviceResult, SetBrightnessResult, SetDeviceLockedResult, SetDeviceNicknameResult, SetRenderCacheDisabledResult};
+use streamduck_daemon::daemon_data::modules::{AddModuleValueResult, GetComponentDefinitionResult, GetComponentOwnerResult, GetModuleCompatibilityResult, GetModuleValuesResult, ModuleStatus, RemoveModuleValueResult, SetModuleValueResult};
+use streamduck_daemon::daemon_data::ops::{CommitAllChangesResult, CommitChangesToConfigResult, DoButtonActionResult};
+use streamduck_daemon::daemon_data::panels::{DropStackToRootResult, ExampleLayout, ExportScreenResult, ForciblyPopScreenResult, GetButtonImagesResult, GetComponentRenderResult, GetCurrentScreenResult, GetDeckCompositeResult, GetLastRenderedResult, GetStackNamesResult, GetStackResult, ImportScreenResult, LoadExampleLayoutResult, PopScreenResult, PushScreenResult, RenderPreviewResult, ReplaceScreenResult, ResetStackResult};
 
 #[cfg(target_family = "unix")]
 pub mod unix;
@@ -26,6 +29,9 @@ pub mod windows;
 
 pub mod util;
 
+/// Asynchronous client
+pub mod async_client;
+
 /// Trait that combines both types of clients
 pub trait SDSyncClient: SDSyncUpcastRequestClient + SDSyncUpcastEventClient {}
 
@@ -43,6 +49,8 @@ pub trait SDSyncUpcastEventClient: SDSyncEventClient {
 pub trait SDSyncEventClient: Send + Sync {
     /// Retrieves an event from daemon, depending on implementation might block
     fn get_event(&self) -> Result<SDGlobalEvent, SDClientError>;
+    /// Retrieves an event from daemon, returns None if no event arrived within provided timeout
+    fn try_get_event(&self, timeout: Duration) -> Result<Option<SDGlobalEvent>, SDClientError>;
 }
 
 /// Trait that defines synchronous request client
@@ -50,12 +58,28 @@ pub trait SDSyncRequestClient: Send + Sync {
     // Version
     /// Retrieves version of the daemon socket API
     fn version(&self) -> Result<String, SDClientError>;
+    /// Checks if daemon is alive, returns time and uptime of the daemon
+    fn ping(&self) -> Result<PingResult, SDClientError>;
+    /// Sends multiple requests at once, daemon processes them in order and responds with results in the same order
+    fn send_batch(&self, requests: Vec<BatchItem>) -> Result<BatchResponse, SDClientError>;
 
     // Device management
     /// Device list
     fn device_list(&self) -> Result<Vec<Device>, SDClientError>;
+    /// Lists layouts of all supported device kinds
+    fn list_device_kinds(&self) -> Result<Vec<DeviceLayout>, SDClientError>;
     /// Adds device to managed list
     fn get_device(&self, serial_number: &str) -> Result<GetDeviceResult, SDClientError>;
+    /// Gets layout information of a device
+    fn get_device_layout(&self, serial_number: &str) -> Result<GetDeviceLayoutResult, SDClientError>;
+    /// Gets render statistics of a device
+    fn get_render_stats(&self, serial_number: &str) -> Result<GetRenderStatsResult, SDClientError>;
+    /// Gets render cache contents of the device without image data, for debugging render hashes
+    fn dump_render_cache(&self, serial_number: &str) -> Result<DumpRenderCacheResult, SDClientError>;
+    /// Gets keys that are currently held down on a device
+    fn get_pressed_keys(&self, serial_number: &str) -> Result<GetPressedKeysResult, SDClientError>;
+    /// Gets recent events of a device, limit of 0 returns all logged events
+    fn get_recent_events(&self, serial_number: &str, limit: usize) -> Result<GetRecentEventsResult, SDClientError>;
     /// Adds device to managed list
     fn add_device(&self, serial_number: &str) -> Result<AddDeviceResult, SDClientError>;
     /// Removes device from managed list
@@ -73,18 +97,39 @@ pub trait SDSyncRequestClient: Send + Sync {
 
     /// Gets device config for a device
     fn get_device_config(&self, serial_number: &str) -> Result<GetDeviceConfigResult, SDClientError>;
+    /// Lists device configs that are stored on filesystem
+    fn list_device_configs(&self) -> Result<ListDeviceConfigsResult, SDClientError>;
 
     /// Imports device config from string
     fn import_device_config(&self, serial_number: &str, config: String) -> Result<ImportDeviceConfigResult, SDClientError>;
     /// Exports device config into string
     fn export_device_config(&self, serial_number: &str) -> Result<ExportDeviceConfigResult, SDClientError>;
+    /// Exports device config as a bundle with embedded images and font references, that can be imported on another machine
+    fn export_device_bundle(&self, serial_number: &str) -> Result<ExportDeviceBundleResult, SDClientError>;
+    /// Imports device bundle into device, reports fonts that the bundle uses but are not installed
+    fn import_device_bundle(&self, serial_number: &str, bundle: String) -> Result<ImportDeviceBundleResult, SDClientError>;
+    /// Copies device config of one device onto another device, existing config of target device only gets overwritten if force is set
+    fn clone_device_config(&self, from_serial: &str, to_serial: &str, force: bool) -> Result<CloneDeviceConfigResult, SDClientError>;
+    /// Gets resolved paths of config file and folders used by the daemon
+    fn get_config_paths(&self) -> Result<GetConfigPaths, SDClientError>;
 
 
     /// Sets device brightness, usually 0-100, but different for each device
     fn set_brightness(&self, serial_number: &str, brightness: u8) -> Result<SetBrightnessResult, SDClientError>;
+    /// Locks or unlocks a device, locked devices reject changes to their layout
+    fn set_device_locked(&self, serial_number: &str, locked: bool) -> Result<SetDeviceLockedResult, SDClientError>;
+    /// Sets user assigned name of a device, empty name removes it
+    fn set_device_nickname(&self, serial_number: &str, nickname: &str) -> Result<SetDeviceNicknameResult, SDClientError>;
+    /// Disables or enables render cache of a device, useful for checking if caching causes stale images
+    fn set_render_cache_disabled(&self, serial_number: &str, disabled: bool) -> Result<SetRenderCacheDisabledResult, SDClientError>;
+    /// Gets device brightness that was last set
+    fn get_brightness(&self, serial_number: &str) -> Result<GetBrightnessResult, SDClientError>;
 
     /// Lists saved images on device
     fn list_images(&self, serial_number: &str) -> Result<ListImagesResult, SDClientError>;
+    /// Lists page of images sorted by identifier, response contains total amount of images
+    /// Daemons without paging support respond with full image list instead
+    fn list_images_page(&self, serial_number: &str, offset: usize, limit: usize) -> Result<ListImagesResult, SDClientError>;
     /// Adds new image to device config
     fn add_image(&self, serial_number: &str, image_data: &str) -> Result<AddImageResult, SDClientError>;
     /// Removes image from device config
@@ -93,11 +138,22 @@ pub trait SDSyncRequestClient: Send + Sync {
     /// Gets names of fonts currently loaded by daemon
     fn list_fonts(&self) -> Result<Vec<String>, SDClientError>;
 
+    /// Measures size of text rendered with specified font and scale
+    fn measure_text(&self, text: &str, font: &str, scale: (f32, f32), letter_spacing: f32, max_width: Option<u32>) -> Result<MeasureTextResult, SDClientError>;
+
     // Module management
     /// Lists all modules loaded by daemon
     fn list_modules(&self) -> Result<Vec<PluginMetadata>, SDClientError>;
+    /// Lists all modules loaded by daemon together with plugins that failed to load and reasons why they failed
+    fn list_module_status(&self) -> Result<Vec<ModuleStatus>, SDClientError>;
     /// Lists all components that were introduced by modules
     fn list_components(&self) -> Result<HashMap<String, HashMap<String, ComponentDefinition>>, SDClientError>;
+    /// Gets definition of a single component, mapped by name of the module that defines it
+    fn get_component_definition(&self, component_name: &str) -> Result<GetComponentDefinitionResult, SDClientError>;
+    /// Gets metadata of modules that define a component
+    fn get_component_owner(&self, component_name: &str) -> Result<GetComponentOwnerResult, SDClientError>;
+    /// Checks compatibility of features used by a module with the daemon
+    fn get_module_compatibility(&self, module_name: &str) -> Result<GetModuleCompatibilityResult, SDClientError>;
 
     /// Gets module settings
     fn get_module_values(&self, module_name: &str) -> Result<GetModuleValuesResult, SDClientError>;
@@ -117,6 +173,18 @@ pub trait SDSyncRequestClient: Send + Sync {
     fn get_current_screen(&self, serial_number: &str) -> Result<GetCurrentScreenResult, SDClientError>;
     /// Gets current images rendered on a device
     fn get_button_images(&self, serial_number: &str) -> Result<GetButtonImagesResult, SDClientError>;
+    /// Gets images of buttons on current screen of a device as raw RGBA pixels
+    fn get_button_images_raw(&self, serial_number: &str) -> Result<GetButtonImagesResult, SDClientError>;
+    /// Gets images of specified buttons on current screen of a device, useful for requesting images in smaller chunks
+    fn get_button_images_chunk(&self, serial_number: &str, keys: &[u8], raw: bool) -> Result<GetButtonImagesResult, SDClientError>;
+    /// Gets image that was last rendered on the key without rendering it again, as base64 png
+    fn get_last_rendered(&self, serial_number: &str, key: u8) -> Result<GetLastRenderedResult, SDClientError>;
+    /// Renders only what modules of the component draw on the button, as base64 png with transparent background
+    fn get_component_render(&self, serial_number: &str, key: u8, component_name: &str) -> Result<GetComponentRenderResult, SDClientError>;
+    /// Renders provided button like it would appear on the device, without adding it to the device
+    fn render_preview(&self, serial_number: &str, key: u8, button: Button) -> Result<RenderPreviewResult, SDClientError>;
+    /// Gets image of the whole device with current button images arranged like on the device
+    fn get_deck_composite(&self, serial_number: &str) -> Result<GetDeckCompositeResult, SDClientError>;
 
     /// Gets a button from current screen of a device
     fn get_button(&self, serial_number: &str, key: u8) -> Result<GetButtonResult, SDClientError>;
@@ -127,10 +195,20 @@ pub trait SDSyncRequestClient: Send + Sync {
 
     /// Returns status of clipboard on daemon
     fn clipboard_status(&self) -> Result<ClipboardStatusResult, SDClientError>;
+    /// Gets button that is currently in clipboard
+    fn get_clipboard_button(&self) -> Result<GetClipboardButtonResult, SDClientError>;
     /// Copies button into daemon's clipboard
     fn copy_button(&self, serial_number: &str, key: u8) -> Result<CopyButtonResult, SDClientError>;
     /// Pastes button from daemon's clipboard
     fn paste_button(&self, serial_number: &str, key: u8) -> Result<PasteButtonResult, SDClientError>;
+    /// Lists names of button templates
+    fn list_button_templates(&self) -> Result<Vec<String>, SDClientError>;
+    /// Saves a button as a template
+    fn save_button_as_template(&self, serial_number: &str, key: u8, template_name: &str) -> Result<SaveButtonAsTemplateResult, SDClientError>;
+    /// Removes a button template
+    fn remove_button_template(&self, template_name: &str) -> Result<RemoveButtonTemplateResult, SDClientError>;
+    /// Creates a new button from a template
+    fn new_button_from_template(&self, serial_number: &str, key: u8, template_name: &str) -> Result<NewButtonFromTemplateResult, SDClientError>;
 
     /// Creates a new empty button on current screen of a device
     fn new_button(&self, serial_number: &str, key: u8) -> Result<NewButtonResult, SDClientError>;
@@ -139,19 +217,31 @@ pub trait SDSyncRequestClient: Send + Sync {
 
     /// Adds component on a button
     fn add_component(&self, serial_number: &str, key: u8, component_name: &str) -> Result<AddComponentResult, SDClientError>;
+    /// Adds component onto buttons on many keys at once
+    fn add_component_to_keys(&self, serial_number: &str, keys: Vec<u8>, component_name: &str) -> Result<AddComponentToKeysResult, SDClientError>;
     /// Gets component values on a button
     fn get_component_values(&self, serial_number: &str, key: u8, component_name: &str) -> Result<GetComponentValuesResult, SDClientError>;
+    /// Gets values of all components on a button
+    fn get_all_component_values(&self, serial_number: &str, key: u8) -> Result<GetAllComponentValuesResult, SDClientError>;
     /// Adds element to component value
     fn add_component_value(&self, serial_number: &str, key: u8, component_name: &str, path: &str) -> Result<AddComponentValueResult, SDClientError>;
     /// Removes element from component value
     fn remove_component_value(&self, serial_number: &str, key: u8, component_name: &str, path: &str, index: usize) -> Result<RemoveComponentValueResult, SDClientError>;
     /// Sets value on component value
     fn set_component_value(&self, serial_number: &str, key: u8, component_name: &str, value: UIPathValue) -> Result<SetComponentValueResult, SDClientError>;
+    /// Sets same component value on buttons on many keys at once
+    fn set_component_value_for_keys(&self, serial_number: &str, keys: Vec<u8>, component_name: &str, value: UIPathValue) -> Result<SetComponentValueForKeysResult, SDClientError>;
+    /// Sets multiple values on component at once
+    fn set_component_values(&self, serial_number: &str, key: u8, component_name: &str, values: Vec<UIPathValue>) -> Result<SetComponentValuesResult, SDClientError>;
     /// Removes component from a button
     fn remove_component(&self, serial_number: &str, key: u8, component_name: &str) -> Result<RemoveComponentResult, SDClientError>;
 
     /// Pushes a new screen on a device
     fn push_screen(&self, serial_number: &str, screen: RawButtonPanel) -> Result<PushScreenResult, SDClientError>;
+    /// Exports a screen at specified stack index
+    fn export_screen(&self, serial_number: &str, stack_index: usize) -> Result<ExportScreenResult, SDClientError>;
+    /// Imports a screen exported with export_screen and pushes it into the stack
+    fn import_screen(&self, serial_number: &str, screen: &str) -> Result<ImportScreenResult, SDClientError>;
     /// Pops a screen from a device
     fn pop_screen(&self, serial_number: &str) -> Result<PopScreenResult, SDClientError>;
     /// Pops a screen from a device, even if it's only one remaining
@@ -160,11 +250,17 @@ pub trait SDSyncRequestClient: Send + Sync {
     fn replace_screen(&self, serial_number: &str, screen: RawButtonPanel) -> Result<ReplaceScreenResult, SDClientError>;
     /// Resets stack and sets provided screen as root screen
     fn reset_stack(&self, serial_number: &str, screen: RawButtonPanel) -> Result<ResetStackResult, SDClientError>;
+    /// Resets stack of a device with one of the example layouts built into the daemon
+    fn load_example_layout(&self, serial_number: &str, name: &str) -> Result<LoadExampleLayoutResult, SDClientError>;
+    /// Lists example layouts built into the daemon
+    fn list_example_layouts(&self) -> Result<Vec<ExampleLayout>, SDClientError>;
     /// Drops stack to root screen
     fn drop_stack_to_root(&self, serial_number: &str) -> Result<DropStackToRootResult, SDClientError>;
 
     /// Commits all changes to stack to device config, should be called after each change/sequence of changes, otherwise all changes will be lost on reconnect
     fn commit_changes(&self, serial_number: &str) -> Result<CommitChangesToConfigResult, SDClientError>;
+    /// Commits changes of all connected devices to their device configs, optionally saving the configs
+    fn commit_all_changes(&self, save: bool) -> Result<CommitAllChangesResult, SDClientError>;
 
     /// Simulate a press on a button on current screen for a device
     fn do_button_action(&self, serial_number: &str, key: u8) -> Result<DoButtonActionResult, SDClientError>;
@@ -182,9 +278,46 @@ pub enum SDClientError {
     SerializeError(serde_json::Error),
     SocketError(streamduck_core::socket::SocketError),
     UTF8Error(std::string::FromUtf8Error),
+    /// Daemon uses socket API version that client doesn't understand
+    IncompatibleVersion {
+        client: String,
+        daemon: String
+    },
     Custom(String)
 }
 
+/// Checks if daemon's socket API version is compatible with the client.
+///
+/// Major versions must match and daemon must not be older than the client,
+/// newer daemon is accepted with a warning
+pub fn check_version_compatibility(daemon_version: &str) -> Result<(), SDClientError> {
+    fn parse_version(version: &str) -> Option<(u32, u32)> {
+        let mut parts = version.trim().split('.').map(|x| x.parse::<u32>());
+
+        let major = parts.next()?.ok()?;
+        let minor = parts.next().unwrap_or(Ok(0)).ok()?;
+
+        Some((major, minor))
+    }
+
+    let client_version = SOCKET_API.1;
+
+    match (parse_version(client_version), parse_version(daemon_version)) {
+        (Some((client_major, client_minor)), Some((daemon_major, daemon_minor))) if client_major == daemon_major && daemon_minor >= client_minor => {
+            if client_version != daemon_version {
+                log::warn!("Version of client library doesn't match daemon API version. Client: {}, Daemon: {}", client_version, daemon_version);
+            }
+
+            Ok(())
+        }
+
+        _ => Err(SDClientError::IncompatibleVersion {
+            client: client_version.to_string(),
+            daemon: daemon_version.to_string()
+        })
+    }
+}
+
 impl From<std::io::Error> for SDClientError {
     fn from(err: Error) -> Self {
         SDClientError::WriteError(err)
@@ -3,6 +3,7 @@ use std::io::BufReader;
 use std::ops::DerefMut;
 use std::os::unix::net::UnixStream;
 use std::sync::{Arc, RwLock, RwLockWriteGuard};
+use std::time::{Duration, Instant};
 use rand::distributions::Alphanumeric;
 use rand::Rng;
 
@@ -11,20 +12,19 @@ use streamduck_core::core::RawButtonPanel;
 use streamduck_core::modules::components::{ComponentDefinition, UIPathValue};
 use streamduck_core::modules::events::SDGlobalEvent;
 use streamduck_core::modules::PluginMetadata;
-use streamduck_core::versions::SOCKET_API;
 use streamduck_core::socket::{send_packet_as_is_sync, SocketPacket};
-use streamduck_daemon::daemon_data::assets::{AddImage, AddImageResult, ListFonts, ListImages, ListImagesResult, RemoveImage, RemoveImageResult};
-use streamduck_daemon::daemon_data::buttons::{AddComponent, AddComponentResult, AddComponentValue, AddComponentValueResult, ClearButton, ClearButtonResult, ClipboardStatusResult, CopyButton, CopyButtonResult, GetButton, GetButtonResult, GetComponentValues, GetComponentValuesResult, NewButton, NewButtonFromComponent, NewButtonFromComponentResult, NewButtonResult, PasteButton, PasteButtonResult, RemoveComponent, RemoveComponentResult, RemoveComponentValue, RemoveComponentValueResult, SetButton, SetButtonResult, SetComponentValue, SetComponentValueResult};
-use streamduck_daemon::daemon_data::config::{ExportDeviceConfig, ExportDeviceConfigResult, GetDeviceConfig, GetDeviceConfigResult, ImportDeviceConfig, ImportDeviceConfigResult, ReloadDeviceConfig, ReloadDeviceConfigResult, ReloadDeviceConfigsResult, SaveDeviceConfig, SaveDeviceConfigResult, SaveDeviceConfigsResult};
-use streamduck_daemon::daemon_data::devices::{AddDevice, AddDeviceResult, Device, GetDevice, GetDeviceResult, ListDevices, RemoveDevice, RemoveDeviceResult, SetBrightness, SetBrightnessResult};
-use streamduck_daemon::daemon_data::modules::{AddModuleValue, AddModuleValueResult, GetModuleValues, GetModuleValuesResult, ListComponents, ListModules, RemoveModuleValue, RemoveModuleValueResult, SetModuleValue, SetModuleValueResult};
-use streamduck_daemon::daemon_data::ops::{CommitChangesToConfig, CommitChangesToConfigResult, DoButtonAction, DoButtonActionResult};
-use streamduck_daemon::daemon_data::panels::{DropStackToRoot, DropStackToRootResult, ForciblyPopScreen, ForciblyPopScreenResult, GetButtonImages, GetButtonImagesResult, GetCurrentScreen, GetCurrentScreenResult, GetStack, GetStackNames, GetStackNamesResult, GetStackResult, PopScreen, PopScreenResult, PushScreen, PushScreenResult, ReplaceScreen, ReplaceScreenResult, ResetStack, ResetStackResult};
-use streamduck_daemon::daemon_data::SocketAPIVersion;
+use streamduck_daemon::daemon_data::assets::{AddImage, AddImageResult, ListFonts, ListImages, ListImagesResult, MeasureText, MeasureTextResult, RemoveImage, RemoveImageResult};
+use streamduck_daemon::daemon_data::buttons::{AddComponent, AddComponentResult, AddComponentToKeys, AddComponentToKeysResult, AddComponentValue, AddComponentValueResult, ClearButton, ClearButtonResult, ClipboardStatusResult, CopyButton, CopyButtonResult, GetAllComponentValues, GetAllComponentValuesResult, GetButton, GetButtonResult, GetClipboardButtonResult, GetComponentValues, GetComponentValuesResult, ListButtonTemplates, NewButton, NewButtonFromComponent, NewButtonFromComponentResult, NewButtonFromTemplate, NewButtonFromTemplateResult, NewButtonResult, PasteButton, PasteButtonResult, RemoveButtonTemplate, RemoveButtonTemplateResult, RemoveComponent, RemoveComponentResult, RemoveComponentValue, RemoveComponentValueResult, SaveButtonAsTemplate, SaveButtonAsTemplateResult, SetButton, SetButtonResult, SetComponentValue, SetComponentValueForKeys, SetComponentValueForKeysResult, SetComponentValueResult, SetComponentValues, SetComponentValuesResult};
+use streamduck_daemon::daemon_data::config::{CloneDeviceConfig, CloneDeviceConfigResult, ExportDeviceBundle, ExportDeviceBundleResult, ExportDeviceConfig, ExportDeviceConfigResult, GetConfigPaths, GetDeviceConfig, GetDeviceConfigResult, ImportDeviceBundle, ImportDeviceBundleResult, ImportDeviceConfig, ImportDeviceConfigResult, ListDeviceConfigsResult, ReloadDeviceConfig, ReloadDeviceConfigResult, ReloadDeviceConfigsResult, SaveDeviceConfig, SaveDeviceConfigResult, SaveDeviceConfigsResult};
+use streamduck_daemon::daemon_data::devices::{AddDevice, AddDeviceResult, Device, DeviceLayout, DumpRenderCache, DumpRenderCacheResult, GetBrightness, GetBrightnessResult, GetDevice, GetDeviceLayout, GetDeviceLayoutResult, GetDeviceResult, GetPressedKeys, GetPressedKeysResult, GetRecentEvents, GetRecentEventsResult, GetRenderStats, GetRenderStatsResult, ListDeviceKinds, ListDevices, RemoveDevice, RemoveDeviceResult, SetBrightness, SetBrightnessResult, SetDeviceLocked, SetDeviceLockedResult, SetDeviceNickname, SetDeviceNicknameResult, SetRenderCacheDisabled, SetRenderCacheDisabledResult};
+use streamduck_daemon::daemon_data::modules::{AddModuleValue, AddModuleValueResult, GetComponentDefinition, GetComponentDefinitionResult, GetComponentOwner, GetComponentOwnerResult, GetModuleCompatibility, GetModuleCompatibilityResult, GetModuleValues, GetModuleValuesResult, ListComponents, ListModuleStatus, ListModules, ModuleStatus, RemoveModuleValue, RemoveModuleValueResult, SetModuleValue, SetModuleValueResult};
+use streamduck_daemon::daemon_data::ops::{CommitAllChanges, CommitAllChangesResult, CommitChangesToConfig, CommitChangesToConfigResult, DoButtonAction, DoButtonActionResult};
+use streamduck_daemon::daemon_data::panels::{DropStackToRoot, DropStackToRootResult, ExampleLayout, ExportScreen, ExportScreenResult, ForciblyPopScreen, ForciblyPopScreenResult, GetButtonImages, GetButtonImagesResult, GetComponentRender, GetComponentRenderResult, GetCurrentScreen, GetCurrentScreenResult, GetDeckComposite, GetDeckCompositeResult, GetLastRendered, GetLastRenderedResult, GetStack, GetStackNames, GetStackNamesResult, GetStackResult, ImportScreen, ImportScreenResult, ListExampleLayouts, LoadExampleLayout, LoadExampleLayoutResult, PopScreen, PopScreenResult, PushScreen, PushScreenResult, RenderPreview, RenderPreviewResult, ReplaceScreen, ReplaceScreenResult, ResetStack, ResetStackResult};
+use streamduck_daemon::daemon_data::{BatchItem, BatchRequest, BatchResponse, PingResult, SocketAPIVersion};
 use streamduck_daemon::UNIX_SOCKET_PATH;
 
-use crate::{SDSyncRequestClient, SDClientError, SDSyncEventClient, SDSyncClient, SDSyncUpcastRequestClient, SDSyncUpcastEventClient};
-use crate::util::{process_request, process_request_without_data, read_response, read_socket};
+use crate::{check_version_compatibility, SDSyncRequestClient, SDClientError, SDSyncEventClient, SDSyncClient, SDSyncUpcastRequestClient, SDSyncUpcastEventClient};
+use crate::util::{pop_buffered_event, process_request, process_request_without_data, read_response, read_socket, wait_for_data};
 
 /// Unix Socket based Streamduck client
 pub struct UnixClient {
@@ -34,23 +34,19 @@ pub struct UnixClient {
 
 #[allow(dead_code)]
 impl UnixClient {
-    fn make_client() -> Result<UnixClient, std::io::Error> {
+    fn make_client() -> Result<UnixClient, SDClientError> {
         let client = UnixClient {
             connection: RwLock::new(BufReader::new(UnixStream::connect(UNIX_SOCKET_PATH)?)),
             event_buffer: Default::default()
         };
 
-        let daemon_version = client.version().expect("Failed to retrieve version");
-
-        if daemon_version != SOCKET_API.1 {
-            println!("[Warning] Version of client library doesn't match daemon API version. Client: {}, Daemon: {}", SOCKET_API.1, daemon_version);
-        }
+        check_version_compatibility(&client.version()?)?;
 
         Ok(client)
     }
 
-    /// Initializes client using unix domain socket
-    pub fn new() -> Result<Arc<dyn SDSyncClient>, std::io::Error> {
+    /// Initializes client using unix domain socket, fails if daemon uses incompatible socket API version
+    pub fn new() -> Result<Arc<dyn SDSyncClient>, SDClientError> {
         Ok(Arc::new(UnixClient::make_client()?))
     }
 
@@ -66,12 +62,32 @@ impl SDSyncRequestClient for UnixClient {
         Ok(response.version)
     }
 
+    fn ping(&self) -> Result<PingResult, SDClientError> {
+        let response: PingResult = process_request_without_data(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn send_batch(&self, requests: Vec<BatchItem>) -> Result<BatchResponse, SDClientError> {
+        let response: BatchResponse = process_request(self.get_handle().deref_mut(), &BatchRequest {
+            requests
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
     fn device_list(&self) -> Result<Vec<Device>, SDClientError> {
         let response: ListDevices = process_request_without_data(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?;
 
         Ok(response.devices)
     }
 
+    fn list_device_kinds(&self) -> Result<Vec<DeviceLayout>, SDClientError> {
+        let response: ListDeviceKinds = process_request_without_data(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response.kinds)
+    }
+
     fn get_device(&self, serial_number: &str) -> Result<GetDeviceResult, SDClientError> {
         let response: GetDeviceResult = process_request(self.get_handle().deref_mut(), &GetDevice {
             serial_number: serial_number.to_string()
@@ -80,6 +96,47 @@ impl SDSyncRequestClient for UnixClient {
         Ok(response)
     }
 
+    fn get_device_layout(&self, serial_number: &str) -> Result<GetDeviceLayoutResult, SDClientError> {
+        let response: GetDeviceLayoutResult = process_request(self.get_handle().deref_mut(), &GetDeviceLayout {
+            serial_number: serial_number.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn get_render_stats(&self, serial_number: &str) -> Result<GetRenderStatsResult, SDClientError> {
+        let response: GetRenderStatsResult = process_request(self.get_handle().deref_mut(), &GetRenderStats {
+            serial_number: serial_number.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn dump_render_cache(&self, serial_number: &str) -> Result<DumpRenderCacheResult, SDClientError> {
+        let response: DumpRenderCacheResult = process_request(self.get_handle().deref_mut(), &DumpRenderCache {
+            serial_number: serial_number.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn get_pressed_keys(&self, serial_number: &str) -> Result<GetPressedKeysResult, SDClientError> {
+        let response: GetPressedKeysResult = process_request(self.get_handle().deref_mut(), &GetPressedKeys {
+            serial_number: serial_number.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn get_recent_events(&self, serial_number: &str, limit: usize) -> Result<GetRecentEventsResult, SDClientError> {
+        let response: GetRecentEventsResult = process_request(self.get_handle().deref_mut(), &GetRecentEvents {
+            serial_number: serial_number.to_string(),
+            limit
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
     fn add_device(&self, serial_number: &str) -> Result<AddDeviceResult, SDClientError> {
         let response: AddDeviceResult = process_request(self.get_handle().deref_mut(), &AddDevice {
             serial_number: serial_number.to_string()
@@ -132,6 +189,12 @@ impl SDSyncRequestClient for UnixClient {
         Ok(response)
     }
 
+    fn list_device_configs(&self) -> Result<ListDeviceConfigsResult, SDClientError> {
+        let response: ListDeviceConfigsResult = process_request_without_data(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
     fn import_device_config(&self, serial_number: &str, config: String) -> Result<ImportDeviceConfigResult, SDClientError> {
         let response: ImportDeviceConfigResult = process_request(self.get_handle().deref_mut(), &ImportDeviceConfig {
             serial_number: serial_number.to_string(),
@@ -149,6 +212,39 @@ impl SDSyncRequestClient for UnixClient {
         Ok(response)
     }
 
+    fn export_device_bundle(&self, serial_number: &str) -> Result<ExportDeviceBundleResult, SDClientError> {
+        let response: ExportDeviceBundleResult = process_request(self.get_handle().deref_mut(), &ExportDeviceBundle {
+            serial_number: serial_number.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn import_device_bundle(&self, serial_number: &str, bundle: String) -> Result<ImportDeviceBundleResult, SDClientError> {
+        let response: ImportDeviceBundleResult = process_request(self.get_handle().deref_mut(), &ImportDeviceBundle {
+            serial_number: serial_number.to_string(),
+            bundle
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn clone_device_config(&self, from_serial: &str, to_serial: &str, force: bool) -> Result<CloneDeviceConfigResult, SDClientError> {
+        let response: CloneDeviceConfigResult = process_request(self.get_handle().deref_mut(), &CloneDeviceConfig {
+            from_serial: from_serial.to_string(),
+            to_serial: to_serial.to_string(),
+            force
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn get_config_paths(&self) -> Result<GetConfigPaths, SDClientError> {
+        let response: GetConfigPaths = process_request_without_data(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
     fn set_brightness(&self, serial_number: &str, brightness: u8) -> Result<SetBrightnessResult, SDClientError> {
         let response: SetBrightnessResult = process_request(self.get_handle().deref_mut(), &SetBrightness {
             serial_number: serial_number.to_string(),
@@ -158,9 +254,56 @@ impl SDSyncRequestClient for UnixClient {
         Ok(response)
     }
 
+    fn set_device_locked(&self, serial_number: &str, locked: bool) -> Result<SetDeviceLockedResult, SDClientError> {
+        let response: SetDeviceLockedResult = process_request(self.get_handle().deref_mut(), &SetDeviceLocked {
+            serial_number: serial_number.to_string(),
+            locked
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn set_device_nickname(&self, serial_number: &str, nickname: &str) -> Result<SetDeviceNicknameResult, SDClientError> {
+        let response: SetDeviceNicknameResult = process_request(self.get_handle().deref_mut(), &SetDeviceNickname {
+            serial_number: serial_number.to_string(),
+            nickname: nickname.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn set_render_cache_disabled(&self, serial_number: &str, disabled: bool) -> Result<SetRenderCacheDisabledResult, SDClientError> {
+        let response: SetRenderCacheDisabledResult = process_request(self.get_handle().deref_mut(), &SetRenderCacheDisabled {
+            serial_number: serial_number.to_string(),
+            disabled
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn get_brightness(&self, serial_number: &str) -> Result<GetBrightnessResult, SDClientError> {
+        let response: GetBrightnessResult = process_request(self.get_handle().deref_mut(), &GetBrightness {
+            serial_number: serial_number.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
     fn list_images(&self, serial_number: &str) -> Result<ListImagesResult, SDClientError> {
         let response: ListImagesResult = process_request(self.get_handle().deref_mut(), &ListImages {
-            serial_number: serial_number.to_string()
+            serial_number: serial_number.to_string(),
+            offset: 0,
+            limit: None
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn list_images_page(&self, serial_number: &str, offset: usize, limit: usize) -> Result<ListImagesResult, SDClientError> {
+        let response: ListImagesResult = process_request(self.get_handle().deref_mut(), &ListImages {
+            serial_number: serial_number.to_string(),
+            offset,
+            limit: Some(limit)
         }, Some(self.event_buffer.write().unwrap()))?;
 
         Ok(response)
@@ -190,18 +333,60 @@ impl SDSyncRequestClient for UnixClient {
         Ok(response.font_names)
     }
 
+    fn measure_text(&self, text: &str, font: &str, scale: (f32, f32), letter_spacing: f32, max_width: Option<u32>) -> Result<MeasureTextResult, SDClientError> {
+        let response: MeasureTextResult = process_request(self.get_handle().deref_mut(), &MeasureText {
+            text: text.to_string(),
+            font: font.to_string(),
+            scale,
+            letter_spacing,
+            max_width
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
     fn list_modules(&self) -> Result<Vec<PluginMetadata>, SDClientError> {
         let response: ListModules = process_request_without_data(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?;
 
         Ok(response.modules)
     }
 
+    fn list_module_status(&self) -> Result<Vec<ModuleStatus>, SDClientError> {
+        let response: ListModuleStatus = process_request_without_data(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response.modules)
+    }
+
     fn list_components(&self) -> Result<HashMap<String, HashMap<String, ComponentDefinition>>, SDClientError> {
         let response: ListComponents = process_request_without_data(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?;
 
         Ok(response.components)
     }
 
+    fn get_component_definition(&self, component_name: &str) -> Result<GetComponentDefinitionResult, SDClientError> {
+        let response: GetComponentDefinitionResult = process_request(self.get_handle().deref_mut(), &GetComponentDefinition {
+            component_name: component_name.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn get_component_owner(&self, component_name: &str) -> Result<GetComponentOwnerResult, SDClientError> {
+        let response: GetComponentOwnerResult = process_request(self.get_handle().deref_mut(), &GetComponentOwner {
+            component_name: component_name.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn get_module_compatibility(&self, module_name: &str) -> Result<GetModuleCompatibilityResult, SDClientError> {
+        let response: GetModuleCompatibilityResult = process_request(self.get_handle().deref_mut(), &GetModuleCompatibility {
+            module_name: module_name.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
     fn get_module_values(&self, module_name: &str) -> Result<GetModuleValuesResult, SDClientError> {
         let response: GetModuleValuesResult = process_request(self.get_handle().deref_mut(), &GetModuleValues {
             module_name: module_name.to_string()
@@ -264,6 +449,65 @@ impl SDSyncRequestClient for UnixClient {
 
     fn get_button_images(&self, serial_number: &str) -> Result<GetButtonImagesResult, SDClientError> {
         let response: GetButtonImagesResult = process_request(self.get_handle().deref_mut(), &GetButtonImages {
+            serial_number: serial_number.to_string(),
+            raw: false,
+            keys: None
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn get_button_images_raw(&self, serial_number: &str) -> Result<GetButtonImagesResult, SDClientError> {
+        let response: GetButtonImagesResult = process_request(self.get_handle().deref_mut(), &GetButtonImages {
+            serial_number: serial_number.to_string(),
+            raw: true,
+            keys: None
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn get_button_images_chunk(&self, serial_number: &str, keys: &[u8], raw: bool) -> Result<GetButtonImagesResult, SDClientError> {
+        let response: GetButtonImagesResult = process_request(self.get_handle().deref_mut(), &GetButtonImages {
+            serial_number: serial_number.to_string(),
+            raw,
+            keys: Some(keys.to_vec())
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn get_last_rendered(&self, serial_number: &str, key: u8) -> Result<GetLastRenderedResult, SDClientError> {
+        let response: GetLastRenderedResult = process_request(self.get_handle().deref_mut(), &GetLastRendered {
+            serial_number: serial_number.to_string(),
+            key
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn get_component_render(&self, serial_number: &str, key: u8, component_name: &str) -> Result<GetComponentRenderResult, SDClientError> {
+        let response: GetComponentRenderResult = process_request(self.get_handle().deref_mut(), &GetComponentRender {
+            serial_number: serial_number.to_string(),
+            key,
+            component_name: component_name.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn render_preview(&self, serial_number: &str, key: u8, button: Button) -> Result<RenderPreviewResult, SDClientError> {
+        let response: RenderPreviewResult = process_request(self.get_handle().deref_mut(), &RenderPreview {
+            serial_number: serial_number.to_string(),
+            key,
+            button
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn get_deck_composite(&self, serial_number: &str) -> Result<GetDeckCompositeResult, SDClientError> {
+        let response: GetDeckCompositeResult = process_request(self.get_handle().deref_mut(), &GetDeckComposite {
             serial_number: serial_number.to_string()
         }, Some(self.event_buffer.write().unwrap()))?;
 
@@ -304,6 +548,12 @@ impl SDSyncRequestClient for UnixClient {
         Ok(response)
     }
 
+    fn get_clipboard_button(&self) -> Result<GetClipboardButtonResult, SDClientError> {
+        let response: GetClipboardButtonResult = process_request_without_data(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
     fn copy_button(&self, serial_number: &str, key: u8) -> Result<CopyButtonResult, SDClientError> {
         let response: CopyButtonResult = process_request(self.get_handle().deref_mut(), &CopyButton {
             serial_number: serial_number.to_string(),
@@ -322,6 +572,40 @@ impl SDSyncRequestClient for UnixClient {
         Ok(response)
     }
 
+    fn list_button_templates(&self) -> Result<Vec<String>, SDClientError> {
+        let response: ListButtonTemplates = process_request_without_data(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response.templates)
+    }
+
+    fn save_button_as_template(&self, serial_number: &str, key: u8, template_name: &str) -> Result<SaveButtonAsTemplateResult, SDClientError> {
+        let response: SaveButtonAsTemplateResult = process_request(self.get_handle().deref_mut(), &SaveButtonAsTemplate {
+            serial_number: serial_number.to_string(),
+            key,
+            template_name: template_name.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn remove_button_template(&self, template_name: &str) -> Result<RemoveButtonTemplateResult, SDClientError> {
+        let response: RemoveButtonTemplateResult = process_request(self.get_handle().deref_mut(), &RemoveButtonTemplate {
+            template_name: template_name.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn new_button_from_template(&self, serial_number: &str, key: u8, template_name: &str) -> Result<NewButtonFromTemplateResult, SDClientError> {
+        let response: NewButtonFromTemplateResult = process_request(self.get_handle().deref_mut(), &NewButtonFromTemplate {
+            serial_number: serial_number.to_string(),
+            key,
+            template_name: template_name.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
     fn new_button(&self, serial_number: &str, key: u8) -> Result<NewButtonResult, SDClientError> {
         let response: NewButtonResult = process_request(self.get_handle().deref_mut(), &NewButton {
             serial_number: serial_number.to_string(),
@@ -351,6 +635,16 @@ impl SDSyncRequestClient for UnixClient {
         Ok(response)
     }
 
+    fn add_component_to_keys(&self, serial_number: &str, keys: Vec<u8>, component_name: &str) -> Result<AddComponentToKeysResult, SDClientError> {
+        let response: AddComponentToKeysResult = process_request(self.get_handle().deref_mut(), &AddComponentToKeys {
+            serial_number: serial_number.to_string(),
+            keys,
+            component_name: component_name.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
     fn get_component_values(&self, serial_number: &str, key: u8, component_name: &str) -> Result<GetComponentValuesResult, SDClientError> {
         let response: GetComponentValuesResult = process_request(self.get_handle().deref_mut(), &GetComponentValues {
             serial_number: serial_number.to_string(),
@@ -361,6 +655,15 @@ impl SDSyncRequestClient for UnixClient {
         Ok(response)
     }
 
+    fn get_all_component_values(&self, serial_number: &str, key: u8) -> Result<GetAllComponentValuesResult, SDClientError> {
+        let response: GetAllComponentValuesResult = process_request(self.get_handle().deref_mut(), &GetAllComponentValues {
+            serial_number: serial_number.to_string(),
+            key
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
     fn add_component_value(&self, serial_number: &str, key: u8, component_name: &str, path: &str) -> Result<AddComponentValueResult, SDClientError> {
         let response: AddComponentValueResult = process_request(self.get_handle().deref_mut(), &AddComponentValue {
             serial_number: serial_number.to_string(),
@@ -395,6 +698,28 @@ impl SDSyncRequestClient for UnixClient {
         Ok(response)
     }
 
+    fn set_component_value_for_keys(&self, serial_number: &str, keys: Vec<u8>, component_name: &str, value: UIPathValue) -> Result<SetComponentValueForKeysResult, SDClientError> {
+        let response: SetComponentValueForKeysResult = process_request(self.get_handle().deref_mut(), &SetComponentValueForKeys {
+            serial_number: serial_number.to_string(),
+            keys,
+            component_name: component_name.to_string(),
+            value
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn set_component_values(&self, serial_number: &str, key: u8, component_name: &str, values: Vec<UIPathValue>) -> Result<SetComponentValuesResult, SDClientError> {
+        let response: SetComponentValuesResult = process_request(self.get_handle().deref_mut(), &SetComponentValues {
+            serial_number: serial_number.to_string(),
+            key,
+            component_name: component_name.to_string(),
+            values
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
     fn remove_component(&self, serial_number: &str, key: u8, component_name: &str) -> Result<RemoveComponentResult, SDClientError> {
         let response: RemoveComponentResult = process_request(self.get_handle().deref_mut(), &RemoveComponent {
             serial_number: serial_number.to_string(),
@@ -414,6 +739,24 @@ impl SDSyncRequestClient for UnixClient {
         Ok(response)
     }
 
+    fn export_screen(&self, serial_number: &str, stack_index: usize) -> Result<ExportScreenResult, SDClientError> {
+        let response: ExportScreenResult = process_request(self.get_handle().deref_mut(), &ExportScreen {
+            serial_number: serial_number.to_string(),
+            stack_index
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn import_screen(&self, serial_number: &str, screen: &str) -> Result<ImportScreenResult, SDClientError> {
+        let response: ImportScreenResult = process_request(self.get_handle().deref_mut(), &ImportScreen {
+            serial_number: serial_number.to_string(),
+            screen: screen.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
     fn pop_screen(&self, serial_number: &str) -> Result<PopScreenResult, SDClientError> {
         let response: PopScreenResult = process_request(self.get_handle().deref_mut(), &PopScreen {
             serial_number: serial_number.to_string()
@@ -448,6 +791,21 @@ impl SDSyncRequestClient for UnixClient {
         Ok(response)
     }
 
+    fn load_example_layout(&self, serial_number: &str, name: &str) -> Result<LoadExampleLayoutResult, SDClientError> {
+        let response: LoadExampleLayoutResult = process_request(self.get_handle().deref_mut(), &LoadExampleLayout {
+            serial_number: serial_number.to_string(),
+            name: name.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
+    fn list_example_layouts(&self) -> Result<Vec<ExampleLayout>, SDClientError> {
+        let response: ListExampleLayouts = process_request_without_data(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response.layouts)
+    }
+
     fn drop_stack_to_root(&self, serial_number: &str) -> Result<DropStackToRootResult, SDClientError> {
         let response: DropStackToRootResult = process_request(self.get_handle().deref_mut(), &DropStackToRoot {
             serial_number: serial_number.to_string()
@@ -464,6 +822,14 @@ impl SDSyncRequestClient for UnixClient {
         Ok(response)
     }
 
+    fn commit_all_changes(&self, save: bool) -> Result<CommitAllChangesResult, SDClientError> {
+        let response: CommitAllChangesResult = process_request(self.get_handle().deref_mut(), &CommitAllChanges {
+            save
+        }, Some(self.event_buffer.write().unwrap()))?;
+
+        Ok(response)
+    }
+
     fn do_button_action(&self, serial_number: &str, key: u8) -> Result<DoButtonActionResult, SDClientError> {
         let response: DoButtonActionResult = process_request(self.get_handle().deref_mut(), &DoButtonAction {
             serial_number: serial_number.to_string(),
@@ -492,15 +858,10 @@ impl SDSyncRequestClient for UnixClient {
 
 impl SDSyncEventClient for UnixClient {
     fn get_event(&self) -> Result<SDGlobalEvent, SDClientError> {
-        let mut buffer = self.event_buffer.write().unwrap();
-
-        if let Some(event) = buffer.pop() {
+        if let Some(event) = pop_buffered_event(&self.event_buffer) {
             return Ok(event);
         }
 
-        drop(buffer);
-
-
         loop {
             let packet = read_socket(self.get_handle().deref_mut())?;
 
@@ -509,6 +870,37 @@ impl SDSyncEventClient for UnixClient {
             }
         }
     }
+
+    fn try_get_event(&self, timeout: Duration) -> Result<Option<SDGlobalEvent>, SDClientError> {
+        if let Some(event) = pop_buffered_event(&self.event_buffer) {
+            return Ok(Some(event));
+        }
+
+        let deadline = Instant::now() + timeout;
+        let mut handle = self.get_handle();
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+
+            if remaining.is_zero() {
+                return Ok(None);
+            }
+
+            handle.get_ref().set_read_timeout(Some(remaining))?;
+            let has_data = wait_for_data(handle.deref_mut());
+            handle.get_ref().set_read_timeout(None)?;
+
+            if !has_data? {
+                return Ok(None);
+            }
+
+            let packet = read_socket(handle.deref_mut())?;
+
+            if let Some(data) = packet.data {
+                return Ok(Some(serde_json::from_value(data)?));
+            }
+        }
+    }
 }
 
 
@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 use std::io::{BufRead, BufReader, Read, Write};
 use std::ops::DerefMut;
-use std::sync::RwLockWriteGuard;
+use std::io::ErrorKind;
+use std::sync::{RwLock, RwLockWriteGuard};
 use rand::distributions::Alphanumeric;
 use rand::Rng;
 use serde::de::DeserializeOwned;
@@ -31,6 +32,22 @@ pub fn read_socket(handle: &mut dyn BufRead) -> Result<SocketPacket, SDClientErr
     Ok(serde_json::from_str(line.replace("\u{0004}", "").trim())?)
 }
 
+/// Takes oldest event out of event buffer
+pub fn pop_buffered_event(event_buffer: &RwLock<Vec<SDGlobalEvent>>) -> Option<SDGlobalEvent> {
+    event_buffer.write().unwrap().pop()
+}
+
+/// Waits until there's data to read, returns false if read timeout of the underlying handle elapsed first
+pub fn wait_for_data(handle: &mut dyn BufRead) -> Result<bool, SDClientError> {
+    match handle.fill_buf() {
+        Ok(buffer) => Ok(!buffer.is_empty()),
+        Err(err) => match err.kind() {
+            ErrorKind::WouldBlock | ErrorKind::TimedOut => Ok(false),
+            _ => Err(err.into())
+        }
+    }
+}
+
 pub fn read_response(handle: &mut dyn BufRead, requester: &str, mut event_buffer: Option<RwLockWriteGuard<Vec<SDGlobalEvent>>>) -> Result<SocketPacket, SDClientError> {
     loop {
         let packet = read_socket(handle)?;
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::io::BufReader;
 use std::ops::DerefMut;
 use std::sync::{Arc, RwLock, RwLockWriteGuard};
+use std::time::{Duration, Instant};
 use named_pipe::PipeClient;
 use rand::distributions::Alphanumeric;
 use rand::Rng;
@@ -11,19 +12,18 @@ use streamduck_core::modules::components::{ComponentDefinition, UIPathValue};
 use streamduck_core::modules::events::SDGlobalEvent;
 use streamduck_core::modules::PluginMetadata;
 use streamduck_core::socket::{ SocketPacket};
-use streamduck_core::versions::SOCKET_API;
-use streamduck_daemon::daemon_data::assets::{AddImage, AddImageResult, ListFonts, ListImages, ListImagesResult, RemoveImage, RemoveImageResult};
-use streamduck_daemon::daemon_data::buttons::{AddComponent, AddComponentResult, AddComponentValue, AddComponentValueResult, ClearButton, ClearButtonResult, ClipboardStatusResult, CopyButton, CopyButtonResult, GetButton, GetButtonResult, GetComponentValues, GetComponentValuesResult, NewButton, NewButtonFromComponent, NewButtonFromComponentResult, NewButtonResult, PasteButton, PasteButtonResult, RemoveComponent, RemoveComponentResult, RemoveComponentValue, RemoveComponentValueResult, SetButton, SetButtonResult, SetComponentValue, SetComponentValueResult};
-use streamduck_daemon::daemon_data::config::{ExportDeviceConfig, ExportDeviceConfigResult, GetDeviceConfig, GetDeviceConfigResult, ImportDeviceConfig, ImportDeviceConfigResult, ReloadDeviceConfig, ReloadDeviceConfigResult, ReloadDeviceConfigsResult, SaveDeviceConfig, SaveDeviceConfigResult, SaveDeviceConfigsResult};
-use streamduck_daemon::daemon_data::devices::{AddDevice, AddDeviceResult, Device, GetDevice, GetDeviceResult, ListDevices, RemoveDevice, RemoveDeviceResult, SetBrightness, SetBrightnessResult};
-use streamduck_daemon::daemon_data::modules::{AddModuleValue, AddModuleValueResult, GetModuleValues, GetModuleValuesResult, ListComponents, ListModules, RemoveModuleValue, RemoveModuleValueResult, SetModuleValue, SetModuleValueResult};
-use streamduck_daemon::daemon_data::ops::{CommitChangesToConfig, CommitChangesToConfigResult, DoButtonAction, DoButtonActionResult};
-use streamduck_daemon::daemon_data::panels::{DropStackToRoot, DropStackToRootResult, ForciblyPopScreen, ForciblyPopScreenResult, GetButtonImages, GetButtonImagesResult, GetCurrentScreen, GetCurrentScreenResult, GetStack, GetStackNames, GetStackNamesResult, GetStackResult, PopScreen, PopScreenResult, PushScreen, PushScreenResult, ReplaceScreen, ReplaceScreenResult, ResetStack, ResetStackResult};
-use streamduck_daemon::daemon_data::SocketAPIVersion;
+use streamduck_daemon::daemon_data::assets::{AddImage, AddImageResult, ListFonts, ListImages, ListImagesResult, MeasureText, MeasureTextResult, RemoveImage, RemoveImageResult};
+use streamduck_daemon::daemon_data::buttons::{AddComponent, AddComponentResult, AddComponentToKeys, AddComponentToKeysResult, AddComponentValue, AddComponentValueResult, ClearButton, ClearButtonResult, ClipboardStatusResult, CopyButton, CopyButtonResult, GetAllComponentValues, GetAllComponentValuesResult, GetButton, GetButtonResult, GetClipboardButtonResult, GetComponentValues, GetComponentValuesResult, ListButtonTemplates, NewButton, NewButtonFromComponent, NewButtonFromComponentResult, NewButtonFromTemplate, NewButtonFromTemplateResult, NewButtonResult, PasteButton, PasteButtonResult, RemoveButtonTemplate, RemoveButtonTemplateResult, RemoveComponent, RemoveComponentResult, RemoveComponentValue, RemoveComponentValueResult, SaveButtonAsTemplate, SaveButtonAsTemplateResult, SetButton, SetButtonResult, SetComponentValue, SetComponentValueForKeys, SetComponentValueForKeysResult, SetComponentValueResult, SetComponentValues, SetComponentValuesResult};
+use streamduck_daemon::daemon_data::config::{CloneDeviceConfig, CloneDeviceConfigResult, ExportDeviceBundle, ExportDeviceBundleResult, ExportDeviceConfig, ExportDeviceConfigResult, GetConfigPaths, GetDeviceConfig, GetDeviceConfigResult, ImportDeviceBundle, ImportDeviceBundleResult, ImportDeviceConfig, ImportDeviceConfigResult, ListDeviceConfigsResult, ReloadDeviceConfig, ReloadDeviceConfigResult, ReloadDeviceConfigsResult, SaveDeviceConfig, SaveDeviceConfigResult, SaveDeviceConfigsResult};
+use streamduck_daemon::daemon_data::devices::{AddDevice, AddDeviceResult, Device, DeviceLayout, DumpRenderCache, DumpRenderCacheResult, GetBrightness, GetBrightnessResult, GetDevice, GetDeviceLayout, GetDeviceLayoutResult, GetDeviceResult, GetPressedKeys, GetPressedKeysResult, GetRecentEvents, GetRecentEventsResult, GetRenderStats, GetRenderStatsResult, ListDeviceKinds, ListDevices, RemoveDevice, RemoveDeviceResult, SetBrightness, SetBrightnessResult, SetDeviceLocked, SetDeviceLockedResult, SetDeviceNickname, SetDeviceNicknameResult, SetRenderCacheDisabled, SetRenderCacheDisabledResult};
+use streamduck_daemon::daemon_data::modules::{AddModuleValue, AddModuleValueResult, GetComponentDefinition, GetComponentDefinitionResult, GetComponentOwner, GetComponentOwnerResult, GetModuleCompatibility, GetModuleCompatibilityResult, GetModuleValues, GetModuleValuesResult, ListComponents, ListModuleStatus, ListModules, ModuleStatus, RemoveModuleValue, RemoveModuleValueResult, SetModuleValue, SetModuleValueResult};
+use streamduck_daemon::daemon_data::ops::{CommitAllChanges, CommitAllChangesResult, CommitChangesToConfig, CommitChangesToConfigResult, DoButtonAction, DoButtonActionResult};
+use streamduck_daemon::daemon_data::panels::{DropStackToRoot, DropStackToRootResult, ExampleLayout, ExportScreen, ExportScreenResult, ForciblyPopScreen, ForciblyPopScreenResult, GetButtonImages, GetButtonImagesResult, GetComponentRender, GetComponentRenderResult, GetCurrentScreen, GetCurrentScreenResult, GetDeckComposite, GetDeckCompositeResult, GetLastRendered, GetLastRenderedResult, GetStack, GetStackNames, GetStackNamesResult, GetStackResult, ImportScreen, ImportScreenResult, ListExampleLayouts, LoadExampleLayout, LoadExampleLayoutResult, PopScreen, PopScreenResult, PushScreen, PushScreenResult, RenderPreview, RenderPreviewResult, ReplaceScreen, ReplaceScreenResult, ResetStack, ResetStackResult};
+use streamduck_daemon::daemon_data::{BatchItem, BatchRequest, BatchResponse, PingResult, SocketAPIVersion};
 use streamduck_daemon::WINDOWS_PIPE_NAME;
 use std::io::Write;
-use crate::{SDClientError, SDSyncClient, SDSyncEventClient, SDSyncRequestClient, SDSyncUpcastEventClient, SDSyncUpcastRequestClient};
-use crate::util::{process_request, process_request_without_data, read_response, read_socket};
+use crate::{check_version_compatibility, SDClientError, SDSyncClient, SDSyncEventClient, SDSyncRequestClient, SDSyncUpcastEventClient, SDSyncUpcastRequestClient};
+use crate::util::{pop_buffered_event, process_request, process_request_without_data, read_response, read_socket, wait_for_data};
 
 /// Windows Named Pipe based Streamduck client
 pub struct WinClient {
@@ -32,18 +32,14 @@ pub struct WinClient {
 }
 
 impl WinClient {
-    /// Initializes client using windows named pipe
-    pub fn new() -> Result<Arc<dyn SDSyncClient>, std::io::Error> {
+    /// Initializes client using windows named pipe, fails if daemon uses incompatible socket API version
+    pub fn new() -> Result<Arc<dyn SDSyncClient>, SDClientError> {
         let client = WinClient {
             connection: RwLock::new(BufReader::new(PipeClient::connect(WINDOWS_PIPE_NAME)?)),
             event_buffer: Default::default()
         };
 
-        let daemon_version = client.version().expect("Failed to retrieve version");
-
-        if daemon_version != SOCKET_API.1 {
-            println!("[Warning] Version of client library doesn't match daemon API version. Client: {}, Daemon: {}", SOCKET_API.1, daemon_version);
-        }
+        check_version_compatibility(&client.version()?)?;
 
         Ok(Arc::new(client))
     }
@@ -59,17 +55,63 @@ impl SDSyncRequestClient for WinClient {
         Ok(response.version)
     }
 
+    fn ping(&self) -> Result<PingResult, SDClientError> {
+        Ok(process_request_without_data::<PingResult, PipeClient>(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn send_batch(&self, requests: Vec<BatchItem>) -> Result<BatchResponse, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &BatchRequest {
+            requests
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
     fn device_list(&self) -> Result<Vec<Device>, SDClientError> {
         let response: ListDevices = process_request_without_data::<ListDevices, PipeClient>(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?;
         Ok(response.devices)
     }
 
+    fn list_device_kinds(&self) -> Result<Vec<DeviceLayout>, SDClientError> {
+        let response: ListDeviceKinds = process_request_without_data::<ListDeviceKinds, PipeClient>(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?;
+        Ok(response.kinds)
+    }
+
     fn get_device(&self, serial_number: &str) -> Result<GetDeviceResult, SDClientError> {
         Ok(process_request(self.get_handle().deref_mut(), &GetDevice {
             serial_number: serial_number.to_string()
         }, Some(self.event_buffer.write().unwrap()))?)
     }
 
+    fn get_device_layout(&self, serial_number: &str) -> Result<GetDeviceLayoutResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &GetDeviceLayout {
+            serial_number: serial_number.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn get_render_stats(&self, serial_number: &str) -> Result<GetRenderStatsResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &GetRenderStats {
+            serial_number: serial_number.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn dump_render_cache(&self, serial_number: &str) -> Result<DumpRenderCacheResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &DumpRenderCache {
+            serial_number: serial_number.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn get_pressed_keys(&self, serial_number: &str) -> Result<GetPressedKeysResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &GetPressedKeys {
+            serial_number: serial_number.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn get_recent_events(&self, serial_number: &str, limit: usize) -> Result<GetRecentEventsResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &GetRecentEvents {
+            serial_number: serial_number.to_string(),
+            limit
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
     fn add_device(&self, serial_number: &str) -> Result<AddDeviceResult, SDClientError> {
         Ok(process_request(self.get_handle().deref_mut(), &AddDevice {
             serial_number: serial_number.to_string()
@@ -108,6 +150,10 @@ impl SDSyncRequestClient for WinClient {
         }, Some(self.event_buffer.write().unwrap()))?)
     }
 
+    fn list_device_configs(&self) -> Result<ListDeviceConfigsResult, SDClientError> {
+        Ok(process_request_without_data::<ListDeviceConfigsResult, PipeClient>(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?)
+    }
+
     fn import_device_config(&self, serial_number: &str, config: String) -> Result<ImportDeviceConfigResult, SDClientError> {
         Ok(process_request(self.get_handle().deref_mut(), &ImportDeviceConfig {
             serial_number: serial_number.to_string(),
@@ -121,6 +167,31 @@ impl SDSyncRequestClient for WinClient {
         }, Some(self.event_buffer.write().unwrap()))?)
     }
 
+    fn export_device_bundle(&self, serial_number: &str) -> Result<ExportDeviceBundleResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &ExportDeviceBundle {
+            serial_number: serial_number.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn import_device_bundle(&self, serial_number: &str, bundle: String) -> Result<ImportDeviceBundleResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &ImportDeviceBundle {
+            serial_number: serial_number.to_string(),
+            bundle
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn clone_device_config(&self, from_serial: &str, to_serial: &str, force: bool) -> Result<CloneDeviceConfigResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &CloneDeviceConfig {
+            from_serial: from_serial.to_string(),
+            to_serial: to_serial.to_string(),
+            force
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn get_config_paths(&self) -> Result<GetConfigPaths, SDClientError> {
+        Ok(process_request_without_data::<GetConfigPaths, PipeClient>(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?)
+    }
+
     fn set_brightness(&self, serial_number: &str, brightness: u8) -> Result<SetBrightnessResult, SDClientError> {
         Ok(process_request(self.get_handle().deref_mut(), &SetBrightness {
             serial_number: serial_number.to_string(),
@@ -128,9 +199,46 @@ impl SDSyncRequestClient for WinClient {
         }, Some(self.event_buffer.write().unwrap()))?)
     }
 
+    fn set_device_locked(&self, serial_number: &str, locked: bool) -> Result<SetDeviceLockedResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &SetDeviceLocked {
+            serial_number: serial_number.to_string(),
+            locked
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn set_device_nickname(&self, serial_number: &str, nickname: &str) -> Result<SetDeviceNicknameResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &SetDeviceNickname {
+            serial_number: serial_number.to_string(),
+            nickname: nickname.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn set_render_cache_disabled(&self, serial_number: &str, disabled: bool) -> Result<SetRenderCacheDisabledResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &SetRenderCacheDisabled {
+            serial_number: serial_number.to_string(),
+            disabled
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn get_brightness(&self, serial_number: &str) -> Result<GetBrightnessResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &GetBrightness {
+            serial_number: serial_number.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
     fn list_images(&self, serial_number: &str) -> Result<ListImagesResult, SDClientError> {
         Ok(process_request(self.get_handle().deref_mut(), &ListImages {
-            serial_number: serial_number.to_string()
+            serial_number: serial_number.to_string(),
+            offset: 0,
+            limit: None
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn list_images_page(&self, serial_number: &str, offset: usize, limit: usize) -> Result<ListImagesResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &ListImages {
+            serial_number: serial_number.to_string(),
+            offset,
+            limit: Some(limit)
         }, Some(self.event_buffer.write().unwrap()))?)
     }
 
@@ -153,16 +261,49 @@ impl SDSyncRequestClient for WinClient {
         Ok(response.font_names)
     }
 
+    fn measure_text(&self, text: &str, font: &str, scale: (f32, f32), letter_spacing: f32, max_width: Option<u32>) -> Result<MeasureTextResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &MeasureText {
+            text: text.to_string(),
+            font: font.to_string(),
+            scale,
+            letter_spacing,
+            max_width
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
     fn list_modules(&self) -> Result<Vec<PluginMetadata>, SDClientError> {
         let response: ListModules = process_request_without_data(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?;
         Ok(response.modules)
     }
 
+    fn list_module_status(&self) -> Result<Vec<ModuleStatus>, SDClientError> {
+        let response: ListModuleStatus = process_request_without_data(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?;
+        Ok(response.modules)
+    }
+
     fn list_components(&self) -> Result<HashMap<String, HashMap<String, ComponentDefinition>>, SDClientError> {
         let response: ListComponents = process_request_without_data(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?;
         Ok(response.components)
     }
 
+    fn get_component_definition(&self, component_name: &str) -> Result<GetComponentDefinitionResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &GetComponentDefinition {
+            component_name: component_name.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn get_component_owner(&self, component_name: &str) -> Result<GetComponentOwnerResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &GetComponentOwner {
+            component_name: component_name.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn get_module_compatibility(&self, module_name: &str) -> Result<GetModuleCompatibilityResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &GetModuleCompatibility {
+            module_name: module_name.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
     fn get_module_values(&self, module_name: &str) -> Result<GetModuleValuesResult, SDClientError> {
         Ok(process_request(self.get_handle().deref_mut(), &GetModuleValues {
             module_name: module_name.to_string()
@@ -211,6 +352,53 @@ impl SDSyncRequestClient for WinClient {
 
     fn get_button_images(&self, serial_number: &str) -> Result<GetButtonImagesResult, SDClientError> {
         Ok(process_request(self.get_handle().deref_mut(), &GetButtonImages {
+            serial_number: serial_number.to_string(),
+            raw: false,
+            keys: None
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn get_button_images_raw(&self, serial_number: &str) -> Result<GetButtonImagesResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &GetButtonImages {
+            serial_number: serial_number.to_string(),
+            raw: true,
+            keys: None
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn get_button_images_chunk(&self, serial_number: &str, keys: &[u8], raw: bool) -> Result<GetButtonImagesResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &GetButtonImages {
+            serial_number: serial_number.to_string(),
+            raw,
+            keys: Some(keys.to_vec())
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn get_last_rendered(&self, serial_number: &str, key: u8) -> Result<GetLastRenderedResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &GetLastRendered {
+            serial_number: serial_number.to_string(),
+            key
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn get_component_render(&self, serial_number: &str, key: u8, component_name: &str) -> Result<GetComponentRenderResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &GetComponentRender {
+            serial_number: serial_number.to_string(),
+            key,
+            component_name: component_name.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn render_preview(&self, serial_number: &str, key: u8, button: Button) -> Result<RenderPreviewResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &RenderPreview {
+            serial_number: serial_number.to_string(),
+            key,
+            button
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn get_deck_composite(&self, serial_number: &str) -> Result<GetDeckCompositeResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &GetDeckComposite {
             serial_number: serial_number.to_string()
         }, Some(self.event_buffer.write().unwrap()))?)
     }
@@ -241,6 +429,10 @@ impl SDSyncRequestClient for WinClient {
         Ok(process_request_without_data(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?)
     }
 
+    fn get_clipboard_button(&self) -> Result<GetClipboardButtonResult, SDClientError> {
+        Ok(process_request_without_data::<GetClipboardButtonResult, PipeClient>(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?)
+    }
+
     fn copy_button(&self, serial_number: &str, key: u8) -> Result<CopyButtonResult, SDClientError> {
         Ok(process_request(self.get_handle().deref_mut(), &CopyButton {
             serial_number: serial_number.to_string(),
@@ -255,6 +447,33 @@ impl SDSyncRequestClient for WinClient {
         }, Some(self.event_buffer.write().unwrap()))?)
     }
 
+    fn list_button_templates(&self) -> Result<Vec<String>, SDClientError> {
+        let response: ListButtonTemplates = process_request_without_data::<ListButtonTemplates, PipeClient>(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?;
+        Ok(response.templates)
+    }
+
+    fn save_button_as_template(&self, serial_number: &str, key: u8, template_name: &str) -> Result<SaveButtonAsTemplateResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &SaveButtonAsTemplate {
+            serial_number: serial_number.to_string(),
+            key,
+            template_name: template_name.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn remove_button_template(&self, template_name: &str) -> Result<RemoveButtonTemplateResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &RemoveButtonTemplate {
+            template_name: template_name.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn new_button_from_template(&self, serial_number: &str, key: u8, template_name: &str) -> Result<NewButtonFromTemplateResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &NewButtonFromTemplate {
+            serial_number: serial_number.to_string(),
+            key,
+            template_name: template_name.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
     fn new_button(&self, serial_number: &str, key: u8) -> Result<NewButtonResult, SDClientError> {
         Ok(process_request(self.get_handle().deref_mut(), &NewButton {
             serial_number: serial_number.to_string(),
@@ -278,6 +497,14 @@ impl SDSyncRequestClient for WinClient {
         }, Some(self.event_buffer.write().unwrap()))?)
     }
 
+    fn add_component_to_keys(&self, serial_number: &str, keys: Vec<u8>, component_name: &str) -> Result<AddComponentToKeysResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &AddComponentToKeys {
+            serial_number: serial_number.to_string(),
+            keys,
+            component_name: component_name.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
     fn get_component_values(&self, serial_number: &str, key: u8, component_name: &str) -> Result<GetComponentValuesResult, SDClientError> {
         Ok(process_request(self.get_handle().deref_mut(), &GetComponentValues {
             serial_number: serial_number.to_string(),
@@ -286,6 +513,13 @@ impl SDSyncRequestClient for WinClient {
         }, Some(self.event_buffer.write().unwrap()))?)
     }
 
+    fn get_all_component_values(&self, serial_number: &str, key: u8) -> Result<GetAllComponentValuesResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &GetAllComponentValues {
+            serial_number: serial_number.to_string(),
+            key
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
     fn add_component_value(&self, serial_number: &str, key: u8, component_name: &str, path: &str) -> Result<AddComponentValueResult, SDClientError> {
         Ok(process_request(self.get_handle().deref_mut(), &AddComponentValue {
             serial_number: serial_number.to_string(),
@@ -314,6 +548,24 @@ impl SDSyncRequestClient for WinClient {
         }, Some(self.event_buffer.write().unwrap()))?)
     }
 
+    fn set_component_value_for_keys(&self, serial_number: &str, keys: Vec<u8>, component_name: &str, value: UIPathValue) -> Result<SetComponentValueForKeysResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &SetComponentValueForKeys {
+            serial_number: serial_number.to_string(),
+            keys,
+            component_name: component_name.to_string(),
+            value
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn set_component_values(&self, serial_number: &str, key: u8, component_name: &str, values: Vec<UIPathValue>) -> Result<SetComponentValuesResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &SetComponentValues {
+            serial_number: serial_number.to_string(),
+            key,
+            component_name: component_name.to_string(),
+            values
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
     fn remove_component(&self, serial_number: &str, key: u8, component_name: &str) -> Result<RemoveComponentResult, SDClientError> {
         Ok(process_request(self.get_handle().deref_mut(), &RemoveComponent {
             serial_number: serial_number.to_string(),
@@ -329,6 +581,20 @@ impl SDSyncRequestClient for WinClient {
         }, Some(self.event_buffer.write().unwrap()))?)
     }
 
+    fn export_screen(&self, serial_number: &str, stack_index: usize) -> Result<ExportScreenResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &ExportScreen {
+            serial_number: serial_number.to_string(),
+            stack_index
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn import_screen(&self, serial_number: &str, screen: &str) -> Result<ImportScreenResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &ImportScreen {
+            serial_number: serial_number.to_string(),
+            screen: screen.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
     fn pop_screen(&self, serial_number: &str) -> Result<PopScreenResult, SDClientError> {
         Ok(process_request(self.get_handle().deref_mut(), &PopScreen {
             serial_number: serial_number.to_string()
@@ -355,6 +621,18 @@ impl SDSyncRequestClient for WinClient {
         }, Some(self.event_buffer.write().unwrap()))?)
     }
 
+    fn load_example_layout(&self, serial_number: &str, name: &str) -> Result<LoadExampleLayoutResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &LoadExampleLayout {
+            serial_number: serial_number.to_string(),
+            name: name.to_string()
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
+    fn list_example_layouts(&self) -> Result<Vec<ExampleLayout>, SDClientError> {
+        let response: ListExampleLayouts = process_request_without_data::<ListExampleLayouts, PipeClient>(self.get_handle().deref_mut(), Some(self.event_buffer.write().unwrap()))?;
+        Ok(response.layouts)
+    }
+
     fn drop_stack_to_root(&self, serial_number: &str) -> Result<DropStackToRootResult, SDClientError> {
         Ok(process_request(self.get_handle().deref_mut(), &DropStackToRoot {
             serial_number: serial_number.to_string()
@@ -367,6 +645,12 @@ impl SDSyncRequestClient for WinClient {
         }, Some(self.event_buffer.write().unwrap()))?)
     }
 
+    fn commit_all_changes(&self, save: bool) -> Result<CommitAllChangesResult, SDClientError> {
+        Ok(process_request(self.get_handle().deref_mut(), &CommitAllChanges {
+            save
+        }, Some(self.event_buffer.write().unwrap()))?)
+    }
+
     fn do_button_action(&self, serial_number: &str, key: u8) -> Result<DoButtonActionResult, SDClientError> {
         Ok(process_request(self.get_handle().deref_mut(), &DoButtonAction {
             serial_number: serial_number.to_string(),
@@ -391,15 +675,10 @@ impl SDSyncRequestClient for WinClient {
 
 impl SDSyncEventClient for WinClient {
     fn get_event(&self) -> Result<SDGlobalEvent, SDClientError> {
-        let mut buffer = self.event_buffer.write().unwrap();
-
-        if let Some(event) = buffer.pop() {
+        if let Some(event) = pop_buffered_event(&self.event_buffer) {
             return Ok(event);
         }
 
-        drop(buffer);
-
-
         loop {
             let packet = read_socket(self.get_handle().deref_mut())?;
 
@@ -410,6 +689,39 @@ impl SDSyncEventClient for WinClient {
             }
         }
     }
+
+    fn try_get_event(&self, timeout: Duration) -> Result<Option<SDGlobalEvent>, SDClientError> {
+        if let Some(event) = pop_buffered_event(&self.event_buffer) {
+            return Ok(Some(event));
+        }
+
+        let deadline = Instant::now() + timeout;
+        let mut handle = self.get_handle();
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+
+            if remaining.is_zero() {
+                return Ok(None);
+            }
+
+            handle.get_mut().set_read_timeout(Some(remaining));
+            let has_data = wait_for_data(handle.deref_mut());
+            handle.get_mut().set_read_timeout(None);
+
+            if !has_data? {
+                return Ok(None);
+            }
+
+            let packet = read_socket(handle.deref_mut())?;
+
+            if packet.ty == "event" {
+                if let Some(data) = packet.data {
+                    return Ok(Some(serde_json::from_value(data)?));
+                }
+            }
+        }
+    }
 }
 
 impl SDSyncUpcastEventClient for WinClient {
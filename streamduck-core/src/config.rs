@@ -1,22 +1,25 @@
 //! Core and device configs
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tokio::fs;
+use tokio::io::AsyncWriteExt;
 use dirs;
 use std::ops::Deref;
-use std::time::{Instant, Duration};
+use std::time::{Instant, Duration, UNIX_EPOCH};
 use std::path::PathBuf;
 use std::sync::{Arc};
 use image::{DynamicImage};
 use serde::{Serialize, Deserialize};
 use serde::de::DeserializeOwned;
 use crate::core::RawButtonPanel;
+use crate::core::button::Button;
 use serde_json::Value;
 use streamdeck::Kind;
 use tokio::sync::RwLock;
 use crate::ImageCollection;
-use crate::images::{SDImage, SDSerializedImage};
+use crate::images::{ImageMetadata, SDImage, SDSerializedImage};
 use crate::util::{hash_image, hash_str};
 use crate::thread::util::resize_for_streamdeck;
+use crate::font::get_font_from_collection;
 
 /// Default folder name
 pub const CONFIG_FOLDER: &'static str = "streamduck";
@@ -25,6 +28,14 @@ pub const CONFIG_FOLDER: &'static str = "streamduck";
 pub const DEFAULT_FRAME_RATE: u32 = 100;
 /// Default reconnect interval
 pub const DEFAULT_RECONNECT_TIME: f32 = 1.0;
+/// Default max size of decoded image data that can be added to image collection, in bytes
+pub const DEFAULT_MAX_IMAGE_SIZE: usize = 8 * 1024 * 1024;
+/// Default time in milliseconds to wait for more changes before reloading a device config that was changed on disk
+pub const DEFAULT_WATCH_DEBOUNCE: u64 = 500;
+/// Default amount of recent events to keep per device, event log is disabled by default as it serializes every event
+pub const DEFAULT_EVENT_LOG_SIZE: usize = 0;
+/// Default amount of threads per device that decode images in background, images are decoded on device thread by default
+pub const DEFAULT_IMAGE_WORKERS: usize = 0;
 /// Name of the fonts folder
 pub const FONTS_FOLDER: &'static str = "fonts";
 /// Name of the device config folder
@@ -33,6 +44,8 @@ pub const DEVICE_CONFIG_FOLDER: &'static str = "devices";
 pub const PLUGINS_FOLDER: &'static str = "plugins";
 /// Name of the plugin settings file
 pub const PLUGINS_SETTINGS_FILE: &'static str = "global.json";
+/// Name of the button templates file
+pub const BUTTON_TEMPLATES_FILE: &'static str = "templates.json";
 /// Name of the config file
 pub const CONFIG_FILE: &'static str = "config.toml";
 
@@ -82,6 +95,8 @@ pub struct Config {
     plugin_settings_path: Option<PathBuf>,
     /// Path to fonts
     font_path: Option<PathBuf>,
+    /// Path to button templates json
+    button_templates_path: Option<PathBuf>,
 
     /// Config folder
     config_dir: Option<PathBuf>,
@@ -94,10 +109,42 @@ pub struct Config {
     /// If plugin compatibility checks should be performed
     plugin_compatibility_checks: Option<bool>,
 
+    /// Max size of decoded image data in bytes that can be added to image collection
+    max_image_size: Option<usize>,
+
+    /// If device config folder should be watched for changes, reloading changed configs
+    watch_device_configs: Option<bool>,
+    /// Time in milliseconds to wait for more changes before reloading a changed device config
+    watch_debounce: Option<u64>,
+
+    /// If daemon should run in safe mode, not loading any plugins and showing placeholders on buttons with components of missing modules
+    safe_mode: Option<bool>,
+
+    /// Amount of recent events to keep per device, 0 disables the event log
+    event_log_size: Option<usize>,
+
+    /// Path of JSONL file that button actions get appended to, button actions aren't logged if not set
+    action_log_path: Option<PathBuf>,
+
+    /// Amount of threads per device that decode images in background, 0 decodes images on device thread
+    image_workers: Option<usize>,
+
+    /// Path of the config file that was loaded
+    #[serde(skip)]
+    config_path: Option<PathBuf>,
+
+    /// Content hashes of last writes of device configs done by the daemon itself
+    #[serde(skip)]
+    last_writes: RwLock<HashMap<String, String>>,
+
     /// Currently loaded plugin settings
     #[serde(skip)]
     pub plugin_settings: RwLock<HashMap<String, Value>>,
 
+    /// Currently loaded button templates
+    #[serde(skip)]
+    pub button_templates: RwLock<HashMap<String, Button>>,
+
     /// Currently loaded device configs
     #[serde(skip)]
     pub loaded_configs: RwLock<HashMap<String, UniqueDeviceConfig>>,
@@ -110,9 +157,9 @@ pub struct Config {
 #[allow(dead_code)]
 impl Config {
     /// Reads config and retrieves config struct
-    pub async fn get(custom_config_path: Option<PathBuf>) -> Config {
-        let config_dir = config_dir();
-        let data_dir = data_dir();
+    pub async fn get(custom_config_path: Option<PathBuf>, custom_root: Option<PathBuf>) -> Config {
+        let config_dir = custom_root.clone().unwrap_or_else(config_dir);
+        let data_dir = custom_root.clone().unwrap_or_else(data_dir);
 
         let path: PathBuf = custom_config_path.unwrap_or_else(|| {
             let mut dir = config_dir.clone();
@@ -122,7 +169,7 @@ impl Config {
 
         log::info!("Config path: {}", path.display());
 
-        let mut config: Config = match fs::read_to_string(path).await {
+        let mut config: Config = match fs::read_to_string(&path).await {
             Ok(content) => {
                 match toml::from_str(&content) {
                     Ok(config) => config,
@@ -143,15 +190,19 @@ impl Config {
             }
         };
 
-        if config.data_dir == None {
+        // Root override takes priority over folders that were set in config file
+        if config.data_dir == None || custom_root.is_some() {
             config.data_dir = Some(data_dir);
         }
 
-        if config.config_dir == None {
+        if config.config_dir == None || custom_root.is_some() {
             config.config_dir = Some(config_dir);
         }
 
+        config.config_path = Some(path);
+
         config.load_plugin_settings().await;
+        config.load_button_templates().await;
 
         log::debug!("config: {:#?}", config);
         config
@@ -177,6 +228,46 @@ impl Config {
         self.plugin_compatibility_checks.unwrap_or(true)
     }
 
+    /// Max image size in bytes, defaults to [DEFAULT_MAX_IMAGE_SIZE] if not set
+    pub fn max_image_size(&self) -> usize {
+        self.max_image_size.unwrap_or(DEFAULT_MAX_IMAGE_SIZE)
+    }
+
+    /// Device config watching, defaults to false if not set
+    pub fn watch_device_configs(&self) -> bool {
+        self.watch_device_configs.unwrap_or(false)
+    }
+
+    /// Debounce time for device config watching, defaults to [DEFAULT_WATCH_DEBOUNCE] if not set
+    pub fn watch_debounce(&self) -> Duration {
+        Duration::from_millis(self.watch_debounce.unwrap_or(DEFAULT_WATCH_DEBOUNCE))
+    }
+
+    /// Safe mode, defaults to false if not set
+    pub fn safe_mode(&self) -> bool {
+        self.safe_mode.unwrap_or(false)
+    }
+
+    /// Enables safe mode regardless of what was set in config file
+    pub fn enable_safe_mode(&mut self) {
+        self.safe_mode = Some(true);
+    }
+
+    /// Event log size, defaults to [DEFAULT_EVENT_LOG_SIZE] if not set
+    pub fn event_log_size(&self) -> usize {
+        self.event_log_size.unwrap_or(DEFAULT_EVENT_LOG_SIZE)
+    }
+
+    /// Amount of image decoding threads per device, defaults to [DEFAULT_IMAGE_WORKERS] if not set
+    pub fn image_workers(&self) -> usize {
+        self.image_workers.unwrap_or(DEFAULT_IMAGE_WORKERS)
+    }
+
+    /// Action log path, button actions aren't logged if not set
+    pub fn action_log_path(&self) -> Option<&PathBuf> {
+        self.action_log_path.as_ref()
+    }
+
     /// Device config path, defaults to [data_dir]/[DEVICE_CONFIG_FOLDER] or [DEVICE_CONFIG_FOLDER] if not set
     pub fn device_config_path(&self) -> PathBuf {
         self.device_config_path.clone().unwrap_or_else(|| {
@@ -216,6 +307,15 @@ impl Config {
         })
     }
 
+    /// Button templates file path, defaults to [data_dir]/[BUTTON_TEMPLATES_FILE] or [BUTTON_TEMPLATES_FILE] if not set
+    pub fn button_templates_path(&self) -> PathBuf {
+        self.button_templates_path.clone().unwrap_or_else(|| {
+                let mut dir = self.data_dir().clone();
+                dir.push(BUTTON_TEMPLATES_FILE);
+                dir
+        })
+    }
+
     /// Data path, defaults to [dirs::data_dir()] if not set
     pub fn data_dir(&self) -> &PathBuf {
         &self.data_dir.as_ref().expect("data_dir not available")
@@ -226,6 +326,15 @@ impl Config {
         &self.config_dir.as_ref().expect("config_dir not available")
     }
 
+    /// Path of the loaded config file, defaults to [config_dir]/[CONFIG_FILE] if config wasn't loaded with [Config::get]
+    pub fn config_path(&self) -> PathBuf {
+        self.config_path.clone().unwrap_or_else(|| {
+            let mut dir = self.config_dir().clone();
+            dir.push(CONFIG_FILE);
+            dir
+        })
+    }
+
     /// Loads plugin settings from file
     pub async fn load_plugin_settings(&self) {
         if let Ok(settings) = fs::read_to_string(self.plugin_settings_path()).await {
@@ -261,6 +370,82 @@ impl Config {
         }
     }
 
+    /// Loads button templates from file
+    pub async fn load_button_templates(&self) {
+        if let Ok(templates) = fs::read_to_string(self.button_templates_path()).await {
+            let mut lock = self.button_templates.write().await;
+
+            match serde_json::from_str(&templates) {
+                Ok(vals) => *lock = vals,
+                Err(err) => log::error!("Failed to parse button templates: {:?}", err),
+            }
+        }
+    }
+
+    /// Lists names of all button templates
+    pub async fn list_button_templates(&self) -> Vec<String> {
+        let lock = self.button_templates.read().await;
+        let mut names: Vec<String> = lock.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Retrieves button template if it exists
+    pub async fn get_button_template(&self, name: &str) -> Option<Button> {
+        self.button_templates.read().await.get(name).cloned()
+    }
+
+    /// Sets button template, overriding any existing template with the same name
+    pub async fn set_button_template(&self, name: &str, button: Button) {
+        let mut lock = self.button_templates.write().await;
+        lock.insert(name.to_string(), button);
+        drop(lock);
+
+        self.write_button_templates().await;
+    }
+
+    /// Removes button template, returns false if template didn't exist
+    pub async fn remove_button_template(&self, name: &str) -> bool {
+        let mut lock = self.button_templates.write().await;
+        let removed = lock.remove(name).is_some();
+        drop(lock);
+
+        if removed {
+            self.write_button_templates().await;
+        }
+
+        removed
+    }
+
+    /// Writes button templates to file
+    pub async fn write_button_templates(&self) {
+        let lock = self.button_templates.read().await;
+        if let Err(err) = fs::write(self.button_templates_path(), serde_json::to_string(lock.deref()).unwrap()).await {
+            log::error!("Failed to write button templates: {:?}", err);
+        }
+    }
+
+    /// Appends entry to the action log, does nothing if action log path isn't set
+    pub async fn append_action_log(&self, entry: &ActionLogEntry) {
+        if let Some(path) = self.action_log_path() {
+            let mut line = serde_json::to_string(entry).unwrap();
+            line.push('\n');
+
+            let result = async {
+                let mut file = fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path).await?;
+
+                file.write_all(line.as_bytes()).await
+            }.await;
+
+            if let Err(err) = result {
+                log::error!("Failed to write to action log: {:?}", err);
+            }
+        }
+    }
+
     /// Reloads device config for specified serial
     pub async fn reload_device_config(&self, serial: &str) -> Result<(), ConfigError> {
         // Clearing image collection to make sure it's fresh for reload
@@ -354,13 +539,79 @@ impl Config {
         let mut path = self.device_config_path();
         let mut device_conf = device.write().await;
         path.push(format!("{}.json", device_conf.serial));
-        fs::write(path, serde_json::to_string(device_conf.deref()).unwrap()).await?;
+        let contents = serde_json::to_string(device_conf.deref()).unwrap();
+        fs::write(path, &contents).await?;
 
         device_conf.mark_clean();
+        self.last_writes.write().await.insert(device_conf.serial.clone(), hash_str(&contents));
 
         Ok(())
     }
 
+    /// Checks if device config file for specified serial still has exactly the contents that daemon last wrote into it
+    pub async fn is_own_write(&self, serial: &str) -> bool {
+        let hash = if let Some(hash) = self.last_writes.read().await.get(serial) {
+            hash.clone()
+        } else {
+            return false;
+        };
+
+        let mut path = self.device_config_path();
+        path.push(format!("{}.json", serial));
+
+        match fs::read_to_string(path).await {
+            Ok(contents) => hash_str(&contents) == hash,
+            Err(_) => false
+        }
+    }
+
+    /// Lists device config files that are stored in device config folder, including disabled ones
+    pub async fn list_device_config_files(&self) -> Result<Vec<DeviceConfigFile>, ConfigError> {
+        let mut configs = vec![];
+
+        let mut dir = match fs::read_dir(self.device_config_path()).await {
+            Ok(dir) => dir,
+            Err(err) => {
+                return if let std::io::ErrorKind::NotFound = err.kind() {
+                    Ok(configs)
+                } else {
+                    Err(err.into())
+                }
+            }
+        };
+
+        while let Some(item) = dir.next_entry().await? {
+            let path = item.path();
+
+            if !path.is_file() {
+                continue;
+            }
+
+            let disabled = match path.extension().and_then(|x| x.to_str()) {
+                Some("json") => false,
+                Some("json_disabled") => true,
+                _ => continue
+            };
+
+            if let Some(serial) = path.file_stem().and_then(|x| x.to_str()) {
+                let metadata = item.metadata().await?;
+
+                configs.push(DeviceConfigFile {
+                    serial: serial.to_string(),
+                    size: metadata.len(),
+                    modified: metadata.modified().ok()
+                        .and_then(|x| x.duration_since(UNIX_EPOCH).ok())
+                        .map(|x| x.as_secs()),
+                    disabled
+                });
+            }
+        }
+
+        configs.sort_by(|a, b| a.serial.cmp(&b.serial));
+
+        Ok(configs)
+    }
+
     /// Retrieves device config for specified serial
     pub async fn get_device_config(&self, serial: &str) -> Option<UniqueDeviceConfig> {
         self.loaded_configs.read().await.get(serial).cloned()
@@ -413,9 +664,15 @@ impl Config {
         if let Some(config) = self.get_device_config(serial).await {
             let mut config_handle = config.write().await;
             let identifier = hash_str(&image);
+            let metadata = ImageMetadata::from_base64(&image);
 
             if let Ok(image) = SDImage::from_base64(&image, config_handle.kind().image_size()).await {
                 config_handle.images.insert(identifier.clone(), image.into());
+
+                if let Some(metadata) = metadata {
+                    config_handle.image_metadata.insert(identifier.clone(), metadata);
+                }
+
                 drop(config_handle);
 
                 self.update_collection(&config).await;
@@ -434,6 +691,11 @@ impl Config {
             let mut config_handle = config.write().await;
             let serialized_image = SDImage::SingleImage(resize_for_streamdeck(config_handle.kind().image_size(), image)).into();
             let identifier = hash_image(&serialized_image);
+
+            if let Some(metadata) = serialized_image.as_image_blob().ok().and_then(|blob| ImageMetadata::from_base64(&blob)) {
+                config_handle.image_metadata.insert(identifier.clone(), metadata);
+            }
+
             config_handle.images.insert(identifier.clone(), serialized_image);
             drop(config_handle);
 
@@ -454,11 +716,30 @@ impl Config {
         }
     }
 
+    /// Gets metadata of images from device config, metadata of images that were stored without it is read from stored image data
+    pub async fn get_image_metadata(&self, serial: &str) -> Option<HashMap<String, ImageMetadata>> {
+        let config = self.get_device_config(serial).await?;
+        let mut config_handle = config.write().await;
+
+        let missing = config_handle.images.iter()
+            .filter(|(identifier, _)| !config_handle.image_metadata.contains_key(*identifier))
+            .filter_map(|(identifier, image)| Some((identifier.clone(), ImageMetadata::from_base64(&image.as_image_blob().ok()?)?)))
+            .collect::<Vec<(String, ImageMetadata)>>();
+
+        if !missing.is_empty() {
+            config_handle.image_metadata.extend(missing);
+            config_handle.dirty_state = true;
+        }
+
+        Some(config_handle.image_metadata.clone())
+    }
+
     /// Removes image from device config
     pub async fn remove_image(&self, serial: &str, identifier: &str) -> bool {
         if let Some(config) = self.get_device_config(serial).await {
             let mut config_handle = config.write().await;
             config_handle.images.remove(identifier);
+            config_handle.image_metadata.remove(identifier);
             drop(config_handle);
 
             self.remove_from_collection(serial, identifier).await;
@@ -554,6 +835,32 @@ impl From<serde_json::Error> for ConfigError {
     }
 }
 
+/// Information about device config file stored on filesystem
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DeviceConfigFile {
+    /// Serial number of the device the config is for
+    pub serial: String,
+    /// Size of the file in bytes
+    pub size: u64,
+    /// Last modification time in seconds since unix epoch, if available
+    pub modified: Option<u64>,
+    /// If config was disabled and won't be loaded by default
+    pub disabled: bool,
+}
+
+/// Entry of the action log, written for every button action if action log is enabled
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ActionLogEntry {
+    /// Serial number of the device
+    pub serial_number: String,
+    /// Key index
+    pub key: u8,
+    /// Names of components the button had
+    pub component_names: Vec<String>,
+    /// Time of the action in milliseconds since unix epoch
+    pub timestamp: u64,
+}
+
 /// Device config struct
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct DeviceConfig {
@@ -569,8 +876,23 @@ pub struct DeviceConfig {
     pub layout: RawButtonPanel,
     /// Image collection
     pub images: HashMap<String, SDSerializedImage>,
+    /// Metadata of images in image collection
+    #[serde(default)]
+    pub image_metadata: HashMap<String, ImageMetadata>,
     /// Device-related plugin data
     pub plugin_data: HashMap<String, Value>,
+    /// If device rejects any changes to its layout
+    #[serde(default)]
+    pub locked: bool,
+    /// User assigned name of the device
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub nickname: String,
+    /// Max amount of key presses per second on each key, presses over the rate get dropped together with their releases. 0 disables throttling
+    #[serde(default)]
+    pub key_event_rate: u32,
+    /// If render cache should be bypassed for the device, every button gets rendered again on each frame
+    #[serde(default)]
+    pub disable_render_cache: bool,
     #[serde(skip)]
     /// Last time the config was committed
     pub commit_time: Option<Instant>,
@@ -606,6 +928,73 @@ impl DeviceConfig {
     pub fn commit_duration(&self) -> Duration {
         Instant::now().duration_since(self.commit_time.unwrap_or(Instant::now()))
     }
+
+    /// Names of fonts that are referenced by buttons of the layout, including nested panels
+    pub fn referenced_fonts(&self) -> Vec<String> {
+        let mut fonts = HashSet::new();
+
+        if let Ok(layout) = serde_json::to_value(&self.layout) {
+            collect_fonts(&layout, &mut fonts);
+        }
+
+        let mut fonts: Vec<String> = fonts.into_iter().collect();
+        fonts.sort();
+        fonts
+    }
+}
+
+/// Recursively looks for font names in serialized layout
+fn collect_fonts(value: &Value, fonts: &mut HashSet<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                match value {
+                    Value::String(name) if key == "font" => {
+                        if !name.is_empty() {
+                            fonts.insert(name.clone());
+                        }
+                    }
+
+                    _ => collect_fonts(value, fonts)
+                }
+            }
+        }
+
+        Value::Array(array) => {
+            for value in array {
+                collect_fonts(value, fonts);
+            }
+        }
+
+        _ => {}
+    }
+}
+
+/// Self-contained device config that can be moved to another machine, images are embedded into the config and fonts are referenced by name
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DeviceBundle {
+    /// Device config together with its image collection
+    pub config: DeviceConfig,
+    /// Names of fonts that the layout uses
+    pub fonts: Vec<String>,
+}
+
+impl DeviceBundle {
+    /// Creates bundle out of device config
+    pub fn from_config(config: &DeviceConfig) -> DeviceBundle {
+        DeviceBundle {
+            config: config.clone(),
+            fonts: config.referenced_fonts()
+        }
+    }
+
+    /// Names of fonts used by the bundle that aren't present in font collection
+    pub fn missing_fonts(&self) -> Vec<String> {
+        self.fonts.iter()
+            .filter(|name| get_font_from_collection(name).is_none())
+            .cloned()
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -615,14 +1004,14 @@ mod tests {
     #[tokio::test]
     async fn config_sys_config_dir() {
         // check if config dir gets created
-        let config = Config::get(None).await;
+        let config = Config::get(None, None).await;
         assert_ne!(config.config_dir, None)
     }
 
     #[tokio::test]
     async fn config_sys_data_dir() {
         // check if data dir gets created
-        let config = Config::get(None).await;
+        let config = Config::get(None, None).await;
         assert_ne!(config.data_dir, None)
     }
 
@@ -636,7 +1025,12 @@ mod tests {
             brightness: Default::default(),
             layout: Default::default(),
             images: Default::default(),
+            image_metadata: Default::default(),
             plugin_data: Default::default(),
+            locked: false,
+            nickname: Default::default(),
+            key_event_rate: 0,
+            disable_render_cache: false,
             commit_time: Default::default(),
             dirty_state: true
         };
@@ -647,7 +1041,7 @@ mod tests {
 
     #[tokio::test]
     async fn config_filesystem_writing() { 
-        let config = Config::get(None).await;
+        let config = Config::get(None, None).await;
         // simulate a changed config
         let device_conf = DeviceConfig {
             vid: Default::default(),
@@ -656,7 +1050,12 @@ mod tests {
             brightness: Default::default(),
             layout: Default::default(),
             images: Default::default(),
+            image_metadata: Default::default(),
             plugin_data: Default::default(),
+            locked: false,
+            nickname: Default::default(),
+            key_event_rate: 0,
+            disable_render_cache: false,
             commit_time: Default::default(),
             dirty_state: true
         };
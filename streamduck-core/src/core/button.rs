@@ -9,6 +9,18 @@ use crate::core::UniqueButton;
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Button(pub HashMap<String, Value>);
 
+/// Reserved key in button's hashmap that holds tags of the button
+pub const BUTTON_TAGS_KEY: &'static str = "tags";
+
+/// Reserved key in button's hashmap that holds order of the components
+pub const BUTTON_COMPONENT_ORDER_KEY: &'static str = "component_order";
+
+/// Reserved key in button's hashmap that holds names of disabled components
+pub const BUTTON_DISABLED_COMPONENTS_KEY: &'static str = "disabled_components";
+
+/// Keys in button's hashmap that aren't components
+pub const BUTTON_RESERVED_KEYS: [&'static str; 3] = [BUTTON_TAGS_KEY, BUTTON_COMPONENT_ORDER_KEY, BUTTON_DISABLED_COMPONENTS_KEY];
+
 impl Button {
     /// Creates a new empty button
     pub fn new() -> Button {
@@ -25,15 +37,157 @@ impl Button {
         self.0.remove(T::NAME)
     }
 
-    /// Returns list of components in the button
+    /// Returns list of components in the button, ordered by component order if button has one
     pub fn component_names(&self) -> Vec<String> {
-        self.0.keys().map(|x| x.clone()).collect()
+        let order: Vec<String> = self.0.get(BUTTON_COMPONENT_ORDER_KEY)
+            .and_then(|x| serde_json::from_value(x.clone()).ok())
+            .unwrap_or_default();
+
+        let mut names: Vec<String> = self.0.keys()
+            .filter(|x| !BUTTON_RESERVED_KEYS.contains(&x.as_str()))
+            .map(|x| x.clone())
+            .collect();
+
+        names.sort_by_key(|x| order.iter().position(|o| o == x).unwrap_or(usize::MAX));
+
+        names
+    }
+
+    /// Moves component to new index in component order, out of range indices are clamped. Returns false if button doesn't have the component
+    pub fn move_component(&mut self, name: &str, index: usize) -> bool {
+        let mut names = self.component_names();
+
+        if let Some(position) = names.iter().position(|x| x == name) {
+            let name = names.remove(position);
+            names.insert(index.min(names.len()), name);
+
+            self.0.insert(BUTTON_COMPONENT_ORDER_KEY.to_string(), Value::from(names));
+
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns list of components in the button that aren't disabled, in component order
+    pub fn enabled_component_names(&self) -> Vec<String> {
+        let disabled = self.disabled_components();
+
+        self.component_names().into_iter()
+            .filter(|x| !disabled.contains(x))
+            .collect()
+    }
+
+    /// Returns names of disabled components
+    pub fn disabled_components(&self) -> Vec<String> {
+        self.0.get(BUTTON_DISABLED_COMPONENTS_KEY)
+            .and_then(|x| serde_json::from_value(x.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Checks if component is enabled
+    pub fn is_component_enabled(&self, name: &str) -> bool {
+        !self.disabled_components().iter().any(|x| x == name)
+    }
+
+    /// Enables or disables component, disabled components keep their values but are ignored by rendering and actions.
+    /// Returns false if button doesn't have the component
+    pub fn set_component_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        let names = self.component_names();
+
+        if !names.iter().any(|x| x == name) {
+            return false;
+        }
+
+        let mut disabled = self.disabled_components();
+        disabled.retain(|x| x != name && names.contains(x));
+
+        if !enabled {
+            disabled.push(name.to_string());
+        }
+
+        if disabled.is_empty() {
+            self.0.remove(BUTTON_DISABLED_COMPONENTS_KEY);
+        } else {
+            self.0.insert(BUTTON_DISABLED_COMPONENTS_KEY.to_string(), Value::from(disabled));
+        }
+
+        true
+    }
+
+    /// Returns tags of the button
+    pub fn tags(&self) -> Vec<String> {
+        self.0.get(BUTTON_TAGS_KEY)
+            .and_then(|x| serde_json::from_value(x.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Sets tags of the button, empty tag list will be removed from the button entirely
+    pub fn set_tags(&mut self, tags: Vec<String>) {
+        if tags.is_empty() {
+            self.0.remove(BUTTON_TAGS_KEY);
+        } else {
+            self.0.insert(BUTTON_TAGS_KEY.to_string(), Value::from(tags));
+        }
+    }
+
+    /// Adds a tag to the button if it wasn't already present
+    pub fn add_tag(&mut self, tag: &str) {
+        let mut tags = self.tags();
+
+        if !tags.iter().any(|x| x == tag) {
+            tags.push(tag.to_string());
+            self.set_tags(tags);
+        }
+    }
+
+    /// Removes a tag from the button
+    pub fn remove_tag(&mut self, tag: &str) {
+        let mut tags = self.tags();
+        tags.retain(|x| x != tag);
+        self.set_tags(tags);
+    }
+
+    /// Checks if button has specified tag
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags().iter().any(|x| x == tag)
     }
 
     /// Checks if button contains specified component name
     pub fn contains(&self, name: &str) -> bool {
         self.0.contains_key(name)
     }
+
+    /// Copies tags, component order and disabled components from reference button,
+    /// component names that this button doesn't have are left out
+    pub fn copy_metadata_from(&mut self, reference: &Button) {
+        let names = self.component_names();
+
+        self.set_tags(reference.tags());
+
+        let order: Vec<String> = reference.0.get(BUTTON_COMPONENT_ORDER_KEY)
+            .and_then(|x| serde_json::from_value::<Vec<String>>(x.clone()).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|x| names.contains(x))
+            .collect();
+
+        if order.is_empty() {
+            self.0.remove(BUTTON_COMPONENT_ORDER_KEY);
+        } else {
+            self.0.insert(BUTTON_COMPONENT_ORDER_KEY.to_string(), Value::from(order));
+        }
+
+        let disabled: Vec<String> = reference.disabled_components().into_iter()
+            .filter(|x| names.contains(x))
+            .collect();
+
+        if disabled.is_empty() {
+            self.0.remove(BUTTON_DISABLED_COMPONENTS_KEY);
+        } else {
+            self.0.insert(BUTTON_DISABLED_COMPONENTS_KEY.to_string(), Value::from(disabled));
+        }
+    }
 }
 
 /// Component trait, simply provides name for component generic methods
@@ -74,4 +228,33 @@ impl From<serde_json::Error> for ParseError {
     fn from(err: Error) -> Self {
         ParseError::JSONError(err)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn button_copy_metadata_from() {
+        let mut reference = Button::new();
+        reference.0.insert("first".to_string(), Value::Null);
+        reference.0.insert("second".to_string(), Value::Null);
+        reference.0.insert("removed".to_string(), Value::Null);
+        reference.set_tags(vec!["tag".to_string()]);
+        reference.move_component("second", 0);
+        reference.move_component("removed", 0);
+        reference.set_component_enabled("first", false);
+        reference.set_component_enabled("removed", false);
+
+        // simulate modules pasting only some of the components
+        let mut pasted = Button::new();
+        pasted.0.insert("first".to_string(), Value::Null);
+        pasted.0.insert("second".to_string(), Value::Null);
+        pasted.copy_metadata_from(&reference);
+
+        assert_eq!(pasted.tags(), vec!["tag".to_string()]);
+        assert_eq!(pasted.component_names(), vec!["second".to_string(), "first".to_string()]);
+        assert_eq!(pasted.disabled_components(), vec!["first".to_string()]);
+        assert_eq!(pasted.0.get(BUTTON_COMPONENT_ORDER_KEY), Some(&Value::from(vec!["second", "first"])));
+    }
+}
@@ -4,13 +4,14 @@ use std::collections::HashMap;
 use std::sync::{Arc};
 use std::time::Duration;
 use futures::{stream, StreamExt};
-use crate::core::{RawButtonPanel, SDCore};
+use crate::core::{generate_panel_id, RawButtonPanel, SDCore};
 use crate::core::methods::CoreHandle;
 use hidapi::HidApi;
 use serde_json::Value;
 use tokio::sync::RwLock;
 use tokio::time::sleep;
 use crate::config::{Config, DeviceConfig};
+use crate::modules::events::SDGlobalEvent;
 use crate::{connect, find_decks, ModuleManager, RenderingManager, SocketManager};
 use crate::util::{make_panel_unique};
 
@@ -91,6 +92,11 @@ impl CoreManager {
             self.config.restore_device_config(serial).await;
 
             handle.insert(serial.to_string(), data.clone());
+            drop(handle);
+
+            self.module_manager.send_global_event_to_modules(SDGlobalEvent::DeviceAdded {
+                serial_number: serial.to_string()
+            }).await;
         }
     }
 
@@ -108,12 +114,18 @@ impl CoreManager {
                 serial: serial.to_string(),
                 brightness: 50,
                 layout: RawButtonPanel {
+                    id: generate_panel_id(),
                     display_name: "Root".to_string(),
                     data: Value::Null,
                     buttons: Default::default()
                 },
                 images: Default::default(),
+                image_metadata: Default::default(),
                 plugin_data: Default::default(),
+                locked: false,
+                nickname: Default::default(),
+                key_event_rate: 0,
+                disable_render_cache: false,
                 commit_time: Default::default(),
                 dirty_state: false,
             }).await;
@@ -156,11 +168,17 @@ impl CoreManager {
     pub async fn remove_device(&self, serial: &str) {
         let mut handle = self.devices.write().await;
         let data = handle.remove(serial);
+        drop(handle);
 
         if let Some(data) = data {
             data.core.close().await;
+            data.core.join_device_thread().await;
             self.config.disable_device_config(serial).await;
             self.config.reload_device_configs().await.ok();
+
+            self.module_manager.send_global_event_to_modules(SDGlobalEvent::DeviceRemoved {
+                serial_number: serial.to_string()
+            }).await;
         }
     }
 
@@ -1,25 +1,28 @@
 use std::collections::HashMap;
-use std::ops::DerefMut;
+use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use image::{DynamicImage, Rgba};
+use futures::future::join_all;
+use image::{DynamicImage, GenericImage, Rgba, RgbaImage};
+use image::imageops::FilterType;
 use serde::de::Error as DeError;
 use serde_json::{Map, Value};
 use serde_json::Error as JSONError;
 use tokio::sync::MutexGuard;
 
 use crate::{Config, ModuleManager, SDCore, SocketManager};
-use crate::core::{ButtonPanel, UniqueButton};
+use crate::config::ActionLogEntry;
+use crate::core::{ButtonPanel, generate_panel_id, RawButtonPanel, UniqueButton, UniqueButtonMap};
 use crate::core::button::{Button, parse_unique_button_to_component};
 use crate::modules::{features_to_vec, UniqueSDModule};
-use crate::modules::components::{UIPathValue, UIValue};
+use crate::modules::components::{resolve_component_dependencies, UIPathValue, UIValue};
 use crate::modules::core_module::CoreSettings;
-use crate::modules::events::SDCoreEvent;
+use crate::modules::events::{core_event_to_global, SDCoreEvent};
 use crate::thread::DeviceThreadCommunication;
 use crate::thread::rendering::{draw_background, draw_custom_renderer_texture, draw_foreground, draw_missing_texture, RendererComponent};
 use crate::thread::util::image_from_solid;
-use crate::util::{add_array_function, button_to_raw, change_from_path, convert_value_to_path, deserialize_panel, make_button_unique, panel_to_raw, remove_array_function, serialize_panel, set_value_function};
+use crate::util::{add_array_function, button_to_raw, change_from_path, convert_value_to_path, coords_from_key, key_from_coords, deserialize_panel, make_button_unique, make_panel_unique, panel_to_raw, remove_array_function, serialize_panel, set_value_function};
 use crate::versions::SUPPORTED_FEATURES;
 
 /// Handle that's given out to a module to perform actions on the core
@@ -48,6 +51,16 @@ pub fn warn_for_feature(module_name: &str, features: &Vec<(String, String)>, fea
     }
 }
 
+/// Gets provided panel or top of the stack if None, together with if the panel is on top of provided stack
+fn screen_with_top_in_stack(stack: &[ButtonPanel], panel: Option<&ButtonPanel>) -> Option<(ButtonPanel, bool)> {
+    let top = stack.last();
+
+    match panel {
+        Some(panel) => Some((panel.clone(), top.map_or(false, |x| Arc::ptr_eq(x, panel)))),
+        None => top.map(|x| (x.clone(), true))
+    }
+}
+
 impl CoreHandle {
     /// Wraps core reference with a handle, used for all core features to be able to bypass feature checking
     pub fn wrap(core: Arc<SDCore>) -> CoreHandle {
@@ -109,6 +122,8 @@ impl CoreHandle {
 
     /// Sends core event to all modules, spawns a separate thread to do it, so doesn't block current thread
     pub async fn send_core_event_to_modules<T: Iterator<Item=UniqueSDModule> + Send + 'static>(&self, event: SDCoreEvent, modules: T) {
+        self.log_core_event(&event).await;
+
         let core = self.clone();
         for module in modules {
             if module.name() == core.module_name {
@@ -117,12 +132,53 @@ impl CoreHandle {
 
             let task_core = core.clone_for(&module);
             let task_event = event.clone();
+            let module_manager = core.module_manager();
             tokio::spawn(async move {
-                module.event(task_core, task_event).await;
+                module_manager.run_module_event(&module.name(), module.event(task_core, task_event)).await;
             });
         }
     }
 
+    /// Sends core event to all modules and waits until all of them finish handling it, for operations that need module state to be settled
+    pub async fn send_core_event_to_modules_blocking<T: Iterator<Item=UniqueSDModule>>(&self, event: SDCoreEvent, modules: T) {
+        self.log_core_event(&event).await;
+
+        join_all(modules
+            .filter(|module| module.name() != self.module_name)
+            .map(|module| {
+                let task_core = self.clone_for(&module);
+                let task_event = event.clone();
+                let module_manager = self.module_manager();
+
+                async move {
+                    module_manager.run_module_event(&module.name(), module.event(task_core, task_event)).await;
+                }
+            })).await;
+    }
+
+    /// Adds event to event log of the core if event log is enabled
+    async fn log_core_event(&self, event: &SDCoreEvent) {
+        if self.core.config.event_log_size() > 0 {
+            self.core.log_event(core_event_to_global(event.clone(), &self.core.serial_number).await).await;
+        }
+    }
+
+    /// Sends stack changed event with names of panels that are currently in the stack
+    async fn send_stack_changed_event(&self) {
+        let stack = self.get_stack().await;
+
+        let mut stack_names = vec![];
+
+        for panel in &stack {
+            stack_names.push(panel.read().await.display_name.clone());
+        }
+
+        self.send_core_event_to_modules(SDCoreEvent::StackChanged {
+            depth: stack_names.len(),
+            stack_names
+        }, self.module_manager().get_module_list().await.into_iter()).await;
+    }
+
     /// Gets current panel stack
     pub async fn get_stack(&self) -> Vec<ButtonPanel> {
         self.required_feature("core_methods");
@@ -131,6 +187,43 @@ impl CoreHandle {
         stack.iter().map(|x| x.clone()).collect()
     }
 
+    /// Calls provided function with borrowed stack while stack lock is held, avoids cloning the stack.
+    ///
+    /// Function must not call back into any methods that lock the stack, that will deadlock
+    pub async fn with_stack<F, R>(&self, f: F) -> R where F: FnOnce(&[ButtonPanel]) -> R {
+        self.required_feature("core_methods");
+        let stack = self.current_stack().await;
+
+        f(&stack)
+    }
+
+    /// Gets amount of panels in the stack
+    pub async fn stack_depth(&self) -> usize {
+        self.required_feature("core_methods");
+        self.current_stack().await.len()
+    }
+
+    /// Gets panel at specified index of the stack, 0 being the root screen
+    pub async fn peek_screen_at(&self, index: usize) -> Option<ButtonPanel> {
+        self.required_feature("core_methods");
+        self.current_stack().await.get(index).cloned()
+    }
+
+    /// Checks if provided panel is currently on top of the stack
+    pub async fn is_top_screen(&self, panel: &ButtonPanel) -> bool {
+        self.required_feature("core_methods");
+        let stack = self.current_stack().await;
+
+        stack.last().map(|x| Arc::ptr_eq(x, panel)).unwrap_or(false)
+    }
+
+    /// Gets provided panel or current screen if None, together with if the panel is on top of the stack.
+    /// Both are taken under the same stack lock, so events can report state the operation was done on
+    async fn screen_with_top(&self, panel: Option<&ButtonPanel>) -> Option<(ButtonPanel, bool)> {
+        let stack = self.current_stack().await;
+        screen_with_top_in_stack(&stack, panel)
+    }
+
     /// Gets panel that's currently on top of the stack
     pub async fn get_current_screen(&self) -> Option<ButtonPanel> {
         self.required_feature("core_methods");
@@ -143,6 +236,25 @@ impl CoreHandle {
         }
     }
 
+    /// Creates a fully independent copy of provided panel, including all of its buttons and their component data, copy gets a new panel ID
+    pub async fn deep_clone_screen(&self, panel: &ButtonPanel) -> ButtonPanel {
+        self.required_feature("core_methods");
+        let mut raw = panel_to_raw(panel).await;
+        raw.id = generate_panel_id();
+
+        make_panel_unique(raw)
+    }
+
+    /// Converts row and column of the device's key grid into key index, returns None if coordinates are out of range
+    pub fn key_from_coords(&self, row: u8, column: u8) -> Option<u8> {
+        key_from_coords(self.core.key_grid, row, column)
+    }
+
+    /// Converts key index into row and column of the device's key grid, returns None if key is out of range
+    pub fn coords_from_key(&self, key: u8) -> Option<(u8, u8)> {
+        coords_from_key(self.core.key_grid, key)
+    }
+
     /// Returns a button from current screen on specified position
     pub async fn get_button(&self, key: u8) -> Option<UniqueButton> {
         self.required_feature("core_methods");
@@ -154,10 +266,35 @@ impl CoreHandle {
         }
     }
 
+    /// Finds buttons on current screen that have specified tag
+    pub async fn find_buttons_by_tag(&self, tag: &str) -> Vec<(u8, UniqueButton)> {
+        self.required_feature("core_methods");
+
+        let mut found = vec![];
+
+        if let Some(screen) = self.get_current_screen().await {
+            let buttons = screen.read().await.buttons.clone();
+
+            for (key, button) in buttons {
+                if button.read().await.has_tag(tag) {
+                    found.push((key, button));
+                }
+            }
+        }
+
+        found.sort_by_key(|(key, _)| *key);
+
+        found
+    }
+
     /// Sets button to current screen with specified position
     pub async fn set_button(&self, key: u8, button: UniqueButton) -> bool {
         self.required_feature("core_methods");
-        if let Some(screen) = self.get_current_screen().await {
+
+        if self.is_locked().await {
+            return false;
+        }
+        if let Some((screen, is_top)) = self.screen_with_top(None).await {
             let mut handle = screen.write().await;
             let previous_button = handle.buttons.get(&key).cloned();
 
@@ -169,6 +306,7 @@ impl CoreHandle {
                 self.send_core_event_to_modules(SDCoreEvent::ButtonUpdated {
                     key,
                     panel: screen.clone(),
+                    is_top,
                     new_button: button.clone(),
                     old_button: previous_button.clone()
                 }, self.module_manager().get_module_list().await.into_iter()).await;
@@ -176,6 +314,7 @@ impl CoreHandle {
                 self.send_core_event_to_modules( SDCoreEvent::ButtonAdded {
                     key,
                     panel: screen.clone(),
+                    is_top,
                     added_button: button.clone()
                 }, self.module_manager().get_module_list().await.into_iter()).await;
             }
@@ -191,7 +330,11 @@ impl CoreHandle {
     /// Clears button from current screen on specified position
     pub async fn clear_button(&self, key: u8) -> bool {
         self.required_feature("core_methods");
-        if let Some(screen) = self.get_current_screen().await {
+
+        if self.is_locked().await {
+            return false;
+        }
+        if let Some((screen, is_top)) = self.screen_with_top(None).await {
             let mut handle = screen.write().await;
             if let Some(button) = handle.buttons.remove(&key) {
                 drop(handle);
@@ -199,6 +342,7 @@ impl CoreHandle {
                 self.send_core_event_to_modules( SDCoreEvent::ButtonDeleted {
                     key,
                     panel: screen.clone(),
+                    is_top,
                     deleted_button: button.clone()
                 }, self.module_manager().get_module_list().await.into_iter()).await;
 
@@ -213,13 +357,17 @@ impl CoreHandle {
         }
     }
 
-    /// Adds component onto a button, returns success boolean
+    /// Adds component onto a button together with any missing components it requires, returns success boolean
     pub async fn add_component(&self, key: u8, component_name: &str) -> bool {
         self.required_feature("core_methods");
 
+        if self.is_locked().await {
+            return false;
+        }
+
         let module_manager = self.module_manager();
 
-        if let Some(screen) = self.get_current_screen().await {
+        if let Some((screen, is_top)) = self.screen_with_top(None).await {
             let handle = screen.read().await;
             if let Some(button) = handle.buttons.get(&key).cloned() {
                 let previous = make_button_unique(button_to_raw(&button).await);
@@ -227,11 +375,20 @@ impl CoreHandle {
                 let mut button_handle = button.write().await;
                 drop(handle);
 
-                if !button_handle.component_names().contains(&component_name.to_string()) {
+                let existing = button_handle.component_names();
+
+                if !existing.contains(&component_name.to_string()) {
                     let components = module_manager.read_component_map().await;
 
-                    if let Some((_, module)) = components.get(component_name) {
-                        module.add_component(self.clone_for(&module), button_handle.deref_mut(), component_name).await;
+                    // Adding missing dependencies first, requested component will be the last one
+                    let to_add = resolve_component_dependencies(&components, component_name, &existing);
+
+                    if !to_add.is_empty() {
+                        for name in &to_add {
+                            if let Some((_, module)) = components.get(name) {
+                                module.add_component(self.clone_for(&module), button_handle.deref_mut(), name).await;
+                            }
+                        }
 
                         drop(button_handle);
                         drop(components);
@@ -239,6 +396,7 @@ impl CoreHandle {
                         self.send_core_event_to_modules(SDCoreEvent::ButtonUpdated {
                             key,
                             panel: screen.clone(),
+                            is_top,
                             new_button: button.clone(),
                             old_button: previous.clone()
                         }, self.module_manager().get_module_list().await.into_iter()).await;
@@ -254,24 +412,79 @@ impl CoreHandle {
         false
     }
 
+    /// Adds component to buttons on each of provided keys, keys without a button are skipped. Returns keys the component was added to
+    pub async fn add_component_to_keys(&self, keys: Vec<u8>, component_name: &str) -> Vec<u8> {
+        self.required_feature("core_methods");
+
+        let mut changed = vec![];
+
+        for key in keys {
+            if self.add_component(key, component_name).await {
+                changed.push(key);
+            }
+        }
+
+        self.send_bulk_update_event(&changed).await;
+
+        changed
+    }
+
+    /// Sets component value by path on buttons on each of provided keys, keys without a button or the component are skipped. Returns keys the value was set on
+    pub async fn set_component_value_for_keys(&self, keys: Vec<u8>, component_name: &str, value: UIPathValue) -> Vec<u8> {
+        self.required_feature("core_methods");
+
+        let mut changed = vec![];
+
+        for key in keys {
+            if self.set_component_value_by_path(key, component_name, value.clone()).await {
+                changed.push(key);
+            }
+        }
+
+        self.send_bulk_update_event(&changed).await;
+
+        changed
+    }
+
+    /// Sends bulk update event for provided keys of current screen, if any keys were changed
+    async fn send_bulk_update_event(&self, keys: &[u8]) {
+        if keys.is_empty() {
+            return;
+        }
+
+        if let Some((screen, is_top)) = self.screen_with_top(None).await {
+            self.send_core_event_to_modules(SDCoreEvent::ButtonsBulkUpdated {
+                keys: keys.to_vec(),
+                panel: screen.clone(),
+                is_top
+            }, self.module_manager().get_module_list().await.into_iter()).await;
+        }
+    }
+
     /// Gets component values from a component on a button
     pub async fn get_component_values(&self, key: u8, component_name: &str) -> Option<Vec<UIValue>> {
         self.required_feature("core_methods");
 
+        let screen = self.get_current_screen().await?;
+        self.get_component_values_on_panel(&screen, key, component_name).await
+    }
+
+    /// Gets component values from a component on a button of specified panel, panel doesn't need to be on top of the stack
+    pub async fn get_component_values_on_panel(&self, screen: &ButtonPanel, key: u8, component_name: &str) -> Option<Vec<UIValue>> {
+        self.required_feature("core_methods");
+
         let module_manager = self.module_manager();
 
-        if let Some(screen) = self.get_current_screen().await {
-            let handle = screen.read().await;
-            if let Some(button) = handle.buttons.get(&key).cloned() {
-                let mut button_handle = button.write().await;
-                drop(handle);
+        let handle = screen.read().await;
+        if let Some(button) = handle.buttons.get(&key).cloned() {
+            let mut button_handle = button.write().await;
+            drop(handle);
 
-                if button_handle.component_names().contains(&component_name.to_string()) {
-                    let components = module_manager.read_component_map().await;
+            if button_handle.component_names().contains(&component_name.to_string()) {
+                let components = module_manager.read_component_map().await;
 
-                    if let Some((_, module)) = components.get(component_name) {
-                        return Some(module.component_values(self.clone_for(&module), button_handle.deref_mut(), component_name).await);
-                    }
+                if let Some((_, module)) = components.get(component_name) {
+                    return Some(module.component_values(self.clone_for(&module), button_handle.deref_mut(), component_name).await);
                 }
             }
         }
@@ -290,39 +503,98 @@ impl CoreHandle {
         }
     }
 
+    /// Gets values with paths of all components on a button, mapped by component name
+    pub async fn get_all_component_values(&self, key: u8) -> Option<HashMap<String, Vec<UIPathValue>>> {
+        self.required_feature("core_methods");
+
+        let button = self.get_button(key).await?;
+        let names = button.read().await.component_names();
+
+        let mut values = HashMap::new();
+
+        for name in names {
+            if let Some(component_values) = self.get_component_values_with_paths(key, &name).await {
+                values.insert(name, component_values);
+            }
+        }
+
+        Some(values)
+    }
+
     /// Sets component values based on changes for component on a button
     pub async fn set_component_value(&self, key: u8, component_name: &str, value: Vec<UIValue>) -> bool {
         self.required_feature("core_methods");
 
+        let paths = value.iter().map(|x| x.name.clone()).collect();
+        self.set_component_value_with_paths(None, key, component_name, value, paths).await
+    }
+
+    /// Sets component values based on changes for component on a button of specified panel, panel doesn't need to be on top of the stack
+    pub async fn set_component_value_on_panel(&self, screen: &ButtonPanel, key: u8, component_name: &str, value: Vec<UIValue>) -> bool {
+        self.required_feature("core_methods");
+
+        let paths = value.iter().map(|x| x.name.clone()).collect();
+        self.set_component_value_with_paths(Some(screen), key, component_name, value, paths).await
+    }
+
+    /// Sets component values on a button of specified panel or current screen if None, sending value changed event for each of provided paths
+    async fn set_component_value_with_paths(&self, panel: Option<&ButtonPanel>, key: u8, component_name: &str, value: Vec<UIValue>, paths: Vec<String>) -> bool {
+        if self.is_locked().await {
+            return false;
+        }
+
         let module_manager = self.module_manager();
 
-        if let Some(screen) = self.get_current_screen().await {
-            let handle = screen.read().await;
-            if let Some(button) = handle.buttons.get(&key).cloned() {
-                let previous = make_button_unique(button_to_raw(&button).await);
+        // Stack stays locked until the button is locked for writing, so is_top matches state the value gets set on
+        let stack = self.current_stack().await;
+        let (screen, is_top) = if let Some(found) = screen_with_top_in_stack(&stack, panel) {
+            found
+        } else {
+            return false;
+        };
 
-                let mut button_handle = button.write().await;
-                drop(handle);
+        let handle = screen.read().await;
+        if let Some(button) = handle.buttons.get(&key).cloned() {
+            let previous = make_button_unique(button_to_raw(&button).await);
 
-                if button_handle.component_names().contains(&component_name.to_string()) {
-                    let components = module_manager.read_component_map().await;
+            let mut button_handle = button.write().await;
+            drop(handle);
+            drop(stack);
 
-                    if let Some((_, module)) = components.get(component_name) {
-                        module.set_component_value(self.clone_for(&module), button_handle.deref_mut(), component_name, value).await;
-                        drop(button_handle);
-                        drop(components);
+            if button_handle.component_names().contains(&component_name.to_string()) {
+                let components = module_manager.read_component_map().await;
 
-                        self.send_core_event_to_modules(SDCoreEvent::ButtonUpdated {
+                if let Some((_, module)) = components.get(component_name) {
+                    if let Err(err) = module.validate_component_value(self.clone_for(&module), button_handle.deref(), component_name, &value).await {
+                        log::warn!("Rejected component value for '{}' on key {}: {}", component_name, key, err);
+                        return false;
+                    }
+
+                    module.set_component_value(self.clone_for(&module), button_handle.deref_mut(), component_name, value).await;
+                    drop(button_handle);
+                    drop(components);
+
+                    self.send_core_event_to_modules(SDCoreEvent::ButtonUpdated {
+                        key,
+                        panel: screen.clone(),
+                        is_top,
+                        new_button: button.clone(),
+                        old_button: previous.clone()
+                    }, self.module_manager().get_module_list().await.into_iter()).await;
+
+                    for path in paths {
+                        self.send_core_event_to_modules(SDCoreEvent::ComponentValueChanged {
                             key,
                             panel: screen.clone(),
-                            new_button: button.clone(),
-                            old_button: previous.clone()
+                            is_top,
+                            component: component_name.to_string(),
+                            path
                         }, self.module_manager().get_module_list().await.into_iter()).await;
+                    }
 
-                        self.core.mark_for_redraw().await;
+                    self.core.mark_for_redraw().await;
 
-                        return true;
-                    }
+                    return true;
                 }
             }
         }
@@ -330,6 +602,11 @@ impl CoreHandle {
         false
     }
 
+    /// Sets component values on a button of current screen, sending value changed event for each of provided paths
+    async fn set_current_component_value(&self, key: u8, component_name: &str, value: Vec<UIValue>, paths: Vec<String>) -> bool {
+        self.set_component_value_with_paths(None, key, component_name, value, paths).await
+    }
+
     /// Adds new array element to a component value
     pub async fn add_element_component_value(&self, key: u8, component_name: &str, path: &str) -> bool {
         self.required_feature("core_methods");
@@ -339,7 +616,7 @@ impl CoreHandle {
 
             if success {
                 if !changes.is_empty() {
-                    self.set_component_value(key, component_name, changes).await
+                    self.set_current_component_value(key, component_name, changes, vec![path.to_string()]).await
                 } else {
                     false
                 }
@@ -360,7 +637,7 @@ impl CoreHandle {
 
             if success {
                 if !changes.is_empty() {
-                    self.set_component_value(key, component_name, changes).await
+                    self.set_current_component_value(key, component_name, changes, vec![path.to_string()]).await
                 } else {
                     false
                 }
@@ -381,7 +658,7 @@ impl CoreHandle {
 
             if success {
                 if !changes.is_empty() {
-                    self.set_component_value(key, component_name, changes).await
+                    self.set_current_component_value(key, component_name, changes, vec![value.path.clone()]).await
                 } else {
                     false
                 }
@@ -393,13 +670,57 @@ impl CoreHandle {
         }
     }
 
+    /// Sets multiple values based on their paths for component value, component will only be updated once.
+    /// Returns success state for each of the provided values, nothing will be set if none of the values applied
+    pub async fn set_component_values_by_paths(&self, key: u8, component_name: &str, values: Vec<UIPathValue>) -> Vec<bool> {
+        self.required_feature("core_methods");
+
+        let mut results = vec![false; values.len()];
+
+        if let Some(mut current_values) = self.get_component_values(key, component_name).await {
+            let mut changed_names = vec![];
+            let mut changed_paths = vec![];
+
+            for (index, value) in values.into_iter().enumerate() {
+                let (changes, success) = change_from_path(&value.path, current_values.clone(), &set_value_function(value.clone()), true);
+
+                if success {
+                    current_values = changes;
+                    results[index] = true;
+
+                    if let Some(name) = value.path.split(".").next() {
+                        changed_names.push(name.to_string());
+                    }
+
+                    changed_paths.push(value.path);
+                }
+            }
+
+            if !changed_names.is_empty() {
+                let changes = current_values.into_iter()
+                    .filter(|x| changed_names.contains(&x.name))
+                    .collect();
+
+                if !self.set_current_component_value(key, component_name, changes, changed_paths).await {
+                    results = vec![false; results.len()];
+                }
+            }
+        }
+
+        results
+    }
+
     /// Removes component from a button
     pub async fn remove_component(&self, key: u8, component_name: &str) -> bool {
         self.required_feature("core_methods");
 
+        if self.is_locked().await {
+            return false;
+        }
+
         let module_manager = self.module_manager();
 
-        if let Some(screen) = self.get_current_screen().await {
+        if let Some((screen, is_top)) = self.screen_with_top(None).await {
             let handle = screen.read().await;
             if let Some(button) = handle.buttons.get(&key).cloned() {
                 let previous = make_button_unique(button_to_raw(&button).await);
@@ -419,6 +740,7 @@ impl CoreHandle {
                         self.send_core_event_to_modules(SDCoreEvent::ButtonUpdated {
                             key,
                             panel: screen.clone(),
+                            is_top,
                             new_button: button.clone(),
                             old_button: previous.clone()
                         }, self.module_manager().get_module_list().await.into_iter()).await;
@@ -434,8 +756,189 @@ impl CoreHandle {
         false
     }
 
+    /// Resets component's values on a button back to defaults of the owning module, component stays on the button
+    pub async fn reset_component_values(&self, key: u8, component_name: &str) -> bool {
+        self.required_feature("core_methods");
+
+        if self.is_locked().await {
+            return false;
+        }
+
+        let module_manager = self.module_manager();
+
+        if let Some((screen, is_top)) = self.screen_with_top(None).await {
+            let handle = screen.read().await;
+            if let Some(button) = handle.buttons.get(&key).cloned() {
+                let previous = make_button_unique(button_to_raw(&button).await);
+
+                let mut button_handle = button.write().await;
+                drop(handle);
+
+                if button_handle.component_names().contains(&component_name.to_string()) {
+                    let components = module_manager.read_component_map().await;
+
+                    if let Some((_, module)) = components.get(component_name) {
+                        // Module re-creates component with default values
+                        module.remove_component(self.clone_for(&module), button_handle.deref_mut(), component_name).await;
+                        module.add_component(self.clone_for(&module), button_handle.deref_mut(), component_name).await;
+
+                        drop(button_handle);
+                        drop(components);
+
+                        self.send_core_event_to_modules(SDCoreEvent::ButtonUpdated {
+                            key,
+                            panel: screen.clone(),
+                            is_top,
+                            new_button: button.clone(),
+                            old_button: previous.clone()
+                        }, self.module_manager().get_module_list().await.into_iter()).await;
+
+                        self.core.mark_for_redraw().await;
+
+                        return true;
+                    }
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Moves component to new index in button's component order, out of range indices are clamped. Returns false if there's no button or button doesn't have the component
+    pub async fn reorder_component(&self, key: u8, component_name: &str, new_index: usize) -> bool {
+        self.required_feature("core_methods");
+
+        if self.is_locked().await {
+            return false;
+        }
+
+        if let Some((screen, is_top)) = self.screen_with_top(None).await {
+            let handle = screen.read().await;
+            if let Some(button) = handle.buttons.get(&key).cloned() {
+                let previous = make_button_unique(button_to_raw(&button).await);
+
+                let mut button_handle = button.write().await;
+                drop(handle);
+
+                if button_handle.move_component(component_name, new_index) {
+                    drop(button_handle);
+
+                    self.send_core_event_to_modules(SDCoreEvent::ButtonUpdated {
+                        key,
+                        panel: screen.clone(),
+                        is_top,
+                        new_button: button.clone(),
+                        old_button: previous.clone()
+                    }, self.module_manager().get_module_list().await.into_iter()).await;
+
+                    self.core.mark_for_redraw().await;
+
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Enables or disables component on a button without removing it, disabled components are skipped by rendering and actions.
+    /// Returns false if there's no button or button doesn't have the component
+    pub async fn set_component_enabled(&self, key: u8, component_name: &str, enabled: bool) -> bool {
+        self.required_feature("core_methods");
+
+        if self.is_locked().await {
+            return false;
+        }
+
+        if let Some((screen, is_top)) = self.screen_with_top(None).await {
+            let handle = screen.read().await;
+            if let Some(button) = handle.buttons.get(&key).cloned() {
+                let previous = make_button_unique(button_to_raw(&button).await);
+
+                let mut button_handle = button.write().await;
+                drop(handle);
+
+                if button_handle.set_component_enabled(component_name, enabled) {
+                    drop(button_handle);
+
+                    self.send_core_event_to_modules(SDCoreEvent::ButtonUpdated {
+                        key,
+                        panel: screen.clone(),
+                        is_top,
+                        new_button: button.clone(),
+                        old_button: previous.clone()
+                    }, self.module_manager().get_module_list().await.into_iter()).await;
+
+                    self.core.mark_for_redraw().await;
+
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Removes all components from a button, making owning modules handle each removal, returns false if there's no button
+    pub async fn remove_all_components(&self, key: u8) -> bool {
+        self.required_feature("core_methods");
+
+        if self.is_locked().await {
+            return false;
+        }
+
+        let module_manager = self.module_manager();
+
+        if let Some((screen, is_top)) = self.screen_with_top(None).await {
+            let handle = screen.read().await;
+            if let Some(button) = handle.buttons.get(&key).cloned() {
+                let previous = make_button_unique(button_to_raw(&button).await);
+
+                let mut button_handle = button.write().await;
+                drop(handle);
+
+                let components = module_manager.read_component_map().await;
+
+                for component_name in button_handle.component_names() {
+                    if let Some((_, module)) = components.get(&component_name) {
+                        module.remove_component(self.clone_for(&module), button_handle.deref_mut(), &component_name).await;
+                    } else {
+                        // Nobody owns the component, removing its data directly
+                        button_handle.0.remove(&component_name);
+                    }
+                }
+
+                drop(button_handle);
+                drop(components);
+
+                self.send_core_event_to_modules(SDCoreEvent::ButtonUpdated {
+                    key,
+                    panel: screen.clone(),
+                    is_top,
+                    new_button: button.clone(),
+                    old_button: previous.clone()
+                }, self.module_manager().get_module_list().await.into_iter()).await;
+
+                self.core.mark_for_redraw().await;
+
+                return true;
+            }
+        }
+
+        false
+    }
+
     /// Creates a new button taking provided one as an example and makes all responsible modules handle the paste action
     pub async fn paste_button(&self, key: u8, reference_button: &Button) -> bool {
+        let new_button = self.preview_paste_button(reference_button).await;
+
+        log::debug!("resulting button: {:?}", new_button);
+
+        self.set_button(key, make_button_unique(new_button)).await
+    }
+
+    /// Makes all responsible modules handle the paste action and returns resulting button without setting it anywhere
+    pub async fn preview_paste_button(&self, reference_button: &Button) -> Button {
         let mut new_button = Button::new();
 
         let responsible_modules = self.module_manager().get_modules_for_declared_components(reference_button.component_names().as_slice()).await;
@@ -443,43 +946,119 @@ impl CoreHandle {
             module.paste_component(self.clone_for(&module), reference_button, &mut new_button).await;
         }
 
-        println!("resulting button: {:?}", new_button);
+        new_button.copy_metadata_from(reference_button);
 
-        self.set_button(key, make_button_unique(new_button)).await
+        new_button
     }
 
-    /// Pushes new panel into the stack
+    /// Pushes new panel into the stack, does nothing if device is locked
     pub async fn push_screen(&self, screen: ButtonPanel) {
         self.required_feature("core_methods");
+
+        if self.is_locked().await {
+            return;
+        }
+
         let mut stack = self.current_stack().await;
 
         stack.push(screen.clone());
         drop(stack);
+        self.core.clear_pressed_keys().await;
 
         self.send_core_event_to_modules(SDCoreEvent::PanelPushed {
             new_panel: screen.clone()
         }, self.module_manager().get_module_list().await.into_iter()).await;
+        self.send_stack_changed_event().await;
 
         self.core.mark_for_redraw().await;
     }
 
-    /// Pops panel from stack
+    /// Pops panel from stack, does nothing if device is locked
     pub async fn pop_screen(&self) {
         self.required_feature("core_methods");
+
+        if self.is_locked().await {
+            return;
+        }
+
         let mut stack = self.current_stack().await;
 
         let old_panel = stack.pop();
         drop(stack);
+        self.core.clear_pressed_keys().await;
 
         if let Some(old_panel) = old_panel {
             self.send_core_event_to_modules(SDCoreEvent::PanelPopped {
                 popped_panel: old_panel.clone()
             }, self.module_manager().get_module_list().await.into_iter()).await;
+            self.send_stack_changed_event().await;
         }
 
         self.core.mark_for_redraw().await;
     }
 
+    /// Returns ID of the panel that's currently on top of the stack
+    pub async fn current_panel_id(&self) -> Option<String> {
+        self.required_feature("core_methods");
+
+        if let Some(screen) = self.get_current_screen().await {
+            Some(screen.read().await.id.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Pops panels from the stack until panel with provided ID is on top, returns false if panel isn't in the stack or device is locked.
+    /// Stack is truncated at once, so only single stack changed event is sent
+    pub async fn navigate_to_panel(&self, id: &str) -> bool {
+        self.required_feature("core_methods");
+
+        if self.is_locked().await {
+            return false;
+        }
+
+        let mut stack = self.current_stack().await;
+
+        let mut index = None;
+
+        for (i, panel) in stack.iter().enumerate().rev() {
+            if panel.read().await.id == id {
+                index = Some(i);
+                break;
+            }
+        }
+
+        let index = if let Some(index) = index {
+            index
+        } else {
+            return false;
+        };
+
+        if index + 1 == stack.len() {
+            return true;
+        }
+
+        stack.truncate(index + 1);
+
+        let mut stack_names = vec![];
+
+        for panel in stack.iter() {
+            stack_names.push(panel.read().await.display_name.clone());
+        }
+
+        drop(stack);
+        self.core.clear_pressed_keys().await;
+
+        self.send_core_event_to_modules(SDCoreEvent::StackChanged {
+            depth: stack_names.len(),
+            stack_names
+        }, self.module_manager().get_module_list().await.into_iter()).await;
+
+        self.core.mark_for_redraw().await;
+
+        true
+    }
+
     /// Returns first panel of the stack for saving purposes
     pub async fn get_root_screen(&self) -> ButtonPanel {
         self.required_feature("core_methods");
@@ -508,10 +1087,12 @@ impl CoreHandle {
         stack.clear();
         stack.push(panel.clone());
         drop(stack);
+        self.core.clear_pressed_keys().await;
 
         self.send_core_event_to_modules(SDCoreEvent::StackReset {
             new_panel: panel.clone()
         }, self.module_manager().get_module_list().await.into_iter()).await;
+        self.send_stack_changed_event().await;
 
         self.core.mark_for_redraw().await;
     }
@@ -519,6 +1100,11 @@ impl CoreHandle {
     /// Clears the stack, attempts to deserialize provided panel value into an actual panel and then pushes it into the stack
     pub async fn load_panels_from_value(&self, panels: Value) -> Result<(), JSONError> {
         self.required_feature("core_methods");
+
+        if self.is_locked().await {
+            return Err(DeError::custom("Device is locked"));
+        }
+
         match deserialize_panel(panels) {
             Ok(panel) => {
                 let mut stack = self.current_stack().await;
@@ -526,10 +1112,12 @@ impl CoreHandle {
                 stack.clear();
                 stack.push(panel.clone());
                 drop(stack);
+                self.core.clear_pressed_keys().await;
 
                 self.send_core_event_to_modules(SDCoreEvent::StackReset {
                     new_panel: panel.clone()
                 }, self.module_manager().get_module_list().await.into_iter()).await;
+                self.send_stack_changed_event().await;
 
                 self.core.mark_for_redraw().await;
 
@@ -541,17 +1129,70 @@ impl CoreHandle {
         }
     }
 
-    /// Triggers button down event on all modules
+    /// Clears the stack, attempts to deserialize provided value into a list of panels and then puts all of them into the stack in order,
+    /// last panel ends up on top of the stack
+    pub async fn load_full_stack_from_value(&self, panels: Value) -> Result<(), JSONError> {
+        self.required_feature("core_methods");
+
+        if self.is_locked().await {
+            return Err(DeError::custom("Device is locked"));
+        }
+
+        match serde_json::from_value::<Vec<RawButtonPanel>>(panels) {
+            Ok(panels) => {
+                let panels: Vec<ButtonPanel> = panels.into_iter()
+                    .map(make_panel_unique)
+                    .collect();
+
+                let top_panel = if let Some(panel) = panels.last() {
+                    panel.clone()
+                } else {
+                    return Err(DeError::custom("Failed to load panels: stack cannot be empty"));
+                };
+
+                let mut stack = self.current_stack().await;
+
+                *stack = panels;
+                drop(stack);
+                self.core.clear_pressed_keys().await;
+
+                self.send_core_event_to_modules(SDCoreEvent::StackReset {
+                    new_panel: top_panel
+                }, self.module_manager().get_module_list().await.into_iter()).await;
+                self.send_stack_changed_event().await;
+
+                self.core.mark_for_redraw().await;
+
+                Ok(())
+            }
+            Err(err) => {
+                Err(DeError::custom(format!("Failed to load panels: {}", err)))
+            }
+        }
+    }
+
+    /// Triggers button feedback and button down events on all modules
     pub async fn button_down(&self, key: u8) {
         self.required_feature("core_methods");
+        self.core.pressed_keys.write().await.insert(key);
+
+        self.send_core_event_to_modules(SDCoreEvent::ButtonFeedback {
+            key
+        }, self.module_manager().get_module_list().await.into_iter()).await;
+
         self.send_core_event_to_modules(SDCoreEvent::ButtonDown {
             key
         }, self.module_manager().get_module_list().await.into_iter()).await;
+
+        self.start_key_repeat(key).await;
     }
 
     /// Triggers button up event on all modules
     pub async fn button_up(&self, key: u8) {
         self.required_feature("core_methods");
+        self.core.pressed_keys.write().await.remove(&key);
+        self.stop_key_repeat(key).await;
+
         self.send_core_event_to_modules(SDCoreEvent::ButtonUp {
             key
         }, self.module_manager().get_module_list().await.into_iter()).await;
@@ -559,24 +1200,124 @@ impl CoreHandle {
         self.button_action(key).await;
     }
 
+    /// Returns keys that are currently held down, sorted by key index
+    pub async fn currently_pressed_keys(&self) -> Vec<u8> {
+        self.required_feature("core_methods");
+        let mut keys = self.core.pressed_keys().await.into_iter().collect::<Vec<u8>>();
+        keys.sort();
+
+        keys
+    }
+
+    /// Checks if button action on the key happened within debounce time set in [CoreSettings], records time of the action if it didn't
+    async fn is_action_debounced(&self, key: u8) -> bool {
+        let settings: CoreSettings = self.core.config.get_plugin_settings().await.unwrap_or_default();
+
+        if settings.button_debounce == 0 {
+            return false;
+        }
+
+        let mut last_actions = self.core.last_actions.write().await;
+
+        if let Some(last_action) = last_actions.get(&key) {
+            if last_action.elapsed() < Duration::from_millis(settings.button_debounce as u64) {
+                return true;
+            }
+        }
+
+        last_actions.insert(key, Instant::now());
+        false
+    }
+
+    /// Starts repeating action of the key while it's held down on the same screen, if key repeat is enabled in core settings
+    async fn start_key_repeat(&self, key: u8) {
+        let settings: CoreSettings = self.core.config.get_plugin_settings().await.unwrap_or_default();
+
+        if settings.key_repeat_delay == 0 || settings.key_repeat_rate == 0 {
+            return;
+        }
+
+        let screen = if let Some(screen) = self.get_current_screen().await {
+            screen
+        } else {
+            return;
+        };
+
+        let delay = Duration::from_millis(settings.key_repeat_delay as u64);
+        let interval = Duration::from_secs_f32(1.0 / settings.key_repeat_rate as f32);
+        let core = self.clone();
+
+        let handle = tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+
+            loop {
+                if core.core.is_closed().await || !core.core.pressed_keys().await.contains(&key) {
+                    break;
+                }
+
+                // Repeat stops as soon as the screen changes, so actions don't leak onto other buttons
+                if !core.get_current_screen().await.map_or(false, |current| Arc::ptr_eq(&current, &screen)) {
+                    break;
+                }
+
+                core.trigger_button_action(key).await;
+                tokio::time::sleep(interval).await;
+            }
+        });
+
+        if let Some(previous) = self.core.key_repeats.write().await.insert(key, handle) {
+            previous.abort();
+        }
+    }
+
+    /// Stops repeating action of the key
+    async fn stop_key_repeat(&self, key: u8) {
+        if let Some(handle) = self.core.key_repeats.write().await.remove(&key) {
+            handle.abort();
+        }
+    }
+
     /// Triggers button action event for modules that are related to components of the button
     pub async fn button_action(&self, key: u8) {
         self.required_feature("core_methods");
-        if let Some(screen) = self.get_current_screen().await {
+
+        if self.is_action_debounced(key).await {
+            log::debug!("Ignored repeated button action on key {}", key);
+            return;
+        }
+
+        self.trigger_button_action(key).await;
+    }
+
+    /// Sends button action event without debouncing, used by key repeat
+    async fn trigger_button_action(&self, key: u8) {
+        if let Some((screen, is_top)) = self.screen_with_top(None).await {
             let handle = screen.read().await;
             if let Some(button) = handle.buttons.get(&key).cloned() {
                 drop(handle);
 
+                let component_names = button.read().await.enabled_component_names();
+
+                if self.config().action_log_path().is_some() {
+                    self.config().append_action_log(&ActionLogEntry {
+                        serial_number: self.core.serial_number().await,
+                        key,
+                        component_names: component_names.clone(),
+                        timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |x| x.as_millis() as u64)
+                    }).await;
+                }
+
                 let event = SDCoreEvent::ButtonAction {
                     key,
                     panel: screen.clone(),
+                    is_top,
                     pressed_button: button.clone()
                 };
 
                 self.send_core_event_to_modules(
                     event.clone(),
                     self.module_manager()
-                        .get_modules_for_components(button.read().await.component_names().as_slice()).await
+                        .get_modules_for_components(component_names.as_slice()).await
                         .into_iter()
                 ).await;
                 //send_event_to_socket(&self.core.socket_manager, core_event_to_global(event, &self.core.serial_number).await).await;
@@ -588,126 +1329,162 @@ impl CoreHandle {
 
     /// Renders what current screen would look like into [DynamicImage] map
     pub async fn get_button_images(&self) -> Option<HashMap<u8, DynamicImage>> {
-        let missing = draw_missing_texture(self.core.image_size);
-        let custom = draw_custom_renderer_texture(self.core.image_size);
-        let blank = image_from_solid(self.core.image_size, Rgba([0, 0, 0, 255]));
+        self.get_button_images_for_keys(None).await
+    }
 
+    /// Renders images of specified keys on current screen, or all keys if None was provided, keys without buttons get blank image
+    pub async fn get_button_images_for_keys(&self, keys: Option<&[u8]>) -> Option<HashMap<u8, DynamicImage>> {
         let panel = self.get_current_screen().await?;
         let current_screen = panel.read().await;
-        let buttons = current_screen.buttons.clone();
+        let buttons = current_screen.buttons.clone().into_iter()
+            .filter(|(key, _)| keys.map_or(true, |keys| keys.contains(key)))
+            .collect::<UniqueButtonMap>();
+        drop(current_screen);
 
-        let renderers = self.core.render_manager.read_renderers().await;
+        let mut images = HashMap::new();
 
-        let core_settings: CoreSettings = self.core.config.get_plugin_settings().await.unwrap_or_default();
+        for (key, button) in buttons {
+            images.insert(key, self.render_button_image(key, &button).await);
+        }
 
+        Some(images)
+    }
 
-        let mut images = HashMap::new();
+    /// Renders current screen into single [DynamicImage] with buttons arranged like on the device
+    pub async fn get_deck_composite(&self) -> Option<DynamicImage> {
+        let images = self.get_button_images().await?;
 
-        for (key, button) in buttons {
-            if let Ok(component) = parse_unique_button_to_component::<RendererComponent>(&button).await {
-                let modules = self.module_manager()
-                    .get_modules_for_rendering(
-                        &button.read().await.component_names()
-                    ).await;
-                let modules = modules.into_values()
-                    .filter(|x| !component.plugin_blacklist.contains(&x.name()))
-                    .filter(|x| !core_settings.renderer.plugin_blacklist.contains(&x.name()))
-                    .collect::<Vec<UniqueSDModule>>();
-
-                let image = if component.renderer.is_empty() {
-                    draw_foreground(
-                        &component,
-                        &button,
-                        &modules,
-                        draw_background(
-                            &component,
-                            self,
-                            &missing
-                        ).await,
-                        self
-                    ).await
-                } else {
-                    if let Some(renderer) = renderers.get(&component.renderer) {
-                        if let Some(image) = renderer.representation(key, &button, self).await {
-                            image
-                        } else {
-                            custom.clone()
-                        }
-                    } else {
-                        custom.clone()
-                    }
-                };
+        let (rows, columns) = self.core.key_grid;
+        let (width, height) = self.core.image_size;
 
-                images.insert(key, image);
-            } else {
-                images.insert(key, blank.clone());
+        let mut composite = image_from_solid((width * columns as usize, height * rows as usize), Rgba([0, 0, 0, 255]));
+
+        for (key, image) in images {
+            let row = (key / columns.max(1)) as u32;
+            let column = (key % columns.max(1)) as u32;
+
+            if row < rows as u32 {
+                composite.copy_from(&image, column * width as u32, row * height as u32).ok();
             }
         }
 
-
-        Some(images)
+        Some(composite)
     }
 
     /// Renders what specified button would look like into [DynamicImage]
     pub async fn get_button_image(&self, key: u8) -> Option<DynamicImage> {
-        let missing = draw_missing_texture(self.core.image_size);
-        let custom = draw_custom_renderer_texture(self.core.image_size);
-        let blank = image_from_solid(self.core.image_size, Rgba([0, 0, 0, 255]));
-
         let button = self.get_button(key).await?;
-        let renderers = self.core.render_manager.read_renderers().await;
+        Some(self.render_button_image(key, &button).await)
+    }
+
+    /// Renders only what modules of specified component draw onto the button, over a transparent image.
+    /// Useful for finding out which component is responsible for something drawn on the button
+    pub async fn render_component(&self, button: &UniqueButton, component_name: &str) -> DynamicImage {
+        self.required_feature("core_methods");
 
         let core_settings: CoreSettings = self.core.config.get_plugin_settings().await.unwrap_or_default();
+        let plugin_blacklist = parse_unique_button_to_component::<RendererComponent>(button).await
+            .map(|x| x.plugin_blacklist)
+            .unwrap_or_default();
+
+        let modules = self.module_manager().get_modules_for_rendering(&vec![component_name.to_string()]).await;
+
+        let (width, height) = self.core.image_size;
+        let mut image = DynamicImage::ImageRgba8(RgbaImage::new(width as u32, height as u32));
 
-        if let Ok(component) = parse_unique_button_to_component::<RendererComponent>(&button).await {
-            let modules = self.module_manager().get_modules_for_rendering(&button.read().await.component_names()).await;
-            let modules = modules.into_values()
-                .filter(|x| !component.plugin_blacklist.contains(&x.name()))
-                .filter(|x| !core_settings.renderer.plugin_blacklist.contains(&x.name()))
-                .collect::<Vec<UniqueSDModule>>();
-
-            let image = if component.renderer.is_empty() {
-                draw_foreground(
-                    &component,
-                    &button,
-                    &modules,
-                    draw_background(
-                        &component,
-                        self,
-                        &missing
-                    ).await,
-                    self
-                ).await
+        for module in modules {
+            if plugin_blacklist.contains(&module.name()) || core_settings.renderer.plugin_blacklist.contains(&module.name()) {
+                continue;
+            }
+
+            module.render(self.clone_for(&module), button, &mut image).await;
+        }
+
+        image
+    }
+
+    /// Renders what provided button would look like on the device into [DynamicImage] without adding it anywhere, key is only used by custom renderers
+    pub async fn render_button_preview(&self, key: u8, button: Button) -> DynamicImage {
+        self.render_button_image(key, &make_button_unique(button)).await
+    }
+
+    /// Renders button into [DynamicImage] the same way device thread would
+    async fn render_button_image(&self, key: u8, button: &UniqueButton) -> DynamicImage {
+        if let Ok(component) = parse_unique_button_to_component::<RendererComponent>(button).await {
+            if component.renderer.is_empty() {
+                self.render_with_default_renderer(button, &component).await
             } else {
+                let renderers = self.core.render_manager.read_renderers().await;
+
                 if let Some(renderer) = renderers.get(&component.renderer) {
-                    if let Some(image) = renderer.representation(key, &button, self).await {
-                        image
-                    } else {
-                        custom.clone()
+                    if let Some(image) = renderer.representation(key, button, self).await {
+                        return image;
                     }
-                } else {
-                    custom.clone()
                 }
-            };
 
-            Some(image)
+                draw_custom_renderer_texture(self.core.image_size)
+            }
         } else {
-            Some(blank)
+            image_from_solid(self.core.image_size, Rgba([0, 0, 0, 255]))
         }
     }
 
-    /// Replaces current screen with specified one
+    /// Renders button with default renderer, background and text of the component are drawn and then modules of button's components render on top
+    async fn render_with_default_renderer(&self, button: &UniqueButton, component: &RendererComponent) -> DynamicImage {
+        let core_settings: CoreSettings = self.core.config.get_plugin_settings().await.unwrap_or_default();
+
+        let modules = self.module_manager().get_modules_for_rendering(&button.read().await.enabled_component_names()).await;
+        let modules = modules.into_iter()
+            .filter(|x| !component.plugin_blacklist.contains(&x.name()))
+            .filter(|x| !core_settings.renderer.plugin_blacklist.contains(&x.name()))
+            .collect::<Vec<UniqueSDModule>>();
+
+        let missing = draw_missing_texture(self.core.image_size);
+
+        draw_foreground(
+            component,
+            button,
+            &modules,
+            draw_background(
+                component,
+                self,
+                &missing
+            ).await,
+            self
+        ).await
+    }
+
+    /// Renders specified button with default renderer into [DynamicImage], ignoring custom renderer set on the button.
+    /// Meant for custom renderers that want to draw on top of what default renderer would've drawn
+    pub async fn render_default_representation(&self, key: u8) -> Option<DynamicImage> {
+        self.required_feature("core_methods");
+
+        let button = self.get_button(key).await?;
+        let component = parse_unique_button_to_component::<RendererComponent>(&button).await.ok()?;
+
+        Some(self.render_with_default_renderer(&button, &component).await)
+    }
+
+    /// Replaces current screen with specified one, does nothing if device is locked
     pub async fn replace_screen(&self, screen: ButtonPanel) {
         self.required_feature("core_methods");
+
+        if self.is_locked().await {
+            return;
+        }
+
         let mut stack = self.current_stack().await;
 
         let old_panel = stack.pop();
         stack.push(screen.clone());
+        drop(stack);
+        self.core.clear_pressed_keys().await;
 
         self.send_core_event_to_modules(SDCoreEvent::PanelReplaced {
             old_panel,
             new_panel: screen
         }, self.module_manager().get_module_list().await.into_iter()).await;
+        self.send_stack_changed_event().await;
 
         self.core.mark_for_redraw().await;
     }
@@ -721,6 +1498,63 @@ impl CoreHandle {
         handle.brightness = brightness;
     }
 
+    /// Shows image on the button for specified duration, then restores its usual render. Doesn't change button config or image caches, setting another temporary image on the same key replaces the previous one
+    pub async fn set_temporary_image(&self, key: u8, image: DynamicImage, duration: Duration) {
+        self.required_feature("core_methods");
+        self.core.send_commands(vec![DeviceThreadCommunication::SetTemporaryImage(key, image.resize_to_fill(self.core.image_size.0 as u32, self.core.image_size.1 as u32, FilterType::Triangle), duration)]).await;
+    }
+
+    /// Checks if device is locked, locked devices reject any changes to buttons, components, layout and the panel stack, but button actions still work
+    pub async fn is_locked(&self) -> bool {
+        self.core.device_config.read().await.locked
+    }
+
+    /// Locks or unlocks the device, lock state is saved with device config
+    pub async fn set_locked(&self, locked: bool) {
+        self.required_feature("core_methods");
+        let mut handle = self.core.device_config.write().await;
+
+        handle.locked = locked;
+        handle.dirty_state = true;
+    }
+
+    /// Gets user assigned name of the device, empty if device doesn't have one
+    pub async fn get_nickname(&self) -> String {
+        self.core.device_config.read().await.nickname.clone()
+    }
+
+    /// Sets user assigned name of the device, name is saved with device config
+    pub async fn set_nickname(&self, nickname: &str) {
+        self.required_feature("core_methods");
+        let mut handle = self.core.device_config.write().await;
+
+        handle.nickname = nickname.to_string();
+        handle.dirty_state = true;
+    }
+
+    /// Checks if render cache is disabled for the device
+    pub async fn is_render_cache_disabled(&self) -> bool {
+        self.core.device_config.read().await.disable_render_cache
+    }
+
+    /// Disables or enables render cache for the device, setting is saved with device config. Meant for checking if caching causes stale images
+    pub async fn set_render_cache_disabled(&self, disabled: bool) {
+        self.required_feature("core_methods");
+        let mut handle = self.core.device_config.write().await;
+
+        handle.disable_render_cache = disabled;
+        handle.dirty_state = true;
+        drop(handle);
+
+        self.core.mark_for_redraw().await;
+    }
+
+    /// Gets brightness that was last set to the streamdeck
+    pub async fn get_brightness(&self) -> u8 {
+        self.required_feature("core_methods");
+        self.core.device_config.read().await.brightness
+    }
+
     /// Commits all changes to layout to device config so it can be later saved
     pub async fn commit_changes(&self) {
         self.required_feature("core_methods");
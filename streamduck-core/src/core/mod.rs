@@ -1,11 +1,14 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
+use std::time::Instant;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use image::DynamicImage;
 use streamdeck::{Kind, StreamDeck};
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{Mutex, oneshot, RwLock};
 use tokio::sync::mpsc::unbounded_channel;
+use tokio::task::{JoinHandle, spawn_blocking};
 
 pub use methods::check_feature_list_for_feature;
 pub use methods::CoreHandle;
@@ -18,7 +21,10 @@ use crate::modules::events::SDGlobalEvent;
 use crate::modules::ModuleManager;
 use crate::socket::SocketManager;
 use crate::thread::{DeviceThreadCommunication, DeviceThreadHandle, spawn_device_thread};
+use crate::thread::image_loader::ImageLoader;
 use crate::thread::rendering::custom::RenderingManager;
+use crate::thread::rendering::{RenderCacheDump, RenderStats};
+use crate::util::get_key_grid;
 
 /// Definitions of button structs
 pub mod button;
@@ -43,8 +49,11 @@ pub type ButtonPanel = Arc<RwLock<Panel<UniqueButtonMap>>>;
 pub type RawButtonPanel = Panel<ButtonMap>;
 
 /// Panel definition
-#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Panel<T> {
+    /// Stable identifier of the panel, generated when panel is created
+    #[serde(default = "generate_panel_id")]
+    pub id: String,
     /// Display name that will be shown in UI
     #[serde(default)]
     pub display_name: String,
@@ -56,6 +65,29 @@ pub struct Panel<T> {
     pub buttons: T
 }
 
+impl<T: Default> Default for Panel<T> {
+    fn default() -> Self {
+        Self {
+            id: generate_panel_id(),
+            display_name: Default::default(),
+            data: Default::default(),
+            buttons: Default::default()
+        }
+    }
+}
+
+/// Generates new random panel ID in UUID v4 format
+pub fn generate_panel_id() -> String {
+    let mut bytes = rand::random::<[u8; 16]>();
+
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex = bytes.iter().map(|x| format!("{:02x}", x)).collect::<String>();
+
+    format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32])
+}
+
 impl Into<ButtonMap> for Panel<ButtonMap> {
     fn into(self) -> ButtonMap {
         self.buttons
@@ -116,12 +148,36 @@ pub struct SDCore {
     /// Key count of the streamdeck device
     pub key_count: u8,
 
+    /// Key grid dimensions of the streamdeck device as (rows, columns)
+    pub key_grid: (u8, u8),
+
     /// Pool rate of how often should the core read events from the device
     pub frame_rate: u32,
 
     /// Decides if core is dead
     pub should_close: RwLock<bool>,
 
+    /// Latest render statistics of the device thread
+    pub render_stats: RwLock<RenderStats>,
+
+    /// Keys that are currently held down
+    pub pressed_keys: RwLock<HashSet<u8>>,
+
+    /// Last images that device thread rendered for each key, only kept if renderer settings allow it
+    pub last_rendered: RwLock<HashMap<u8, Arc<DynamicImage>>>,
+
+    /// Times of last button actions per key, used for debouncing
+    pub last_actions: RwLock<HashMap<u8, Instant>>,
+
+    /// Tasks that repeat button actions of held keys
+    pub key_repeats: RwLock<HashMap<u8, JoinHandle<()>>>,
+
+    /// Recent events of the device, oldest first
+    pub recent_events: RwLock<VecDeque<SDGlobalEvent>>,
+
+    /// Worker pool that decodes images in background
+    pub image_loader: ImageLoader,
+
     handles: Mutex<Option<ThreadHandles>>
 }
 
@@ -142,8 +198,16 @@ impl SDCore {
             image_collection,
             kind: Kind::Original,
             key_count: 0,
+            key_grid: (0, 0),
             frame_rate: 0,
-            should_close: RwLock::new(true)
+            should_close: RwLock::new(true),
+            render_stats: Default::default(),
+            pressed_keys: Default::default(),
+            last_rendered: Default::default(),
+            last_actions: Default::default(),
+            key_repeats: Default::default(),
+            recent_events: Default::default(),
+            image_loader: ImageLoader::new(0)
         })
     }
 
@@ -158,6 +222,8 @@ impl SDCore {
             serial_number: serial_number.clone()
         }).await;
 
+        let image_loader = ImageLoader::new(config.image_workers());
+
         let core = Arc::new(SDCore {
             serial_number,
             module_manager,
@@ -171,8 +237,16 @@ impl SDCore {
             image_collection,
             kind: connection.kind(),
             key_count: connection.kind().keys(),
+            key_grid: get_key_grid(&connection.kind()),
             frame_rate,
-            should_close: RwLock::new(false)
+            should_close: RwLock::new(false),
+            render_stats: Default::default(),
+            pressed_keys: Default::default(),
+            last_rendered: Default::default(),
+            last_actions: Default::default(),
+            key_repeats: Default::default(),
+            recent_events: Default::default(),
+            image_loader
         });
 
         let renderer = spawn_device_thread(core.clone(), connection, key_tx);
@@ -212,6 +286,13 @@ impl SDCore {
         handles.as_ref().unwrap().renderer.send(vec![DeviceThreadCommunication::RefreshScreen]);
     }
 
+    /// Tells device thread to redraw all buttons once while bypassing image cache, useful for diagnosing stale images
+    pub async fn redraw_uncached(&self) {
+        let handles = self.handles.lock().await;
+
+        handles.as_ref().unwrap().renderer.send(vec![DeviceThreadCommunication::RedrawUncached]);
+    }
+
     /// Sends commands to streamdeck thread
     pub async fn send_commands(&self, commands: Vec<DeviceThreadCommunication>) {
         let handles = self.handles.lock().await;
@@ -229,6 +310,59 @@ impl SDCore {
         *self.should_close.read().await
     }
 
+    /// Returns latest render statistics of the device thread
+    pub async fn render_stats(&self) -> RenderStats {
+        self.render_stats.read().await.clone()
+    }
+
+    /// Returns image that device thread last rendered for the key, only available if renderer keeps last rendered images
+    pub async fn last_rendered_image(&self, key: u8) -> Option<Arc<DynamicImage>> {
+        self.last_rendered.read().await.get(&key).cloned()
+    }
+
+    /// Asks device thread for contents of its render cache, returns None if device thread isn't running
+    pub async fn dump_render_cache(&self) -> Option<RenderCacheDump> {
+        let (tx, rx) = oneshot::channel();
+
+        if let Some(handles) = self.handles.lock().await.as_ref() {
+            handles.renderer.send(vec![DeviceThreadCommunication::DumpRenderCache(tx)]);
+        } else {
+            return None;
+        }
+
+        rx.await.ok()
+    }
+
+    /// Returns keys that are currently held down
+    pub async fn pressed_keys(&self) -> HashSet<u8> {
+        self.pressed_keys.read().await.clone()
+    }
+
+    /// Forgets all keys that are held down, so they don't get stuck after screen changes or disconnects
+    pub async fn clear_pressed_keys(&self) {
+        self.pressed_keys.write().await.clear();
+    }
+
+    /// Adds event to the event log, removing oldest events if log is over the configured size
+    pub async fn log_event(&self, event: SDGlobalEvent) {
+        let size = self.config.event_log_size();
+        let mut events = self.recent_events.write().await;
+
+        events.push_back(event);
+
+        while events.len() > size {
+            events.pop_front();
+        }
+    }
+
+    /// Returns up to limit of most recent events, oldest first, 0 limit returns all of them
+    pub async fn recent_events(&self, limit: usize) -> Vec<SDGlobalEvent> {
+        let events = self.recent_events.read().await;
+        let skip = if limit == 0 { 0 } else { events.len().saturating_sub(limit) };
+
+        events.iter().skip(skip).cloned().collect()
+    }
+
     /// Kills the core and all the related threads
     pub async fn close(&self) {
         self.module_manager.send_global_event_to_modules(SDGlobalEvent::DeviceDisconnected {
@@ -237,6 +371,24 @@ impl SDCore {
 
         let mut lock = self.should_close.write().await;
         *lock = true;
+        drop(lock);
+
+        self.clear_pressed_keys().await;
+
+        if let Some(handles) = self.handles.lock().await.as_ref() {
+            handles.renderer.shutdown();
+        }
+    }
+
+    /// Waits for device thread to exit, returns false if thread panicked or was already joined
+    pub async fn join_device_thread(&self) -> bool {
+        let join_handle = self.handles.lock().await.as_ref().and_then(|handles| handles.renderer.take_join_handle());
+
+        if let Some(join_handle) = join_handle {
+            spawn_blocking(move || join_handle.join().is_ok()).await.unwrap_or(false)
+        } else {
+            false
+        }
     }
 }
 
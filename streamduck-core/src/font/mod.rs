@@ -1,4 +1,5 @@
 use std::fs;
+use std::path::Path;
 use std::sync::Arc;
 use rusttype::Font;
 
@@ -21,9 +22,14 @@ pub fn load_default_font() {
 
 /// Loads fonts into global collection from fonts folder
 pub fn load_fonts_from_resources() {
+    load_fonts_from_folder("fonts")
+}
+
+/// Loads fonts into global collection from specified folder
+pub fn load_fonts_from_folder<P: AsRef<Path>>(path: P) {
     let mut counter = 0;
 
-    match fs::read_dir("fonts") {
+    match fs::read_dir(path) {
         Ok(directory) => {
             for entry in directory {
                 if let Ok(entry) = entry {
@@ -68,6 +74,13 @@ pub fn get_font_from_collection(name: &str) -> Option<Arc<Font<'static>>> {
     None
 }
 
+/// Gets font reference from global collection, falls back to provided default font and then to built-in font if font wasn't found
+pub fn get_font_or_default(name: &str, default: &str) -> Option<Arc<Font<'static>>> {
+    get_font_from_collection(name)
+        .or_else(|| get_font_from_collection(default))
+        .or_else(|| get_font_from_collection("default"))
+}
+
 /// Returns names of fonts in global collection
 pub fn get_font_names() -> Vec<String> {
     unsafe { &LOADED_FONTS }.iter().map(|(n, ..)| n.to_string()).collect()
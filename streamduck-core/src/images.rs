@@ -1,9 +1,10 @@
 use std::hash::{Hash, Hasher};
 use std::io::Cursor;
 use std::time::Duration;
-use image::{AnimationDecoder, DynamicImage, Frame, ImageFormat};
+use image::{AnimationDecoder, DynamicImage, Frame, ImageFormat, ImageOutputFormat};
 use image::codecs::gif::GifDecoder;
 use image::codecs::png::PngDecoder;
+use image::codecs::webp::WebPDecoder;
 use image::io::Reader;
 use itertools::Itertools;
 use rayon::iter::*;
@@ -44,6 +45,10 @@ impl SDImage {
         let decoder = Reader::new(Cursor::new(bytes)).with_guessed_format()?;
 
         if let Some(format) = decoder.format() {
+            if !is_format_supported(format) {
+                return Err(ImageDeserializationError::UnsupportedFormat(format));
+            }
+
             match format {
                 // Only png and gif that need special handling
                 ImageFormat::Png => {
@@ -68,6 +73,13 @@ impl SDImage {
                     Ok(SDImage::AnimatedImage(convert_frames(frames, size).await))
                 }
 
+                ImageFormat::WebP => {
+                    // Decoded as a still image, animated WebP isn't supported by the image crate yet
+                    let decoder = WebPDecoder::new(decoder.into_inner())?;
+
+                    Ok(SDImage::SingleImage(resize_for_streamdeck(size, DynamicImage::from_decoder(decoder)?)))
+                }
+
                 _ => {
                     Ok(SDImage::SingleImage(resize_for_streamdeck(size, decoder.decode()?)))
                 }
@@ -127,6 +139,40 @@ impl SDSerializedImage {
     }
 }
 
+/// Metadata of an image that was added to device config
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ImageMetadata {
+    /// Width of the image in pixels
+    pub width: u32,
+    /// Height of the image in pixels
+    pub height: u32,
+    /// Format of the image as detected from image data
+    pub format: String,
+    /// Size of image data in bytes
+    pub byte_size: usize,
+}
+
+impl ImageMetadata {
+    /// Reads metadata from image data, only header of the image is decoded
+    pub fn from_bytes(bytes: &[u8]) -> Option<ImageMetadata> {
+        let reader = Reader::new(Cursor::new(bytes)).with_guessed_format().ok()?;
+        let format = reader.format()?;
+        let (width, height) = reader.into_dimensions().ok()?;
+
+        Some(ImageMetadata {
+            width,
+            height,
+            format: format!("{:?}", format),
+            byte_size: bytes.len()
+        })
+    }
+
+    /// Reads metadata from base64 encoded image data
+    pub fn from_base64(data: &str) -> Option<ImageMetadata> {
+        ImageMetadata::from_bytes(&base64::decode(data).ok()?)
+    }
+}
+
 /// Frame of animated image
 #[derive(Clone, Debug)]
 pub struct AnimationFrame {
@@ -282,6 +328,7 @@ impl TryFrom<&SDSerializedImage> for SDImage {
 }
 
 /// Error for deserializing images
+#[derive(Debug)]
 pub enum ImageDeserializationError {
     /// Failed to decode base64
     Base64Error(base64::DecodeError),
@@ -293,12 +340,22 @@ pub enum ImageDeserializationError {
     InvalidByteBuffer,
     /// Image format is not supported
     UnrecognizedFormat,
+    /// Image format was recognized, but decoding it wasn't enabled at build time
+    UnsupportedFormat(ImageFormat),
     /// Failed to spawn a blocking task
     JoinError(tokio::task::JoinError),
     /// No frame
     NoFrame
 }
 
+/// Checks if image format can be decoded with features this build was compiled with
+pub fn is_format_supported(format: ImageFormat) -> bool {
+    match format {
+        ImageFormat::Avif => cfg!(feature = "avif"),
+        _ => format.can_read()
+    }
+}
+
 impl From<base64::DecodeError> for ImageDeserializationError {
     fn from(err: base64::DecodeError) -> Self {
         ImageDeserializationError::Base64Error(err)
@@ -323,14 +380,49 @@ impl From<tokio::task::JoinError> for ImageDeserializationError {
     }
 }
 
+/// Quality of JPEG images sent to devices that expect JPEG images, unless configured otherwise
+pub const DEFAULT_JPEG_QUALITY: u8 = 75;
+
 /// Converts image to device image
 pub fn convert_image(kind: &Kind, image: DynamicImage) -> DeviceImage {
+    convert_image_with_quality(kind, image, DEFAULT_JPEG_QUALITY)
+}
+
+/// Converts image to device image, JPEG quality is only used if device expects JPEG images
+pub fn convert_image_with_quality(kind: &Kind, image: DynamicImage, jpeg_quality: u8) -> DeviceImage {
+    DeviceImage::from(encode_image_with_quality(kind, image, jpeg_quality))
+}
+
+/// Encodes image into bytes of the format that device expects
+pub fn encode_image_with_quality(kind: &Kind, image: DynamicImage, jpeg_quality: u8) -> Vec<u8> {
     let mut buffer = vec![];
 
-    image.rotate180().to_rgba8().write_to(&mut Cursor::new(&mut buffer), match kind.image_mode() {
-        ImageMode::Bmp => ImageFormat::Bmp,
-        ImageMode::Jpeg => ImageFormat::Jpeg,
-    }).ok();
+    image.rotate180().to_rgba8().write_to(&mut Cursor::new(&mut buffer), device_image_format(kind, jpeg_quality)).ok();
+
+    buffer
+}
 
-    DeviceImage::from(buffer)
+/// Returns format in which images should be encoded for the device
+pub fn device_image_format(kind: &Kind, jpeg_quality: u8) -> ImageOutputFormat {
+    match kind.image_mode() {
+        ImageMode::Bmp => ImageOutputFormat::Bmp,
+        ImageMode::Jpeg => ImageOutputFormat::Jpeg(jpeg_quality.clamp(1, 100)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1x1 lossy WebP image
+    const WEBP_FIXTURE: &str = "UklGRiIAAABXRUJQVlA4IBYAAAAwAQCdASoBAAEADsD+JaQAA3AAAAAA";
+
+    #[tokio::test]
+    async fn images_load_webp() {
+        let image = SDImage::from_base64(WEBP_FIXTURE, (72, 72)).await.expect("failed to load webp");
+
+        assert!(!image.is_animated());
+        assert_eq!(image.get_image().width(), 72);
+        assert_eq!(image.get_image().height(), 72);
+    }
 }
@@ -13,10 +13,46 @@ pub struct ComponentDefinition {
     /// Description of the component
     pub description: String,
 
+    /// Names of components that are required for this component to work, they will be added to the button before this component
+    #[serde(default)]
+    pub requires: Vec<String>,
+
     /// Default looks for a button, in case user doesn't want to setup one on their own
     pub default_looks: RendererComponent
 }
 
+/// Resolves components that have to be added for specified component to work, dependencies come first and the component itself is last.
+/// Components that already exist or aren't defined are skipped, circular dependencies are broken with a warning
+pub fn resolve_component_dependencies<T>(components: &HashMap<String, (ComponentDefinition, T)>, component_name: &str, existing: &[String]) -> Vec<String> {
+    fn visit<T>(components: &HashMap<String, (ComponentDefinition, T)>, name: &str, existing: &[String], visiting: &mut Vec<String>, order: &mut Vec<String>) {
+        if existing.iter().any(|x| x == name) || order.iter().any(|x| x == name) {
+            return;
+        }
+
+        if visiting.iter().any(|x| x == name) {
+            log::warn!("Circular component dependency detected: {} -> {}", visiting.join(" -> "), name);
+            return;
+        }
+
+        if let Some((definition, _)) = components.get(name) {
+            visiting.push(name.to_string());
+
+            for dependency in &definition.requires {
+                visit(components, dependency, existing, visiting, order);
+            }
+
+            visiting.pop();
+            order.push(name.to_string());
+        } else if let Some(parent) = visiting.last() {
+            log::warn!("Component '{}' requires '{}', but it isn't defined by any module", parent, name);
+        }
+    }
+
+    let mut order = vec![];
+    visit(components, component_name, existing, &mut vec![], &mut order);
+    order
+}
+
 /// UI Field, will be represented in a list similar to Unity's inspector
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct UIField {
@@ -26,7 +62,8 @@ pub struct UIField {
     /// Display name of the field
     pub display_name: String,
 
-    /// Description of the field
+    /// Description of the field, empty if module didn't provide any
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub description: String,
 
     /// Type of the field
@@ -45,7 +82,8 @@ pub struct UIValue {
     /// Display name of the value
     pub display_name: String,
 
-    /// Description of the value
+    /// Description of the value, shown as help text by editors, empty if module didn't provide any
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub description: String,
 
     /// Type of the value
@@ -67,7 +105,8 @@ pub struct UIPathValue {
     /// Display name of the value
     pub display_name: String,
 
-    /// Description of the value
+    /// Description of the value, copied over from [UIValue]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub description: String,
 
     /// Type of the value
@@ -1,7 +1,9 @@
 //! Core module
 
 use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Arc;
+use strum::VariantNames;
 use serde::{Deserialize, Serialize};
 use crate::config::PluginConfig;
 use crate::core::button::{Button, Component};
@@ -12,8 +14,9 @@ use crate::modules::{PluginMetadata, SDModule};
 use crate::modules::events::{core_event_to_global, SDCoreEvent, SDGlobalEvent};
 use crate::socket::send_event_to_socket;
 use crate::SocketManager;
-use crate::thread::rendering::{RendererComponent, RendererSettings};
+use crate::thread::rendering::{MAX_TEXT_SUPERSAMPLING, RendererComponent, RendererSettings};
 use crate::thread::rendering::component_values::{get_renderer_component_values, set_renderer_component_values};
+use crate::thread::util::ColorMode;
 use crate::util::straight_copy;
 use crate::versions::{CORE, MODULE_MANAGER};
 
@@ -34,6 +37,7 @@ impl SDModule for CoreModule {
         map.insert("renderer".to_string(), ComponentDefinition {
             display_name: "Renderer".to_string(),
             description: "The only thing that makes a button render an image on streamdeck".to_string(),
+            requires: vec![],
             default_looks: Default::default()
         });
 
@@ -124,11 +128,101 @@ impl SDModule for CoreModule {
                         }
                     );
 
+                    fields.push(
+                        UIValue {
+                            name: "color_mode".to_string(),
+                            display_name: "Color Mode".to_string(),
+                            description: "Color mode of images sent to devices, for hardware that only supports grayscale or 1-bit images".to_string(),
+                            ty: UIFieldType::Choice(ColorMode::VARIANTS.iter().map(|x| x.to_string()).collect()),
+                            value: UIFieldValue::Choice(settings.renderer.color_mode.to_string())
+                        }
+                    );
+
+                    fields.push(
+                        UIValue {
+                            name: "default_font".to_string(),
+                            display_name: "Default Font".to_string(),
+                            description: "Font used for text that doesn't specify a font, or if specified font wasn't found".to_string(),
+                            ty: UIFieldType::Font,
+                            value: UIFieldValue::Font(settings.renderer.default_font.clone())
+                        }
+                    );
+
+                    fields.push(
+                        UIValue {
+                            name: "default_scale".to_string(),
+                            display_name: "Default Text Scale".to_string(),
+                            description: "Scale used for text that has scale of zero".to_string(),
+                            ty: UIFieldType::InputFieldFloat2,
+                            value: UIFieldValue::InputFieldFloat2(settings.renderer.default_scale.0, settings.renderer.default_scale.1)
+                        }
+                    );
+
+                    fields.push(
+                        UIValue {
+                            name: "jpeg_quality".to_string(),
+                            display_name: "JPEG Quality".to_string(),
+                            description: "Quality from 1 to 100 of images sent to devices that expect JPEG images".to_string(),
+                            ty: UIFieldType::InputFieldUnsignedInteger,
+                            value: UIFieldValue::InputFieldUnsignedInteger(settings.renderer.jpeg_quality as u32)
+                        }
+                    );
+
+                    fields.push(
+                        UIValue {
+                            name: "text_supersampling".to_string(),
+                            display_name: "Text Supersampling".to_string(),
+                            description: "Draws text up to 4 times larger and scales it down for smoother edges, 1 disables supersampling".to_string(),
+                            ty: UIFieldType::InputFieldUnsignedInteger,
+                            value: UIFieldValue::InputFieldUnsignedInteger(settings.renderer.text_supersampling)
+                        }
+                    );
+
+                    fields.push(
+                        UIValue {
+                            name: "retain_rendered_images".to_string(),
+                            display_name: "Keep Last Rendered Images".to_string(),
+                            description: "Keeps last rendered image of each button in memory, so it can be retrieved without rendering again".to_string(),
+                            ty: UIFieldType::Checkbox { disabled: false },
+                            value: UIFieldValue::Checkbox(settings.renderer.retain_rendered_images)
+                        }
+                    );
+
                     fields
                 })
             }
         );
 
+        fields.push(
+            UIValue {
+                name: "button_debounce".to_string(),
+                display_name: "Button Debounce".to_string(),
+                description: "Time in milliseconds during which repeated presses of the same button are ignored, 0 to disable".to_string(),
+                ty: UIFieldType::InputFieldUnsignedInteger,
+                value: UIFieldValue::InputFieldUnsignedInteger(settings.button_debounce)
+            }
+        );
+
+        fields.push(
+            UIValue {
+                name: "key_repeat_delay".to_string(),
+                display_name: "Key Repeat Delay".to_string(),
+                description: "Time in milliseconds a button has to be held before its action starts repeating, 0 to disable".to_string(),
+                ty: UIFieldType::InputFieldUnsignedInteger,
+                value: UIFieldValue::InputFieldUnsignedInteger(settings.key_repeat_delay)
+            }
+        );
+
+        fields.push(
+            UIValue {
+                name: "key_repeat_rate".to_string(),
+                display_name: "Key Repeat Rate".to_string(),
+                description: "How many times per second action of a held button is repeated".to_string(),
+                ty: UIFieldType::InputFieldUnsignedInteger,
+                value: UIFieldValue::InputFieldUnsignedInteger(settings.key_repeat_rate)
+            }
+        );
+
         fields
     }
 
@@ -156,6 +250,62 @@ impl SDModule for CoreModule {
                         }
                     }
                 }
+
+                if let Some(value) = change_map.get("color_mode") {
+                    if let UIFieldValue::Choice(mode) = &value.value {
+                        if let Ok(mode) = ColorMode::from_str(mode) {
+                            settings.renderer.color_mode = mode;
+                        }
+                    }
+                }
+
+                if let Some(value) = change_map.get("default_font") {
+                    if let UIFieldValue::Font(font) = &value.value {
+                        settings.renderer.default_font = font.clone();
+                    }
+                }
+
+                if let Some(value) = change_map.get("default_scale") {
+                    if let Ok(scale) = value.value.try_into_f32_f32() {
+                        settings.renderer.default_scale = scale;
+                    }
+                }
+
+                if let Some(value) = change_map.get("jpeg_quality") {
+                    if let UIFieldValue::InputFieldUnsignedInteger(quality) = value.value {
+                        settings.renderer.jpeg_quality = quality.clamp(1, 100) as u8;
+                    }
+                }
+
+                if let Some(value) = change_map.get("text_supersampling") {
+                    if let UIFieldValue::InputFieldUnsignedInteger(supersampling) = value.value {
+                        settings.renderer.text_supersampling = supersampling.clamp(1, MAX_TEXT_SUPERSAMPLING);
+                    }
+                }
+
+                if let Some(value) = change_map.get("retain_rendered_images") {
+                    if let UIFieldValue::Checkbox(retain) = value.value {
+                        settings.renderer.retain_rendered_images = retain;
+                    }
+                }
+            }
+        }
+
+        if let Some(value) = change_map.get("button_debounce") {
+            if let UIFieldValue::InputFieldUnsignedInteger(debounce) = value.value {
+                settings.button_debounce = debounce;
+            }
+        }
+
+        if let Some(value) = change_map.get("key_repeat_delay") {
+            if let UIFieldValue::InputFieldUnsignedInteger(delay) = value.value {
+                settings.key_repeat_delay = delay;
+            }
+        }
+
+        if let Some(value) = change_map.get("key_repeat_rate") {
+            if let UIFieldValue::InputFieldUnsignedInteger(rate) = value.value {
+                settings.key_repeat_rate = rate;
             }
         }
 
@@ -191,10 +341,34 @@ impl SDModule for CoreModule {
 }
 
 /// Settings related to various things around the core
-#[derive(Serialize, Deserialize, Default)]
+#[derive(Serialize, Deserialize)]
 pub struct CoreSettings {
     /// Renderer settings
-    pub renderer: RendererSettings
+    pub renderer: RendererSettings,
+    /// Time in milliseconds during which repeated button actions on the same key are ignored, 0 disables debouncing
+    #[serde(default)]
+    pub button_debounce: u32,
+    /// Time in milliseconds a key has to be held before its action starts repeating, 0 disables key repeat
+    #[serde(default)]
+    pub key_repeat_delay: u32,
+    /// How many times per second action of a held key is repeated
+    #[serde(default = "default_key_repeat_rate")]
+    pub key_repeat_rate: u32
+}
+
+fn default_key_repeat_rate() -> u32 {
+    10
+}
+
+impl Default for CoreSettings {
+    fn default() -> Self {
+        Self {
+            renderer: Default::default(),
+            button_debounce: 0,
+            key_repeat_delay: 0,
+            key_repeat_rate: default_key_repeat_rate()
+        }
+    }
 }
 
 impl PluginConfig for CoreSettings {
@@ -12,6 +12,8 @@ pub enum SDCoreEvent {
         key: u8,
         /// Current panel
         panel: ButtonPanel,
+        /// If panel is currently on top of the stack
+        is_top: bool,
         /// Button that was added
         added_button: UniqueButton
     },
@@ -21,6 +23,8 @@ pub enum SDCoreEvent {
         key: u8,
         /// Current panel
         panel: ButtonPanel,
+        /// If panel is currently on top of the stack
+        is_top: bool,
         /// New version of the button
         new_button: UniqueButton,
         /// Old version of the button
@@ -32,20 +36,43 @@ pub enum SDCoreEvent {
         key: u8,
         /// Current panel
         panel: ButtonPanel,
+        /// If panel is currently on top of the stack
+        is_top: bool,
         /// Button that was deleted
         deleted_button: UniqueButton
     },
 
+    /// Called when a value of a component was changed, sent together with [SDCoreEvent::ButtonUpdated] for each changed path
+    ComponentValueChanged {
+        /// Key index
+        key: u8,
+        /// Panel of the button
+        panel: ButtonPanel,
+        /// If panel is currently on top of the stack
+        is_top: bool,
+        /// Name of the component
+        component: String,
+        /// Path of the value that was changed
+        path: String
+    },
+
     /// Called when a valid button was pressed on
     ButtonAction {
         /// Key index
         key: u8,
         /// Current panel
         panel: ButtonPanel,
+        /// If panel is currently on top of the stack
+        is_top: bool,
         /// Button that was pressed
         pressed_button: UniqueButton
     },
 
+    /// Called when a button is pressed down, before [SDCoreEvent::ButtonDown], meant for modules that provide press feedback like sounds or haptics
+    ButtonFeedback {
+        /// Key index
+        key: u8
+    },
     /// Called when a button is pressed down
     ButtonDown {
         /// Key index
@@ -79,6 +106,22 @@ pub enum SDCoreEvent {
         /// New root panel
         new_panel: ButtonPanel
     },
+    /// Called after any change to the stack, in addition to more specific events
+    StackChanged {
+        /// Display names of panels in the stack, starting from root
+        stack_names: Vec<String>,
+        /// Amount of panels in the stack
+        depth: usize
+    },
+    /// Called once after an operation that changed many buttons at once, in addition to events of each button
+    ButtonsBulkUpdated {
+        /// Keys of buttons that were changed
+        keys: Vec<u8>,
+        /// Current panel
+        panel: ButtonPanel,
+        /// If panel is currently on top of the stack
+        is_top: bool
+    },
 }
 
 /// Global event enumeration for events that are related to whole program, serializable
@@ -120,6 +163,18 @@ pub enum SDGlobalEvent {
         deleted_button: Button
     },
 
+    /// Called when a value of a component was changed, sent together with [SDGlobalEvent::ButtonUpdated] for each changed path
+    ComponentValueChanged {
+        /// Serial number of the device
+        serial_number: String,
+        /// Key index
+        key: u8,
+        /// Name of the component
+        component: String,
+        /// Path of the value that was changed
+        path: String
+    },
+
     /// Called when a valid button was pressed on
     ButtonAction {
         /// Serial number of the device
@@ -132,6 +187,13 @@ pub enum SDGlobalEvent {
         pressed_button: Button
     },
 
+    /// Called when a button is pressed down, before [SDGlobalEvent::ButtonDown], meant for modules that provide press feedback like sounds or haptics
+    ButtonFeedback {
+        /// Serial number of the device
+        serial_number: String,
+        /// Key index
+        key: u8,
+    },
     /// Called when a button is pressed down
     ButtonDown {
         /// Serial number of the device
@@ -177,6 +239,24 @@ pub enum SDGlobalEvent {
         /// New root panel
         new_panel: RawButtonPanel
     },
+    /// Called after any change to the stack, in addition to more specific events
+    StackChanged {
+        /// Serial number of the device
+        serial_number: String,
+        /// Display names of panels in the stack, starting from root
+        stack_names: Vec<String>,
+        /// Amount of panels in the stack
+        depth: usize
+    },
+    /// Called once after an operation that changed many buttons at once, in addition to events of each button
+    ButtonsBulkUpdated {
+        /// Serial number of the device
+        serial_number: String,
+        /// Keys of buttons that were changed
+        keys: Vec<u8>,
+        /// Current panel
+        panel: RawButtonPanel
+    },
 
     /// Called when device has connected
     DeviceConnected {
@@ -189,20 +269,38 @@ pub enum SDGlobalEvent {
         /// Serial number of the device
         serial_number: String
     },
+
+    /// Called when device was added to managed devices, device will be connected to once it's available
+    DeviceAdded {
+        /// Serial number of the device
+        serial_number: String
+    },
+
+    /// Called when device was removed from managed devices
+    DeviceRemoved {
+        /// Serial number of the device
+        serial_number: String
+    },
+
+    /// Called when device config was reloaded after being changed on disk
+    DeviceConfigReloaded {
+        /// Serial number of the device
+        serial_number: String
+    },
 }
 
 /// Converts [SDCoreEvent] to [SDGlobalEvent] by adding serial number
 pub async fn core_event_to_global(event: SDCoreEvent, serial: &str) -> SDGlobalEvent {
     let serial_number = serial.to_string();
     match event {
-        SDCoreEvent::ButtonAdded { key, panel, added_button } => SDGlobalEvent::ButtonAdded {
+        SDCoreEvent::ButtonAdded { key, panel, added_button, .. } => SDGlobalEvent::ButtonAdded {
             serial_number,
             key,
             panel: panel_to_raw(&panel).await,
             added_button: button_to_raw(&added_button).await,
         },
 
-        SDCoreEvent::ButtonUpdated { key, panel, new_button, old_button } => SDGlobalEvent::ButtonUpdated {
+        SDCoreEvent::ButtonUpdated { key, panel, new_button, old_button, .. } => SDGlobalEvent::ButtonUpdated {
             serial_number,
             key,
             panel: panel_to_raw(&panel).await,
@@ -210,20 +308,32 @@ pub async fn core_event_to_global(event: SDCoreEvent, serial: &str) -> SDGlobalE
             old_button: button_to_raw(&old_button).await,
         },
 
-        SDCoreEvent::ButtonDeleted { key, panel, deleted_button } => SDGlobalEvent::ButtonDeleted {
+        SDCoreEvent::ButtonDeleted { key, panel, deleted_button, .. } => SDGlobalEvent::ButtonDeleted {
             serial_number,
             key,
             panel: panel_to_raw(&panel).await,
             deleted_button: button_to_raw(&deleted_button).await,
         },
 
-        SDCoreEvent::ButtonAction { key, panel, pressed_button } => SDGlobalEvent::ButtonAction {
+        SDCoreEvent::ComponentValueChanged { key, component, path, .. } => SDGlobalEvent::ComponentValueChanged {
+            serial_number,
+            key,
+            component,
+            path,
+        },
+
+        SDCoreEvent::ButtonAction { key, panel, pressed_button, .. } => SDGlobalEvent::ButtonAction {
             serial_number,
             key,
             panel: panel_to_raw(&panel).await,
             pressed_button: button_to_raw(&pressed_button).await,
         },
 
+        SDCoreEvent::ButtonFeedback { key } => SDGlobalEvent::ButtonFeedback {
+            serial_number,
+            key,
+        },
+
         SDCoreEvent::ButtonDown { key } => SDGlobalEvent::ButtonDown {
             serial_number,
             key,
@@ -253,6 +363,18 @@ pub async fn core_event_to_global(event: SDCoreEvent, serial: &str) -> SDGlobalE
         SDCoreEvent::StackReset { new_panel } => SDGlobalEvent::StackReset {
             serial_number,
             new_panel: panel_to_raw(&new_panel).await,
+        },
+
+        SDCoreEvent::StackChanged { stack_names, depth } => SDGlobalEvent::StackChanged {
+            serial_number,
+            stack_names,
+            depth,
+        },
+
+        SDCoreEvent::ButtonsBulkUpdated { keys, panel, .. } => SDGlobalEvent::ButtonsBulkUpdated {
+            serial_number,
+            keys,
+            panel: panel_to_raw(&panel).await,
         }
     }
 }
\ No newline at end of file
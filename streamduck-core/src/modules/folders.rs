@@ -5,11 +5,11 @@ use serde::{Deserialize, Serialize};
 use async_recursion::async_recursion;
 use tokio::sync::RwLock;
 use crate::core::button::{Button, Component, parse_button_to_component, parse_unique_button_to_component};
-use crate::core::{ButtonPanel, CoreHandle, RawButtonPanel};
+use crate::core::{ButtonPanel, CoreHandle, generate_panel_id, RawButtonPanel};
 use crate::modules::components::{ComponentDefinition, map_ui_values, UIFieldType, UIFieldValue, UIValue};
 use crate::modules::events::SDCoreEvent;
 use crate::modules::{PluginMetadata, SDModule};
-use crate::thread::rendering::{ButtonBackground, ButtonText, RendererComponentBuilder};
+use crate::thread::rendering::{ButtonBackground, ButtonText, RendererComponentBuilder, TextFill};
 use crate::util::{button_to_raw, make_panel_unique, straight_copy};
 use crate::thread::util::TextAlignment;
 use crate::versions::{CORE, CORE_METHODS, CORE_EVENTS, MODULE_MANAGER};
@@ -41,6 +41,7 @@ impl SDModule for FolderModule {
         map.insert(FolderComponent::NAME.to_string(), ComponentDefinition {
             display_name: "Folder".to_string(),
             description: "Opens folder".to_string(),
+            requires: vec![],
             default_looks: RendererComponentBuilder::new()
                 .background(ButtonBackground::Solid((0, 50, 200, 255)))
                 .add_text(ButtonText {
@@ -50,8 +51,14 @@ impl SDModule for FolderModule {
                     alignment: TextAlignment::Center,
                     padding: 0,
                     offset: (0.0, 0.0),
-                    color: (255, 255, 255, 255),
-                    shadow: None
+                    fill: TextFill::Solid((255, 255, 255, 255)),
+                    shadow: None,
+                    auto_fit: false,
+                    max_width: 0,
+                    max_height: 0,
+                    letter_spacing: 0.0,
+                    blink: None,
+                    truncate: None
                 })
                 .build()
         });
@@ -59,6 +66,7 @@ impl SDModule for FolderModule {
         map.insert(FolderLinkComponent::NAME.to_string(), ComponentDefinition {
             display_name: "Folder Link".to_string(),
             description: "Opens existing folders in symlink style".to_string(),
+            requires: vec![],
             default_looks: RendererComponentBuilder::new()
                 .background(ButtonBackground::Solid((0, 50, 200, 255)))
                 .add_text(ButtonText {
@@ -68,8 +76,14 @@ impl SDModule for FolderModule {
                                 alignment: TextAlignment::BottomRight,
                                 padding: 7,
                                 offset: (0.0, 0.0),
-                                color: (255, 255, 255, 255),
-                                shadow: None
+                                fill: TextFill::Solid((255, 255, 255, 255)),
+                                shadow: None,
+                                auto_fit: false,
+                                max_width: 0,
+                                max_height: 0,
+                                letter_spacing: 0.0,
+                                blink: None,
+                                truncate: None
                 })
                 .build()
         });
@@ -77,6 +91,7 @@ impl SDModule for FolderModule {
         map.insert(FolderUpComponent::NAME.to_string(), ComponentDefinition {
             display_name: "Folder Up".to_string(),
             description: "Back button for folders".to_string(),
+            requires: vec![],
             default_looks: RendererComponentBuilder::new()
                 .background(ButtonBackground::Solid((50, 50, 50, 255)))
                 .add_text(ButtonText {
@@ -86,8 +101,14 @@ impl SDModule for FolderModule {
                     alignment: TextAlignment::Center,
                     padding: 0,
                     offset: (0.0, 0.0),
-                    color: (255, 255, 255, 255),
-                    shadow: None
+                    fill: TextFill::Solid((255, 255, 255, 255)),
+                    shadow: None,
+                    auto_fit: false,
+                    max_width: 0,
+                    max_height: 0,
+                    letter_spacing: 0.0,
+                    blink: None,
+                    truncate: None
                 })
                 .build()
         });
@@ -285,7 +306,7 @@ impl SDModule for FolderModule {
 
     async fn event(&self, core: CoreHandle, event: SDCoreEvent) {
         match event {
-            SDCoreEvent::ButtonAdded { key, added_button, panel } |
+            SDCoreEvent::ButtonAdded { key, added_button, panel, .. } |
             SDCoreEvent::ButtonUpdated { key, new_button: added_button, panel, .. } => {
                 let panel = panel.read().await;
 
@@ -399,6 +420,7 @@ impl FolderModule {
 
         self.update_folders_data(core, |f| {
             f.insert(folder_id.clone(), RawButtonPanel {
+                id: generate_panel_id(),
                 display_name: "Folder".to_string(),
                 data: Default::default(),
                 buttons: Default::default()
@@ -481,6 +503,9 @@ impl FolderModule {
             }
         }
 
+        // Copy is a different panel, so it shouldn't share stable ID with the original
+        folder.id = generate_panel_id();
+
         let str = self.random_unique_name(core).await;
         self.update_folder(core, &str, folder).await;
         Some(str)
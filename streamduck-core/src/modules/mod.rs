@@ -8,14 +8,17 @@ pub mod plugins;
 pub mod core_module;
 
 use std::collections::HashMap;
+use std::future::Future;
 use std::hash::Hasher;
+use std::panic::AssertUnwindSafe;
 use std::sync::Arc;
 
-use crate::core::button::{Button};
+use crate::core::button::{Button, BUTTON_RESERVED_KEYS};
 use crate::modules::components::{ComponentDefinition, UIPathValue, UIValue};
 use crate::modules::events::{SDCoreEvent, SDGlobalEvent};
 use crate::modules::folders::FolderModule;
 
+use futures::FutureExt;
 use serde::{Deserialize, Serialize};
 
 use image::DynamicImage;
@@ -38,8 +41,26 @@ pub struct ModuleManager {
 
     /// Separate list of modules that can render things
     rendering_modules: RwLock<HashMap<String, HashMap<String, UniqueSDModule>>>,
+
+    /// Amount of times modules panicked while handling events
+    module_panics: Arc<RwLock<HashMap<String, usize>>>,
+
+    /// Plugins that were found, but failed to load
+    failed_plugins: RwLock<Vec<FailedPlugin>>,
+}
+
+/// Plugin that was found in plugins folder, but failed to load
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FailedPlugin {
+    /// File name of the plugin
+    pub file: String,
+    /// Reason why plugin failed to load
+    pub reason: String,
 }
 
+/// Amount of panics after which module is quarantined and stops receiving events
+pub const MODULE_PANIC_LIMIT: usize = 3;
+
 impl ModuleManager {
     /// Creates new module manager, used in daemon for loading plugins and base modules
     pub fn new() -> Arc<ModuleManager> {
@@ -50,6 +71,17 @@ impl ModuleManager {
     pub async fn add_module(&self, module: UniqueSDModule) {
         let module_name = module.name();
 
+        // Names reserved for button metadata can't be used as components, as they share the same map in buttons
+        let mut components = module.components();
+        components.retain(|component, _| {
+            if BUTTON_RESERVED_KEYS.contains(&component.as_str()) {
+                log::warn!("Module '{}' declares component '{}', but the name is reserved for button metadata. Component will be ignored", module_name, component);
+                false
+            } else {
+                true
+            }
+        });
+
         // Adding to module map
         let mut module_map = self.module_map.write().await;
         module_map.insert(module_name.clone(), module.clone());
@@ -57,7 +89,7 @@ impl ModuleManager {
 
         // Adding to module component map
         let mut module_component_map = self.module_component_map.write().await;
-        for (component, definition) in module.components() {
+        for (component, definition) in components.clone() {
             if let Some(component_map) = module_component_map.get_mut(&module_name) {
                 component_map.insert(component, definition);
             } else {
@@ -72,7 +104,7 @@ impl ModuleManager {
 
         // Adding to component to module map
         let mut component_map = self.component_map.write().await;
-        for (component, definition) in module.components() {
+        for (component, definition) in components {
             component_map.insert(component, (definition, module.clone()));
         }
         drop(component_map);
@@ -187,10 +219,9 @@ impl ModuleManager {
 
     /// Retrieves components that module defined
     pub async fn get_components_of_module(&self, module_name: &str) -> Option<HashMap<String, ComponentDefinition>> {
-        let handle = self.module_map.read().await;
-
-        if let Some(module) = handle.get(module_name) {
-            Some(module.components())
+        if self.module_map.read().await.contains_key(module_name) {
+            // Module component map only has components that were accepted when the module was added
+            Some(self.module_component_map.read().await.get(module_name).cloned().unwrap_or_default())
         } else {
             None
         }
@@ -211,19 +242,26 @@ impl ModuleManager {
         self.rendering_modules.read().await.clone()
     }
 
-    /// Retrieves all modules that should be able to render according to list of component names
-    pub async fn get_modules_for_rendering(&self, names: &Vec<String>) -> HashMap<String, UniqueSDModule> {
+    /// Retrieves all modules that should be able to render according to list of component names, in order of the component names
+    pub async fn get_modules_for_rendering(&self, names: &Vec<String>) -> Vec<UniqueSDModule> {
         let rendering_map = self.rendering_modules.read().await;
 
-        let mut map = HashMap::new();
+        let mut list: Vec<UniqueSDModule> = vec![];
 
         for name in names {
             if let Some(modules) = rendering_map.get(name) {
-                map.extend(modules.clone())
+                let mut modules = modules.values().cloned().collect::<Vec<UniqueSDModule>>();
+                modules.sort_by(|a, b| a.name().cmp(&b.name()));
+
+                for module in modules {
+                    if !list.iter().any(|x| x.name() == module.name()) {
+                        list.push(module);
+                    }
+                }
             }
         }
 
-        map
+        list
     }
 
 
@@ -259,7 +297,54 @@ impl ModuleManager {
 
     /// Sends global event to all modules, spawns a separate thread to do it, so doesn't block current thread
     pub async fn send_global_event_to_modules(&self, event: SDGlobalEvent) {
-        send_global_event_to_modules(event, self.get_module_list().await.into_iter());
+        send_global_event_to_modules(event, self.get_module_list().await.into_iter(), self.module_panics.clone());
+    }
+
+    /// Runs event handler of a module, catching any panics. Modules that panicked [MODULE_PANIC_LIMIT] times are quarantined and their handlers won't be run anymore
+    pub async fn run_module_event<F: Future<Output=()>>(&self, module_name: &str, future: F) {
+        run_module_event(&self.module_panics, module_name, future).await
+    }
+
+    /// Checks if module was quarantined after panicking too many times
+    pub async fn is_module_quarantined(&self, module_name: &str) -> bool {
+        self.module_panics.read().await.get(module_name).map_or(false, |x| *x >= MODULE_PANIC_LIMIT)
+    }
+
+    /// Returns amount of times modules panicked while handling events
+    pub async fn get_module_panics(&self) -> HashMap<String, usize> {
+        self.module_panics.read().await.clone()
+    }
+
+    /// Remembers plugin that failed to load, so it can be reported to the user
+    pub async fn add_failed_plugin(&self, file: &str, reason: &str) {
+        self.failed_plugins.write().await.push(FailedPlugin {
+            file: file.to_string(),
+            reason: reason.to_string()
+        });
+    }
+
+    /// Returns plugins that were found, but failed to load
+    pub async fn get_failed_plugins(&self) -> Vec<FailedPlugin> {
+        self.failed_plugins.read().await.clone()
+    }
+}
+
+/// Runs event handler of a module unless it was quarantined, counting panics
+async fn run_module_event<F: Future<Output=()>>(panics: &RwLock<HashMap<String, usize>>, module_name: &str, future: F) {
+    if panics.read().await.get(module_name).map_or(false, |x| *x >= MODULE_PANIC_LIMIT) {
+        return;
+    }
+
+    if AssertUnwindSafe(future).catch_unwind().await.is_err() {
+        let mut handle = panics.write().await;
+        let count = handle.entry(module_name.to_string()).or_insert(0);
+        *count += 1;
+
+        log::error!("Module '{}' panicked while handling an event ({}/{})", module_name, count, MODULE_PANIC_LIMIT);
+
+        if *count >= MODULE_PANIC_LIMIT {
+            log::warn!("Module '{}' panicked too many times and won't receive any more events", module_name);
+        }
     }
 }
 
@@ -331,6 +416,9 @@ pub trait SDModule: Send + Sync {
 
         meta
     }
+
+    /// Method for checking values before they're set on components, returning an error will prevent values from being set
+    async fn validate_component_value(&self, core: CoreHandle, button: &Button, name: &str, value: &Vec<UIValue>) -> Result<(), String> { Ok(()) }
 }
 
 /// Keeps relevant information about plugins
@@ -418,11 +506,12 @@ pub async fn set_module_setting(core_manager: Arc<CoreManager>, module: &UniqueS
 }
 
 /// Sends global event to all modules, spawns a separate thread to do it, so doesn't block current thread
-fn send_global_event_to_modules<T: Iterator<Item=UniqueSDModule> + Send + 'static>(event: SDGlobalEvent, modules: T) {
+fn send_global_event_to_modules<T: Iterator<Item=UniqueSDModule> + Send + 'static>(event: SDGlobalEvent, modules: T, panics: Arc<RwLock<HashMap<String, usize>>>) {
     modules.for_each(|x| {
         let task_event = event.clone();
+        let task_panics = panics.clone();
         tokio::spawn(async move {
-            x.global_event(task_event).await
+            run_module_event(&task_panics, &x.name(), x.global_event(task_event)).await
         });
     });
 }
@@ -2,6 +2,8 @@
 
 use std::collections::HashMap;
 use std::ffi::OsStr;
+use std::fmt;
+use std::fmt::{Display, Formatter};
 use std::fs;
 use std::hash::Hasher;
 use std::path::Path;
@@ -11,6 +13,7 @@ use crate::modules::{ModuleManager, PluginMetadata, SDModule, UniqueSDModule};
 use dlopen::wrapper::{Container, WrapperApi};
 use dlopen_derive::WrapperApi;
 use image::DynamicImage;
+use serde::{Deserialize, Serialize};
 use tokio::task::{JoinError, spawn_blocking};
 use crate::core::button::Button;
 use crate::core::manager::CoreManager;
@@ -65,6 +68,10 @@ impl SDModule for PluginProxy {
         self.plugin.set_component_value(core, button, name, value).await
     }
 
+    async fn validate_component_value(&self, core: CoreHandle, button: &Button, name: &str, value: &Vec<UIValue>) -> Result<(), String> {
+        self.plugin.validate_component_value(core, button, name, value).await
+    }
+
     fn listening_for(&self) -> Vec<String> {
         self.plugin.listening_for()
     }
@@ -219,14 +226,58 @@ pub fn compare_plugin_versions(versions: &Vec<(String, String)>) -> Result<(), P
     Ok(())
 }
 
+/// Features that every plugin should report
+pub const ESSENTIAL_FEATURES: &[&str] = &["compiler_version", "plugin_api", "sdmodule_trait"];
+
+/// Compatibility of a feature used by a plugin with the core
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FeatureCompatibility {
+    /// Name of the feature
+    pub name: String,
+    /// Version of the feature that plugin is using
+    pub plugin_version: String,
+    /// Version of the feature that core supports, None if core doesn't know the feature
+    pub core_version: Option<String>,
+    /// If versions match
+    pub compatible: bool,
+}
+
+/// Compares each feature used by a plugin against features supported by the core
+pub fn get_feature_compatibility(versions: &Vec<(String, String)>) -> Vec<FeatureCompatibility> {
+    let core_versions = SUPPORTED_FEATURES.clone().into_iter()
+        .map(|(n, v)| (*n, *v))
+        .collect::<HashMap<&str, &str>>();
+
+    versions.iter()
+        .map(|(name, version)| {
+            let core_version = core_versions.get(name.as_str()).map(|x| x.to_string());
+
+            FeatureCompatibility {
+                name: name.clone(),
+                plugin_version: version.clone(),
+                compatible: core_version.as_ref() == Some(version),
+                core_version
+            }
+        })
+        .collect()
+}
+
+/// Returns essential features that plugin didn't report in its metadata
+pub fn get_missing_essential_features(meta: &PluginMetadata) -> Vec<String> {
+    ESSENTIAL_FEATURES.iter()
+        .filter(|feature| !check_feature_list_for_feature(&meta.used_features, feature))
+        .map(|feature| feature.to_string())
+        .collect()
+}
+
 /// Warns about essential features
 fn warn_about_essential_features(meta: &PluginMetadata) {
     let name = &meta.name;
     let features = &meta.used_features;
 
-    warn_for_feature(name, &features, "compiler_version");
-    warn_for_feature(name, &features, "plugin_api");
-    warn_for_feature(name, &features, "sdmodule_trait");
+    for feature in ESSENTIAL_FEATURES {
+        warn_for_feature(name, &features, feature);
+    }
 }
 
 /// Loads a plugin into module manager
@@ -291,15 +342,10 @@ pub async fn load_plugins_from_folder<T: AsRef<OsStr>>(config: Arc<Config>, modu
                             if let Some(file_name) = entry.path().file_name() {
                                 log::info!("Loading plugin {:?}", file_name);
                                 match load_plugin(config.clone(), module_manager.clone(), socket_manager.clone(), render_manager.clone(), entry.path()).await {
-                                    Err(err) => match err {
-                                        PluginError::LoadError(err) => log::error!("Failed to load plugin: {}", err),
-                                        PluginError::WrongVersion(plugin, software) => log::error!("Failed to load plugin: Plugin is using unsupported version of '{}', software's using '{}'", plugin, software),
-                                        PluginError::TooNew(version) => log::error!("Failed to load plugin: Software doesn't support '{}', try updating the software", version),
-                                        PluginError::AlreadyExists(name) => log::error!("Failed to load plugin: Module '{}' was already defined", name),
-                                        PluginError::ComponentConflict(name, component_name) => log::error!("Failed to load plugin: Module '{}' is declaring '{}' component, but it was already previously declared by other module", name, component_name),
-                                        PluginError::JoinError(err) => log::error!("Failed to load plugin: {}", err),
-                                        PluginError::NoModulesFound => log::error!("Failed to load plugin: No modules found")
-                                    },
+                                    Err(err) => {
+                                        log::error!("Failed to load plugin: {}", err);
+                                        module_manager.add_failed_plugin(&file_name.to_string_lossy(), &err.to_string()).await;
+                                    }
                                     _ => {}
                                 }
                             }
@@ -338,6 +384,20 @@ pub enum PluginError {
     JoinError(tokio::task::JoinError)
 }
 
+impl Display for PluginError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PluginError::LoadError(err) => write!(f, "{}", err),
+            PluginError::WrongVersion(plugin, software) => write!(f, "Plugin is using unsupported version of '{}', software's using '{}'", plugin, software),
+            PluginError::TooNew(version) => write!(f, "Software doesn't support '{}', try updating the software", version),
+            PluginError::AlreadyExists(name) => write!(f, "Module '{}' was already defined", name),
+            PluginError::ComponentConflict(name, component_name) => write!(f, "Module '{}' is declaring '{}' component, but it was already previously declared by other module", name, component_name),
+            PluginError::JoinError(err) => write!(f, "{}", err),
+            PluginError::NoModulesFound => write!(f, "No modules found")
+        }
+    }
+}
+
 impl From<dlopen::Error> for PluginError {
     fn from(err: Error) -> Self {
         PluginError::LoadError(err)
@@ -0,0 +1,140 @@
+//! Worker pool for decoding images outside of device thread
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread::spawn;
+use tokio::runtime::Builder;
+use crate::core::SDCore;
+use crate::images::SDImage;
+use crate::util::hash_str;
+
+/// Image decoding job
+struct ImageJob {
+    identifier: String,
+    blob: String,
+    core: Arc<SDCore>,
+}
+
+/// Pool of threads that decode images, so device thread can keep rendering other buttons while large images are loading
+pub struct ImageLoader {
+    tx: Option<Mutex<Sender<ImageJob>>>,
+    pending: Arc<Mutex<HashSet<String>>>,
+    failed: Arc<Mutex<HashSet<String>>>,
+    decoded: Arc<Mutex<HashMap<String, SDImage>>>,
+}
+
+impl ImageLoader {
+    /// Creates image loader with specified amount of worker threads, 0 workers creates disabled loader
+    pub fn new(workers: usize) -> ImageLoader {
+        let pending = Arc::new(Mutex::new(HashSet::new()));
+        let failed = Arc::new(Mutex::new(HashSet::new()));
+        let decoded = Arc::new(Mutex::new(HashMap::new()));
+
+        if workers == 0 {
+            return ImageLoader {
+                tx: None,
+                pending,
+                failed,
+                decoded
+            };
+        }
+
+        let (tx, rx) = channel::<ImageJob>();
+        let rx = Arc::new(Mutex::new(rx));
+
+        for _ in 0..workers {
+            spawn_worker(rx.clone(), pending.clone(), failed.clone(), decoded.clone());
+        }
+
+        ImageLoader {
+            tx: Some(Mutex::new(tx)),
+            pending,
+            failed,
+            decoded
+        }
+    }
+
+    /// Checks if images should be decoded by the loader
+    pub fn is_enabled(&self) -> bool {
+        self.tx.is_some()
+    }
+
+    /// Checks if image with provided identifier is currently being decoded
+    pub fn is_pending(&self, identifier: &str) -> bool {
+        self.pending.lock().unwrap().contains(identifier)
+    }
+
+    /// Checks if image with provided identifier failed to decode, such images won't be queued again
+    pub fn has_failed(&self, identifier: &str) -> bool {
+        self.failed.lock().unwrap().contains(identifier)
+    }
+
+    /// Gets image that was already decoded by the loader
+    pub fn get_decoded(&self, identifier: &str) -> Option<SDImage> {
+        self.decoded.lock().unwrap().get(identifier).cloned()
+    }
+
+    /// Checks if image with provided identifier was already decoded by the loader
+    pub fn is_decoded(&self, identifier: &str) -> bool {
+        self.decoded.lock().unwrap().contains_key(identifier)
+    }
+
+    /// Queues base64 image to be decoded, decoded image is kept by the loader separately from image collection, and the core gets redrawn.
+    /// Returns false if loader is disabled
+    pub fn load_base64(&self, core: Arc<SDCore>, blob: &String) -> bool {
+        if let Some(tx) = &self.tx {
+            let identifier = hash_str(blob);
+
+            if !self.has_failed(&identifier) && !self.is_decoded(&identifier) && self.pending.lock().unwrap().insert(identifier.clone()) {
+                tx.lock().unwrap().send(ImageJob {
+                    identifier,
+                    blob: blob.clone(),
+                    core
+                }).ok();
+            }
+
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Spawns worker thread that exits once loader is dropped
+fn spawn_worker(rx: Arc<Mutex<Receiver<ImageJob>>>, pending: Arc<Mutex<HashSet<String>>>, failed: Arc<Mutex<HashSet<String>>>, decoded: Arc<Mutex<HashMap<String, SDImage>>>) {
+    spawn(move || {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        loop {
+            let job = rx.lock().unwrap().recv();
+
+            if let Ok(job) = job {
+                runtime.block_on(async {
+                    match SDImage::from_base64(&job.blob, job.core.image_size).await {
+                        Ok(image) => {
+                            // Not adding to image collection, as it would end up saved into device config
+                            decoded.lock().unwrap().insert(job.identifier.clone(), image);
+                        }
+
+                        Err(err) => {
+                            log::warn!("Failed to decode image: {:?}", err);
+                            failed.lock().unwrap().insert(job.identifier.clone());
+                        }
+                    }
+
+                    pending.lock().unwrap().remove(&job.identifier);
+
+                    if !job.core.is_closed().await {
+                        job.core.mark_for_redraw().await;
+                    }
+                });
+            } else {
+                break;
+            }
+        }
+    });
+}
@@ -2,35 +2,37 @@
 //!
 //! A separate thread for processing, rendering images on streamdeck and reading buttons
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::Cursor;
 use std::ops::Deref;
-use std::sync::{Arc};
+use std::sync::{Arc, Mutex};
 use std::sync::mpsc::{channel, Sender, TryRecvError};
-use std::thread::spawn;
+use std::thread::{JoinHandle, spawn};
 use std::time::{Duration, Instant};
-use image::{DynamicImage, ImageFormat};
-use streamdeck::{Colour, DeviceImage, ImageMode, StreamDeck};
+use image::DynamicImage;
+use streamdeck::{Colour, DeviceImage, StreamDeck};
 use tokio::runtime::Builder;
 use tokio::sync::mpsc::UnboundedSender;
-use tokio::sync::RwLock;
-use rendering::RendererComponent;
+use tokio::sync::{oneshot, RwLock};
+use rendering::{RenderCache, RenderCacheDump, RendererComponent, RendererSettings, RenderStats};
 use crate::core::{CoreHandle, SDCore};
 use crate::core::button::{Component, parse_unique_button_to_component};
-use crate::images::SDImage;
+use crate::images::{convert_image_with_quality, device_image_format, SDImage};
 use crate::modules::core_module::CoreSettings;
-use crate::modules::UniqueSDModule;
+use crate::thread::util::apply_color_mode_on_image;
 
 /// Rendering utilities
 pub mod util;
 pub mod rendering;
+pub mod image_loader;
 
 /// Collection of images
 pub type ImageCollection = Arc<RwLock<HashMap<String, SDImage>>>;
 
 /// Handle for contacting renderer thread
 pub struct DeviceThreadHandle {
-    tx: Sender<Vec<DeviceThreadCommunication>>
+    tx: Sender<Vec<DeviceThreadCommunication>>,
+    join_handle: Mutex<Option<JoinHandle<()>>>
 }
 
 impl DeviceThreadHandle {
@@ -38,6 +40,16 @@ impl DeviceThreadHandle {
     pub fn send(&self, commands: Vec<DeviceThreadCommunication>) {
         self.tx.send(commands).ok();
     }
+
+    /// Tells device thread to stop, thread will exit on its next iteration
+    pub fn shutdown(&self) {
+        self.send(vec![DeviceThreadCommunication::Shutdown]);
+    }
+
+    /// Takes join handle of the device thread, can only be taken once
+    pub fn take_join_handle(&self) -> Option<JoinHandle<()>> {
+        self.join_handle.lock().unwrap().take()
+    }
 }
 
 /// Enum of various operations that can be sent to device thread
@@ -46,6 +58,9 @@ pub enum DeviceThreadCommunication {
     /// Tells renderer that screen should be updated
     RefreshScreen,
 
+    /// Tells renderer to redraw all buttons once without using or filling the image cache
+    RedrawUncached,
+
     /// Sets streamdeck brightness to provided value
     SetBrightness(u8),
 
@@ -55,15 +70,56 @@ pub enum DeviceThreadCommunication {
     /// Sets button image to raw buffer of image
     SetButtonImageRaw(u8, Arc<DeviceImage>),
 
+    /// Shows image on the button for specified duration instead of its render, replaces previous temporary image of the button
+    SetTemporaryImage(u8, DynamicImage, Duration),
+
     /// Clears button and sets it to black color
     ClearButtonImage(u8),
+
+    /// Sends contents of render cache to provided channel
+    DumpRenderCache(oneshot::Sender<RenderCacheDump>),
+
+    /// Tells device thread to stop and exit
+    Shutdown,
+}
+
+/// Coalesces rapid presses of keys that go over configured event rate
+#[derive(Default)]
+struct KeyThrottle {
+    last_presses: HashMap<u8, Instant>,
+    dropped: HashSet<u8>,
+}
+
+impl KeyThrottle {
+    /// Checks if key event should be passed on, releases of dropped presses are dropped too. Rate of 0 lets all presses through
+    fn allow(&mut self, key: u8, down: bool, rate: u32) -> bool {
+        if !down {
+            return !self.dropped.remove(&key);
+        }
+
+        if rate == 0 {
+            return true;
+        }
+
+        let interval = Duration::from_secs_f32(1.0 / rate as f32);
+
+        if let Some(last_press) = self.last_presses.get(&key) {
+            if last_press.elapsed() < interval {
+                self.dropped.insert(key);
+                return false;
+            }
+        }
+
+        self.last_presses.insert(key, Instant::now());
+        true
+    }
 }
 
 /// Spawns device thread from a core reference
 pub fn spawn_device_thread(core: Arc<SDCore>, streamdeck: StreamDeck, key_tx: UnboundedSender<(u8, bool)>) -> DeviceThreadHandle {
     let (tx, rx) = channel::<Vec<DeviceThreadCommunication>>();
 
-    spawn(move || {
+    let join_handle = spawn(move || {
         let runtime = Builder::new_current_thread()
             .enable_all()
             .build()
@@ -81,11 +137,24 @@ pub fn spawn_device_thread(core: Arc<SDCore>, streamdeck: StreamDeck, key_tx: Un
             let mut animation_counters = HashMap::new();
             let mut last_iter = Instant::now();
             let mut renderer_map = HashMap::new();
-            let mut animation_cache: HashMap<u64, (Arc<DeviceImage>, u64)> = HashMap::new();
+            let mut missing_keys = HashSet::new();
+            let mut renderer_refreshes: HashMap<String, Instant> = HashMap::new();
+            let mut animation_cache: RenderCache = HashMap::new();
             let mut previous_state: HashMap<u8, u64> = HashMap::new();
+            let mut last_good_images: HashMap<u8, Arc<DeviceImage>> = HashMap::new();
+            let mut temporary_images: HashMap<u8, Instant> = HashMap::new();
+            let mut skip_cache = false;
+            let mut renderer_settings = RendererSettings::default();
             let mut time = 0;
             let mut last_time = time;
-            loop {
+            let mut stats = RenderStats::default();
+            let mut stats_time = Instant::now();
+            let mut stats_frames = 0;
+            let mut stats_frame_time = 0.0;
+            let mut key_throttle = KeyThrottle::default();
+            let mut key_event_rate = core.core.device_config.read().await.key_event_rate;
+            let mut render_cache_disabled = core.core.device_config.read().await.disable_render_cache;
+            'device: loop {
                 if core.core.is_closed().await {
                     break;
                 }
@@ -99,17 +168,23 @@ pub fn spawn_device_thread(core: Arc<SDCore>, streamdeck: StreamDeck, key_tx: Un
                                     streamdeck.set_brightness(brightness).ok();
                                 }
 
-                                DeviceThreadCommunication::SetButtonImage(key, image) => {
+                                DeviceThreadCommunication::SetButtonImage(key, mut image) => {
+                                    apply_color_mode_on_image(&mut image, renderer_settings.color_mode);
+
                                     let mut buffer = vec![];
 
-                                    image.write_to(&mut Cursor::new(&mut buffer), match streamdeck.kind().image_mode() {
-                                        ImageMode::Bmp => ImageFormat::Bmp,
-                                        ImageMode::Jpeg => ImageFormat::Jpeg,
-                                    }).ok();
+                                    image.write_to(&mut Cursor::new(&mut buffer), device_image_format(&streamdeck.kind(), renderer_settings.jpeg_quality)).ok();
 
                                     streamdeck.write_button_image(key, &DeviceImage::from(buffer)).ok();
                                 }
 
+                                DeviceThreadCommunication::SetTemporaryImage(key, mut image, duration) => {
+                                    apply_color_mode_on_image(&mut image, renderer_settings.color_mode);
+
+                                    streamdeck.write_button_image(key, &convert_image_with_quality(&streamdeck.kind(), image, renderer_settings.jpeg_quality)).ok();
+                                    temporary_images.insert(key, Instant::now() + duration);
+                                }
+
                                 DeviceThreadCommunication::SetButtonImageRaw(key, image) => {
                                     streamdeck.write_button_image(key, image.deref()).ok();
                                 }
@@ -122,6 +197,19 @@ pub fn spawn_device_thread(core: Arc<SDCore>, streamdeck: StreamDeck, key_tx: Un
                                     }).ok();
                                 }
 
+                                DeviceThreadCommunication::Shutdown => {
+                                    break 'device;
+                                }
+
+                                DeviceThreadCommunication::DumpRenderCache(sender) => {
+                                    sender.send(RenderCacheDump::new(&animation_cache, &previous_state, time)).ok();
+                                }
+
+                                DeviceThreadCommunication::RedrawUncached => {
+                                    previous_state.clear();
+                                    skip_cache = true;
+                                }
+
                                 DeviceThreadCommunication::RefreshScreen => {
                                     let current_screen = core.get_current_screen().await;
 
@@ -137,20 +225,38 @@ pub fn spawn_device_thread(core: Arc<SDCore>, streamdeck: StreamDeck, key_tx: Un
                                     let core_settings: CoreSettings = core.config().get_plugin_settings().await.unwrap_or_default();
 
                                     renderer_map.clear();
+                                    missing_keys.clear();
+                                    renderer_settings = core_settings.renderer.clone();
+                                    key_event_rate = core.core.device_config.read().await.key_event_rate;
+                                    render_cache_disabled = core.core.device_config.read().await.disable_render_cache;
+
+                                    if render_cache_disabled {
+                                        animation_cache.clear();
+                                    }
+
+                                    let safe_mode = core.config().safe_mode();
+                                    let component_map = core.module_manager().get_components().await;
 
                                     for (key, button) in current_screen {
                                         let unwrapped_button = button.read().await;
+
+                                        // Buttons with components of modules that weren't loaded in safe mode get a placeholder
+                                        if safe_mode && unwrapped_button.component_names().iter().any(|x| !component_map.contains_key(x)) {
+                                            missing_keys.insert(key);
+                                            continue;
+                                        }
+
                                         if unwrapped_button.0.contains_key(RendererComponent::NAME) {
-                                            let names = unwrapped_button.component_names();
+                                            let names = unwrapped_button.enabled_component_names();
                                             let mut modules = core.module_manager().get_modules_for_rendering(&names).await;
                                             drop(unwrapped_button);
 
                                             let component = parse_unique_button_to_component::<RendererComponent>(&button).await.unwrap();
 
-                                            modules.retain(|x, _| !component.plugin_blacklist.contains(x));
-                                            modules.retain(|x, _| !core_settings.renderer.plugin_blacklist.contains(x));
+                                            modules.retain(|x| !component.plugin_blacklist.contains(&x.name()));
+                                            modules.retain(|x| !core_settings.renderer.plugin_blacklist.contains(&x.name()));
 
-                                            renderer_map.insert(key, (component, button, modules.into_values().collect::<Vec<UniqueSDModule>>()));
+                                            renderer_map.insert(key, (component, button, modules));
                                         }
                                     }
 
@@ -169,12 +275,55 @@ pub fn spawn_device_thread(core: Arc<SDCore>, streamdeck: StreamDeck, key_tx: Un
                     }
                 }
 
-                rendering::process_frame(&core, &mut streamdeck, &mut animation_cache, &mut animation_counters, &mut renderer_map, &mut previous_state, &missing, time).await;
+                // Periodically refreshing custom renderers that are used on current screen, their buttons are rendered every tick anyway
+                for (name, renderer) in core.core.render_manager.read_renderers().await.iter() {
+                    if let Some(interval) = renderer.refresh_interval() {
+                        if !renderer_map.values().any(|(component, ..)| component.renderer == *name) {
+                            continue;
+                        }
+
+                        let last_refresh = renderer_refreshes.entry(name.clone()).or_insert_with(Instant::now);
+                        if last_refresh.elapsed() >= interval {
+                            *last_refresh = Instant::now();
+                            renderer.refresh(&core).await;
+                        }
+                    }
+                }
+
+                // Restoring renders of buttons whose temporary images expired
+                temporary_images.retain(|key, until| {
+                    if Instant::now() >= *until {
+                        previous_state.remove(key);
+                        false
+                    } else {
+                        true
+                    }
+                });
+
+                let frame_start = Instant::now();
+                rendering::process_frame(&core, &mut streamdeck, &mut animation_cache, &mut animation_counters, &mut renderer_map, &mut previous_state, &mut last_good_images, &missing_keys, &temporary_images, &missing, time, &mut stats, skip_cache || render_cache_disabled, &renderer_settings).await;
+                skip_cache = false;
                 time += 1;
 
+                // Publishing render statistics every second
+                stats_frames += 1;
+                stats_frame_time += frame_start.elapsed().as_secs_f32();
+
+                let stats_elapsed = stats_time.elapsed().as_secs_f32();
+                if stats_elapsed >= 1.0 {
+                    stats.frames_per_second = stats_frames as f32 / stats_elapsed;
+                    stats.average_frame_time = stats_frame_time / stats_frames as f32 * 1000.0;
+                    stats.cached_images = animation_cache.len();
+                    *core.core.render_stats.write().await = stats.clone();
+
+                    stats_time = Instant::now();
+                    stats_frames = 0;
+                    stats_frame_time = 0.0;
+                }
+
                 // Occasionally cleaning cache
                 if time % 3000 == 0 && time != last_time {
-                    animation_cache.retain(|_, (_, t)| *t > time);
+                    animation_cache.retain(|_, entry| entry.expires_at > time);
                 }
 
                 last_time = time;
@@ -192,14 +341,14 @@ pub fn spawn_device_thread(core: Arc<SDCore>, streamdeck: StreamDeck, key_tx: Un
                     Ok(buttons) => {
                         for (key, value) in buttons.iter().enumerate() {
                             if let Some(last_value) = last_buttons.get(key) {
-                                if last_value != value {
+                                if last_value != value && key_throttle.allow(key as u8, *last_value == 0, key_event_rate) {
                                     if key_tx.send((key as u8, *last_value == 0)).is_err() {
                                         log::error!("Key Handler task crashed, killing connection...");
                                         core.core.close().await;
                                     }
                                 }
                             } else {
-                                if *value > 0 {
+                                if *value > 0 && key_throttle.allow(key as u8, true, key_event_rate) {
                                     if key_tx.send((key as u8, true)).is_err() {
                                         log::error!("Key Handler task crashed, killing connection...");
                                         core.core.close().await;
@@ -231,6 +380,7 @@ pub fn spawn_device_thread(core: Arc<SDCore>, streamdeck: StreamDeck, key_tx: Un
     });
 
     DeviceThreadHandle {
-        tx
+        tx,
+        join_handle: Mutex::new(Some(join_handle))
     }
 }
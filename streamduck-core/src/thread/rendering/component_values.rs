@@ -2,12 +2,19 @@ use strum::VariantNames;
 use std::str::FromStr;
 use crate::core::button::{Button, parse_button_to_component};
 use crate::core::CoreHandle;
-use crate::modules::components::{map_ui_values, map_ui_values_ref, UIField, UIFieldType, UIFieldValue, UIValue};
-use crate::thread::rendering::{ButtonBackground, ButtonText, ButtonTextShadow, RendererComponent};
+use crate::modules::core_module::CoreSettings;
+use crate::modules::components::{map_ui_values, map_ui_values_ref, UIField, UIFieldType, UIFieldValue, UIScalar, UIValue};
+use crate::thread::rendering::{BlinkConfig, ButtonBackground, ButtonText, ButtonTextShadow, GradientDirection, ImageLayer, RendererComponent, TextFill, TruncateMode};
 use crate::thread::util::TextAlignment;
 use crate::images::SDImage;
 use crate::util::hash_str;
 
+/// Choices of text truncation in component values
+const TRUNCATE_CHOICES: [&str; 3] = ["None", "Characters", "Width"];
+
+/// Choices of text fill in component values
+const FILL_CHOICES: [&str; 4] = ["Solid", "Horizontal Gradient", "Vertical Gradient", "Diagonal Gradient"];
+
 /// Retrieves component values for the renderer in specified button
 pub async fn get_renderer_component_values(core: &CoreHandle, button: &Button) -> Vec<UIValue> {
     if let Ok(component) = parse_button_to_component::<RendererComponent>(button) {
@@ -50,7 +57,7 @@ pub async fn get_renderer_component_values(core: &CoreHandle, button: &Button) -
                                 name: "background".to_string(),
                                 display_name: "Background Type".to_string(),
                                 description: "Type of the background to use".to_string(),
-                                ty: UIFieldType::Choice(vec!["Solid Color".to_string(), "Horizontal Gradient".to_string(), "Vertical Gradient".to_string(), "Existing Image".to_string(), "New Image".to_string()]),
+                                ty: UIFieldType::Choice(vec!["Solid Color".to_string(), "Horizontal Gradient".to_string(), "Vertical Gradient".to_string(), "Existing Image".to_string(), "New Image".to_string(), "Image Stack".to_string()]),
                                 value: UIFieldValue::Choice(
                                     match &component.background {
                                         ButtonBackground::Solid(_) => "Solid Color",
@@ -58,6 +65,7 @@ pub async fn get_renderer_component_values(core: &CoreHandle, button: &Button) -
                                         ButtonBackground::VerticalGradient(_, _) => "Vertical Gradient",
                                         ButtonBackground::ExistingImage(_) => "Existing Image",
                                         ButtonBackground::NewImage(_) => "New Image",
+                                        ButtonBackground::ImageStack(_) => "Image Stack",
                                     }.to_string()
                                 )
                             }
@@ -141,6 +149,99 @@ pub async fn get_renderer_component_values(core: &CoreHandle, button: &Button) -
                                     }
                                 );
                             }
+                            ButtonBackground::ImageStack(layers) => {
+                                fields.push(
+                                    UIValue {
+                                        name: "layers".to_string(),
+                                        display_name: "Image Layers".to_string(),
+                                        description: "Images that will be drawn on top of each other, first layer is at the bottom".to_string(),
+                                        ty: UIFieldType::Array(
+                                            vec![
+                                                UIField {
+                                                    name: "image".to_string(),
+                                                    display_name: "Image".to_string(),
+                                                    description: "Image of the layer".to_string(),
+                                                    ty: UIFieldType::ExistingImage,
+                                                    default_value: UIFieldValue::ExistingImage("".to_string())
+                                                },
+                                                UIField {
+                                                    name: "position".to_string(),
+                                                    display_name: "Position".to_string(),
+                                                    description: "Position of top left corner of the layer in pixels".to_string(),
+                                                    ty: UIFieldType::InputFieldInteger2,
+                                                    default_value: UIFieldValue::InputFieldInteger2(0, 0)
+                                                },
+                                                UIField {
+                                                    name: "scale".to_string(),
+                                                    display_name: "Scale".to_string(),
+                                                    description: "Size of the layer relative to button size".to_string(),
+                                                    ty: UIFieldType::InputFieldFloat,
+                                                    default_value: UIFieldValue::InputFieldFloat(1.0)
+                                                },
+                                                UIField {
+                                                    name: "opacity".to_string(),
+                                                    display_name: "Opacity".to_string(),
+                                                    description: "Opacity of the layer".to_string(),
+                                                    ty: UIFieldType::InputFieldFloat,
+                                                    default_value: UIFieldValue::InputFieldFloat(1.0)
+                                                },
+                                                UIField {
+                                                    name: "autocrop".to_string(),
+                                                    display_name: "Autocrop".to_string(),
+                                                    description: "If fully transparent borders of the image should be trimmed before scaling".to_string(),
+                                                    ty: UIFieldType::Checkbox {
+                                                        disabled: false
+                                                    },
+                                                    default_value: UIFieldValue::Checkbox(false)
+                                                },
+                                            ]
+                                        ),
+                                        value: UIFieldValue::Array(
+                                            layers.iter()
+                                                .map(|layer| vec![
+                                                    UIValue {
+                                                        name: "image".to_string(),
+                                                        display_name: "Image".to_string(),
+                                                        description: "Image of the layer".to_string(),
+                                                        ty: UIFieldType::ExistingImage,
+                                                        value: UIFieldValue::ExistingImage(layer.image.clone())
+                                                    },
+                                                    UIValue {
+                                                        name: "position".to_string(),
+                                                        display_name: "Position".to_string(),
+                                                        description: "Position of top left corner of the layer in pixels".to_string(),
+                                                        ty: UIFieldType::InputFieldInteger2,
+                                                        value: UIFieldValue::InputFieldInteger2(layer.position.0, layer.position.1)
+                                                    },
+                                                    UIValue {
+                                                        name: "scale".to_string(),
+                                                        display_name: "Scale".to_string(),
+                                                        description: "Size of the layer relative to button size".to_string(),
+                                                        ty: UIFieldType::InputFieldFloat,
+                                                        value: UIFieldValue::InputFieldFloat(layer.scale)
+                                                    },
+                                                    UIValue {
+                                                        name: "opacity".to_string(),
+                                                        display_name: "Opacity".to_string(),
+                                                        description: "Opacity of the layer".to_string(),
+                                                        ty: UIFieldType::InputFieldFloat,
+                                                        value: UIFieldValue::InputFieldFloat(layer.opacity)
+                                                    },
+                                                    UIValue {
+                                                        name: "autocrop".to_string(),
+                                                        display_name: "Autocrop".to_string(),
+                                                        description: "If fully transparent borders of the image should be trimmed before scaling".to_string(),
+                                                        ty: UIFieldType::Checkbox {
+                                                            disabled: false
+                                                        },
+                                                        value: UIFieldValue::Checkbox(layer.autocrop)
+                                                    },
+                                                ])
+                                                .collect()
+                                        )
+                                    }
+                                );
+                            }
                         }
 
                         fields
@@ -148,6 +249,9 @@ pub async fn get_renderer_component_values(core: &CoreHandle, button: &Button) -
                 }
             );
 
+            let core_settings: CoreSettings = core.core.config.get_plugin_settings().await.unwrap_or_default();
+            let renderer_settings = core_settings.renderer;
+
             // Text array
             fields.push(
                 UIValue {
@@ -177,14 +281,14 @@ pub async fn get_renderer_component_values(core: &CoreHandle, button: &Button) -
                                             display_name: "Font".to_string(),
                                             description: "Font that will be used for text rendering".to_string(),
                                             ty: UIFieldType::Font,
-                                            default_value: UIFieldValue::Font("default".to_string())
+                                            default_value: UIFieldValue::Font(renderer_settings.default_font.clone())
                                         },
                                         UIField {
                                             name: "scale".to_string(),
                                             display_name: "Text Scale".to_string(),
                                             description: "Scale of the text".to_string(),
                                             ty: UIFieldType::InputFieldFloat2,
-                                            default_value: UIFieldValue::InputFieldFloat2(15.0, 15.0)
+                                            default_value: UIFieldValue::InputFieldFloat2(renderer_settings.default_scale.0, renderer_settings.default_scale.1)
                                         },
                                         UIField {
                                             name: "alignment".to_string(),
@@ -216,6 +320,85 @@ pub async fn get_renderer_component_values(core: &CoreHandle, button: &Button) -
                                             ty: UIFieldType::Color,
                                             default_value: UIFieldValue::Color(0, 0, 0, 255)
                                         },
+                                        UIField {
+                                            name: "fill".to_string(),
+                                            display_name: "Text Fill".to_string(),
+                                            description: "If text should be filled with text color or with gradient from text color to gradient end color".to_string(),
+                                            ty: UIFieldType::Choice(FILL_CHOICES.iter().map(|x| x.to_string()).collect()),
+                                            default_value: UIFieldValue::Choice("Solid".to_string())
+                                        },
+                                        UIField {
+                                            name: "fill_end".to_string(),
+                                            display_name: "Gradient End Color".to_string(),
+                                            description: "Color at the end of text gradient".to_string(),
+                                            ty: UIFieldType::Color,
+                                            default_value: UIFieldValue::Color(0, 0, 0, 255)
+                                        },
+                                        UIField {
+                                            name: "auto_fit".to_string(),
+                                            display_name: "Auto Fit".to_string(),
+                                            description: "If text scale should be reduced until text fits into max size".to_string(),
+                                            ty: UIFieldType::Checkbox {
+                                                disabled: false
+                                            },
+                                            default_value: UIFieldValue::Checkbox(false)
+                                        },
+                                        UIField {
+                                            name: "max_width".to_string(),
+                                            display_name: "Max Width".to_string(),
+                                            description: "Max width of auto fitted text, 0 to use button width".to_string(),
+                                            ty: UIFieldType::InputFieldUnsignedInteger,
+                                            default_value: UIFieldValue::InputFieldUnsignedInteger(0)
+                                        },
+                                        UIField {
+                                            name: "max_height".to_string(),
+                                            display_name: "Max Height".to_string(),
+                                            description: "Max height of auto fitted text, 0 to use button height".to_string(),
+                                            ty: UIFieldType::InputFieldUnsignedInteger,
+                                            default_value: UIFieldValue::InputFieldUnsignedInteger(0)
+                                        },
+                                        UIField {
+                                            name: "letter_spacing".to_string(),
+                                            display_name: "Letter Spacing".to_string(),
+                                            description: "Extra space in pixels between characters".to_string(),
+                                            ty: UIFieldType::InputFieldFloat,
+                                            default_value: UIFieldValue::InputFieldFloat(0.0)
+                                        },
+                                        UIField {
+                                            name: "blink_on".to_string(),
+                                            display_name: "Blink On Time".to_string(),
+                                            description: "Time in milliseconds during which blinking text is shown, blinking is disabled if both times are 0".to_string(),
+                                            ty: UIFieldType::InputFieldUnsignedInteger,
+                                            default_value: UIFieldValue::InputFieldUnsignedInteger(0)
+                                        },
+                                        UIField {
+                                            name: "blink_off".to_string(),
+                                            display_name: "Blink Off Time".to_string(),
+                                            description: "Time in milliseconds during which blinking text is hidden, blinking is disabled if both times are 0".to_string(),
+                                            ty: UIFieldType::InputFieldUnsignedInteger,
+                                            default_value: UIFieldValue::InputFieldUnsignedInteger(0)
+                                        },
+                                        UIField {
+                                            name: "truncate".to_string(),
+                                            display_name: "Truncate".to_string(),
+                                            description: "How text that is too long should be shortened".to_string(),
+                                            ty: UIFieldType::Choice(TRUNCATE_CHOICES.iter().map(|x| x.to_string()).collect()),
+                                            default_value: UIFieldValue::Choice("None".to_string())
+                                        },
+                                        UIField {
+                                            name: "truncate_limit".to_string(),
+                                            display_name: "Truncate Limit".to_string(),
+                                            description: "Max amount of characters or max width in pixels of the text, 0 width means button width".to_string(),
+                                            ty: UIFieldType::InputFieldUnsignedInteger,
+                                            default_value: UIFieldValue::InputFieldUnsignedInteger(0)
+                                        },
+                                        UIField {
+                                            name: "ellipsis".to_string(),
+                                            display_name: "Ellipsis".to_string(),
+                                            description: "Text that will be added to the end of shortened text".to_string(),
+                                            ty: UIFieldType::InputFieldString,
+                                            default_value: UIFieldValue::InputFieldString("...".to_string())
+                                        },
                                         UIField {
                                             name: "shadow_enabled".to_string(),
                                             display_name: "Text Shadow".to_string(),
@@ -288,7 +471,115 @@ pub async fn get_renderer_component_values(core: &CoreHandle, button: &Button) -
                                             display_name: "Text Color".to_string(),
                                             description: "Color that text will be displayed in".to_string(),
                                             ty: UIFieldType::Color,
-                                            value: text.color.into()
+                                            value: match &text.fill {
+                                                TextFill::Solid(color) => (*color).into(),
+                                                TextFill::Gradient { start, .. } => (*start).into()
+                                            }
+                                        });
+
+                                        let (fill, fill_end) = match &text.fill {
+                                            TextFill::Gradient { end, direction, .. } => (match direction {
+                                                GradientDirection::Horizontal => "Horizontal Gradient",
+                                                GradientDirection::Vertical => "Vertical Gradient",
+                                                GradientDirection::Diagonal => "Diagonal Gradient",
+                                            }, *end),
+                                            _ => ("Solid", (0, 0, 0, 255))
+                                        };
+
+                                        values.push(UIValue {
+                                            name: "fill".to_string(),
+                                            display_name: "Text Fill".to_string(),
+                                            description: "If text should be filled with text color or with gradient from text color to gradient end color".to_string(),
+                                            ty: UIFieldType::Choice(FILL_CHOICES.iter().map(|x| x.to_string()).collect()),
+                                            value: UIFieldValue::Choice(fill.to_string())
+                                        });
+
+                                        values.push(UIValue {
+                                            name: "fill_end".to_string(),
+                                            display_name: "Gradient End Color".to_string(),
+                                            description: "Color at the end of text gradient".to_string(),
+                                            ty: UIFieldType::Color,
+                                            value: fill_end.into()
+                                        });
+
+                                        values.push(UIValue {
+                                            name: "auto_fit".to_string(),
+                                            display_name: "Auto Fit".to_string(),
+                                            description: "If text scale should be reduced until text fits into max size".to_string(),
+                                            ty: UIFieldType::Checkbox {
+                                                disabled: false
+                                            },
+                                            value: UIFieldValue::Checkbox(text.auto_fit)
+                                        });
+
+                                        values.push(UIValue {
+                                            name: "max_width".to_string(),
+                                            display_name: "Max Width".to_string(),
+                                            description: "Max width of auto fitted text, 0 to use button width".to_string(),
+                                            ty: UIFieldType::InputFieldUnsignedInteger,
+                                            value: UIFieldValue::InputFieldUnsignedInteger(text.max_width)
+                                        });
+
+                                        values.push(UIValue {
+                                            name: "max_height".to_string(),
+                                            display_name: "Max Height".to_string(),
+                                            description: "Max height of auto fitted text, 0 to use button height".to_string(),
+                                            ty: UIFieldType::InputFieldUnsignedInteger,
+                                            value: UIFieldValue::InputFieldUnsignedInteger(text.max_height)
+                                        });
+
+                                        values.push(UIValue {
+                                            name: "letter_spacing".to_string(),
+                                            display_name: "Letter Spacing".to_string(),
+                                            description: "Extra space in pixels between characters".to_string(),
+                                            ty: UIFieldType::InputFieldFloat,
+                                            value: UIFieldValue::InputFieldFloat(text.letter_spacing)
+                                        });
+
+                                        values.push(UIValue {
+                                            name: "blink_on".to_string(),
+                                            display_name: "Blink On Time".to_string(),
+                                            description: "Time in milliseconds during which blinking text is shown, blinking is disabled if both times are 0".to_string(),
+                                            ty: UIFieldType::InputFieldUnsignedInteger,
+                                            value: UIFieldValue::InputFieldUnsignedInteger(text.blink.map_or(0, |x| x.on_interval as u32))
+                                        });
+
+                                        values.push(UIValue {
+                                            name: "blink_off".to_string(),
+                                            display_name: "Blink Off Time".to_string(),
+                                            description: "Time in milliseconds during which blinking text is hidden, blinking is disabled if both times are 0".to_string(),
+                                            ty: UIFieldType::InputFieldUnsignedInteger,
+                                            value: UIFieldValue::InputFieldUnsignedInteger(text.blink.map_or(0, |x| x.off_interval as u32))
+                                        });
+
+                                        let (truncate, truncate_limit, ellipsis) = match &text.truncate {
+                                            Some(TruncateMode::Chars { max, ellipsis }) => ("Characters", *max as u32, ellipsis.clone()),
+                                            Some(TruncateMode::Width { max, ellipsis }) => ("Width", *max, ellipsis.clone()),
+                                            None => ("None", 0, "...".to_string())
+                                        };
+
+                                        values.push(UIValue {
+                                            name: "truncate".to_string(),
+                                            display_name: "Truncate".to_string(),
+                                            description: "How text that is too long should be shortened".to_string(),
+                                            ty: UIFieldType::Choice(TRUNCATE_CHOICES.iter().map(|x| x.to_string()).collect()),
+                                            value: UIFieldValue::Choice(truncate.to_string())
+                                        });
+
+                                        values.push(UIValue {
+                                            name: "truncate_limit".to_string(),
+                                            display_name: "Truncate Limit".to_string(),
+                                            description: "Max amount of characters or max width in pixels of the text, 0 width means button width".to_string(),
+                                            ty: UIFieldType::InputFieldUnsignedInteger,
+                                            value: UIFieldValue::InputFieldUnsignedInteger(truncate_limit)
+                                        });
+
+                                        values.push(UIValue {
+                                            name: "ellipsis".to_string(),
+                                            display_name: "Ellipsis".to_string(),
+                                            description: "Text that will be added to the end of shortened text".to_string(),
+                                            ty: UIFieldType::InputFieldString,
+                                            value: UIFieldValue::InputFieldString(ellipsis)
                                         });
 
                                         if let Some(shadow) = &text.shadow {
@@ -354,9 +645,9 @@ pub async fn get_renderer_component_values(core: &CoreHandle, button: &Button) -
                     description: "Disabled plugins will not appear on button".to_string(),
                     ty: UIFieldType::Collapsable,
                     value: UIFieldValue::Collapsable({
-                        let names = core.module_manager().get_modules_for_rendering(&button.component_names()).await;
+                        let names = core.module_manager().get_modules_for_rendering(&button.enabled_component_names()).await;
 
-                        names.into_values()
+                        names.into_iter()
                             .map(|x| {
                                 let name = x.name();
 
@@ -383,6 +674,55 @@ pub async fn get_renderer_component_values(core: &CoreHandle, button: &Button) -
                     value: UIFieldValue::Checkbox(component.to_cache)
                 }
             );
+
+            fields.push(
+                UIValue {
+                    name: "opacity".to_string(),
+                    display_name: "Opacity".to_string(),
+                    description: "Opacity of the whole button, lower values make the button appear faded".to_string(),
+                    ty: UIFieldType::ValueSliderFloat(UIScalar {
+                        max_value: 1.0,
+                        min_value: 0.0,
+                        step: 0.01,
+                        allow_out_of_bounds: false
+                    }),
+                    value: UIFieldValue::ValueSliderFloat(component.opacity)
+                }
+            );
+
+            fields.push(
+                UIValue {
+                    name: "autocrop".to_string(),
+                    display_name: "Autocrop".to_string(),
+                    description: "If fully transparent borders of image backgrounds should be trimmed before scaling".to_string(),
+                    ty: UIFieldType::Checkbox {
+                        disabled: false
+                    },
+                    value: UIFieldValue::Checkbox(component.autocrop)
+                }
+            );
+
+            fields.push(
+                UIValue {
+                    name: "image_offset".to_string(),
+                    display_name: "Image Offset".to_string(),
+                    description: "Offset in pixels of image backgrounds within the button".to_string(),
+                    ty: UIFieldType::InputFieldInteger2,
+                    value: UIFieldValue::InputFieldInteger2(component.image_offset.0, component.image_offset.1)
+                }
+            );
+
+            if !component.states.is_empty() {
+                fields.push(
+                    UIValue {
+                        name: "state_index".to_string(),
+                        display_name: "State".to_string(),
+                        description: format!("Index of the state that should be drawn, button has {} states", component.states.len()),
+                        ty: UIFieldType::InputFieldUnsignedInteger,
+                        value: UIFieldValue::InputFieldUnsignedInteger(component.state_index as u32)
+                    }
+                );
+            }
         }
 
         fields
@@ -427,6 +767,9 @@ pub async fn set_renderer_component_values(core: &CoreHandle, button: &mut Butto
                                 "Vertical Gradient" => component.background = ButtonBackground::VerticalGradient((0, 0, 0, 255), (0, 0, 0, 255)),
                                 "Existing Image" => component.background = ButtonBackground::ExistingImage("".to_string()),
                                 "New Image" => component.background = ButtonBackground::NewImage("".to_string()),
+                                "Image Stack" => if !matches!(component.background, ButtonBackground::ImageStack(_)) {
+                                    component.background = ButtonBackground::ImageStack(vec![])
+                                },
 
                                 _ => {}
                             }
@@ -496,6 +839,24 @@ pub async fn set_renderer_component_values(core: &CoreHandle, button: &mut Butto
                             _ => {}
                         }
                     }
+
+                    if let Some(value) = change_map.get("layers") {
+                        if let (ButtonBackground::ImageStack(_), UIFieldValue::Array(items)) = (&component.background, &value.value) {
+                            fn get_image_layer(item: &Vec<UIValue>) -> Option<ImageLayer> {
+                                let map = map_ui_values_ref(item);
+
+                                Some(ImageLayer {
+                                    image: (&map.get("image")?.value).try_into().ok()?,
+                                    position: (&map.get("position")?.value).try_into().ok()?,
+                                    scale: map.get("scale")?.value.try_into_f32().ok()?,
+                                    opacity: map.get("opacity")?.value.try_into_f32().ok()?,
+                                    autocrop: map.get("autocrop").and_then(|x| x.value.try_into_bool().ok()).unwrap_or(false)
+                                })
+                            }
+
+                            component.background = ButtonBackground::ImageStack(items.iter().filter_map(get_image_layer).collect());
+                        }
+                    }
                 }
             }
 
@@ -517,7 +878,6 @@ pub async fn set_renderer_component_values(core: &CoreHandle, button: &mut Butto
                                     alignment: TextAlignment::from_str(&map.get("alignment")?.value.try_into_string().ok()?).ok()?,
                                     padding: (&map.get("padding")?.value).try_into().ok()?,
                                     offset: (&map.get("offset")?.value).try_into_f32_f32().ok()?,
-                                    color: (&map.get("color")?.value).try_into().ok()?,
                                     shadow: if let Some(bool) = map.get("shadow_enabled")?.value.try_into_bool().ok() {
                                         let get_shadow = || {
                                             Some(ButtonTextShadow {
@@ -536,6 +896,59 @@ pub async fn set_renderer_component_values(core: &CoreHandle, button: &mut Butto
                                         }
                                     } else {
                                         None
+                                    },
+                                    auto_fit: map.get("auto_fit").and_then(|x| x.value.try_into_bool().ok()).unwrap_or(false),
+                                    max_width: map.get("max_width").and_then(|x| (&x.value).try_into().ok()).unwrap_or(0),
+                                    max_height: map.get("max_height").and_then(|x| (&x.value).try_into().ok()).unwrap_or(0),
+                                    letter_spacing: map.get("letter_spacing").and_then(|x| x.value.try_into_f32().ok()).unwrap_or(0.0),
+                                    blink: {
+                                        let on_interval: u32 = map.get("blink_on").and_then(|x| (&x.value).try_into().ok()).unwrap_or(0);
+                                        let off_interval: u32 = map.get("blink_off").and_then(|x| (&x.value).try_into().ok()).unwrap_or(0);
+
+                                        if on_interval == 0 && off_interval == 0 {
+                                            None
+                                        } else {
+                                            Some(BlinkConfig {
+                                                on_interval: on_interval as u64,
+                                                off_interval: off_interval as u64
+                                            })
+                                        }
+                                    },
+                                    truncate: {
+                                        let limit: u32 = map.get("truncate_limit").and_then(|x| (&x.value).try_into().ok()).unwrap_or(0);
+                                        let ellipsis = map.get("ellipsis").and_then(|x| x.value.try_into_string().ok()).unwrap_or_else(|| "...".to_string());
+
+                                        match map.get("truncate").and_then(|x| x.value.try_into_string().ok()).as_deref() {
+                                            Some("Characters") => Some(TruncateMode::Chars {
+                                                max: limit as usize,
+                                                ellipsis
+                                            }),
+                                            Some("Width") => Some(TruncateMode::Width {
+                                                max: limit,
+                                                ellipsis
+                                            }),
+                                            _ => None
+                                        }
+                                    },
+                                    fill: {
+                                        let start = (&map.get("color")?.value).try_into().ok()?;
+                                        let end = map.get("fill_end").and_then(|x| (&x.value).try_into().ok()).unwrap_or((0, 0, 0, 255));
+
+                                        let direction = match map.get("fill").and_then(|x| x.value.try_into_string().ok()).as_deref() {
+                                            Some("Horizontal Gradient") => Some(GradientDirection::Horizontal),
+                                            Some("Vertical Gradient") => Some(GradientDirection::Vertical),
+                                            Some("Diagonal Gradient") => Some(GradientDirection::Diagonal),
+                                            _ => None
+                                        };
+
+                                        match direction {
+                                            Some(direction) => TextFill::Gradient {
+                                                start,
+                                                end,
+                                                direction
+                                            },
+                                            None => TextFill::Solid(start)
+                                        }
                                     }
                                 })
                             }
@@ -571,6 +984,30 @@ pub async fn set_renderer_component_values(core: &CoreHandle, button: &mut Butto
                     component.to_cache = value;
                 }
             }
+
+            if let Some(value) = change_map.get("opacity") {
+                if let Ok(value) = value.value.try_into_f32() {
+                    component.opacity = value.clamp(0.0, 1.0);
+                }
+            }
+
+            if let Some(value) = change_map.get("autocrop") {
+                if let Ok(value) = value.value.try_into_bool() {
+                    component.autocrop = value;
+                }
+            }
+
+            if let Some(value) = change_map.get("image_offset") {
+                if let Ok(value) = (&value.value).try_into() {
+                    component.image_offset = value;
+                }
+            }
+
+            if let Some(value) = change_map.get("state_index") {
+                if let Ok(value) = value.value.try_into_u32() {
+                    component.state_index = value as usize;
+                }
+            }
         }
 
         // Apply changes to button
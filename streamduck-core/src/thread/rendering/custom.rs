@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use image::DynamicImage;
 use streamdeck::{DeviceImage, StreamDeck};
 use tokio::sync::{RwLock, RwLockReadGuard};
@@ -38,7 +39,12 @@ pub trait CustomRenderer: Send + Sync {
     /// Name of the renderer
     fn name(&self) -> String;
 
-    /// Called whenever current screen changes, should be used for any things that shouldn't be called every tick
+    /// Interval at which [refresh](CustomRenderer::refresh) is additionally called while buttons using the renderer are on current screen,
+    /// None means renderer is only refreshed on screen changes
+    fn refresh_interval(&self) -> Option<Duration> { None }
+
+    /// Called whenever current screen changes, and periodically if [refresh_interval](CustomRenderer::refresh_interval) is set.
+    /// Should be used for any things that shouldn't be called every tick
     async fn refresh(&self, core_handle: &CoreHandle) {}
 
     /// Called on every tick with device reference provided
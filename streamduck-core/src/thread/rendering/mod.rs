@@ -5,26 +5,31 @@ pub mod custom;
 /// Renderer's component values
 pub mod component_values;
 
+use std::future::Future;
 use std::hash::{Hash, Hasher};
+use std::panic::AssertUnwindSafe;
+use futures::FutureExt;
+use lazy_static::lazy_static;
 use image::{DynamicImage, Rgba, RgbaImage};
-use rusttype::Scale;
+use rusttype::{point, Font, Scale};
 use image::imageops::{FilterType, tile};
 use streamdeck::{DeviceImage, StreamDeck};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::collections::hash_map::DefaultHasher;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::ops::Deref;
-use serde::{Serialize, Deserialize};
+use serde::{Serialize, Deserialize, Deserializer};
 use serde_json::Value;
 use crate::core::button::Component;
 use crate::core::{CoreHandle, UniqueButton};
-use crate::font::get_font_from_collection;
-use crate::images::{AnimationFrame, convert_image, SDImage};
+use crate::font::{get_font_from_collection, get_font_or_default};
+use crate::images::{AnimationFrame, convert_image_with_quality, DEFAULT_JPEG_QUALITY, encode_image_with_quality, SDImage};
 use crate::modules::UniqueSDModule;
+use crate::modules::core_module::CoreSettings;
 use crate::thread::rendering::custom::DeviceReference;
-use crate::thread::util::{image_from_horiz_gradient, image_from_solid, image_from_vert_gradient, render_aligned_shadowed_text_on_image, render_aligned_text_on_image, TextAlignment};
-use crate::util::hash_value;
+use crate::thread::util::{apply_color_mode_on_image, autocrop_image, apply_opacity_on_image, calculate_bounds_for_glyphs, calculate_bounds_for_text, fit_scale_to_bounds, image_from_horiz_gradient, image_from_solid, image_from_vert_gradient, overlay_image, render_aligned_filled_text_on_image, render_aligned_filled_text_with_fallback_on_image, render_aligned_shadowed_text_on_image, render_aligned_shadowed_text_with_fallback_on_image, render_aligned_text_on_image, render_aligned_text_with_fallback_on_image, layout_text_with_fallback, upscale_image, downsample_image, truncate_text_to_chars, truncate_text_to_width, ColorMode, TextAlignment};
+use crate::util::{hash_str, hash_value};
 
 /// Animation counter that counts frames for animated images
 pub struct AnimationCounter {
@@ -80,20 +85,144 @@ impl AnimationCounter {
     }
 }
 
+/// Statistics of device thread's rendering
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct RenderStats {
+    /// Amount of frames processed in last second
+    pub frames_per_second: f32,
+    /// Average time it took to process a frame in last second, in milliseconds
+    pub average_frame_time: f32,
+    /// Amount of times a button was drawn from cache
+    pub cache_hits: u64,
+    /// Amount of times a cacheable button had to be rendered
+    pub cache_misses: u64,
+    /// Amount of times a button was rendered
+    pub buttons_rendered: u64,
+    /// Amount of images currently in the cache
+    pub cached_images: usize,
+}
+
+/// Cache of rendered button images by their render hash
+pub type RenderCache = HashMap<u64, CachedImage>;
+
+/// Rendered button image in render cache
+pub struct CachedImage {
+    /// Image encoded for the device
+    pub image: Arc<DeviceImage>,
+    /// Frame after which the image gets removed from the cache if it wasn't used
+    pub expires_at: u64,
+    /// Size of encoded image in bytes
+    pub size: usize,
+    /// Image before encoding, only kept if renderer retains last rendered images
+    pub rendered: Option<Arc<DynamicImage>>,
+}
+
+/// Entry of render cache, without the image itself
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RenderCacheEntry {
+    /// Render hash of the image
+    pub hash: u64,
+    /// Size of encoded image in bytes
+    pub size: usize,
+    /// Frame after which the image gets removed from the cache if it wasn't used
+    pub expires_at: u64,
+    /// Keys that currently display the image
+    pub keys: Vec<u8>,
+}
+
+/// Contents of device thread's render cache, used for debugging render hashes
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct RenderCacheDump {
+    /// Current frame of the device thread
+    pub frame: u64,
+    /// Images in the cache
+    pub entries: Vec<RenderCacheEntry>,
+    /// Render hashes of images that are currently shown on keys, 0 means the key is cleared
+    pub key_hashes: HashMap<u8, u64>,
+}
+
+impl RenderCacheDump {
+    /// Creates dump out of render cache and render hashes of keys
+    pub fn new(cache: &RenderCache, key_hashes: &HashMap<u8, u64>, frame: u64) -> RenderCacheDump {
+        let mut entries: Vec<RenderCacheEntry> = cache.iter()
+            .map(|(hash, entry)| {
+                let mut keys: Vec<u8> = key_hashes.iter()
+                    .filter(|(_, key_hash)| *key_hash == hash)
+                    .map(|(key, _)| *key)
+                    .collect();
+                keys.sort();
+
+                RenderCacheEntry {
+                    hash: *hash,
+                    size: entry.size,
+                    expires_at: entry.expires_at,
+                    keys
+                }
+            })
+            .collect();
+        entries.sort_by_key(|entry| entry.hash);
+
+        RenderCacheDump {
+            frame,
+            entries,
+            key_hashes: key_hashes.clone()
+        }
+    }
+}
+
 /// Rendering code that's being called every loop
 pub async fn process_frame(
     core: &CoreHandle,
     streamdeck: &mut StreamDeck,
-    cache: &mut HashMap<u64, (Arc<DeviceImage>, u64)>,
+    cache: &mut RenderCache,
     counters: &mut HashMap<String, AnimationCounter>,
     renderer_map: &mut HashMap<u8, (RendererComponent, UniqueButton, Vec<UniqueSDModule>)>,
     previous_state: &mut HashMap<u8, u64>,
+    last_good_images: &mut HashMap<u8, Arc<DeviceImage>>,
+    missing_keys: &HashSet<u8>,
+    temporary_images: &HashMap<u8, Instant>,
     missing: &DynamicImage,
-    time: u64
+    time: u64,
+    stats: &mut RenderStats,
+    skip_cache: bool,
+    settings: &RendererSettings
 ) {
 
+    let pressed_keys = core.core.pressed_keys().await;
+    let retain = settings.retain_rendered_images;
+    let mut rendered: Vec<(u8, Option<Arc<DynamicImage>>)> = vec![];
+
     for key in 0..core.core.key_count {
+        // Buttons showing temporary images are left alone until the image expires
+        if temporary_images.contains_key(&key) {
+            continue;
+        }
+
+        if missing_keys.contains(&key) {
+            // Placeholder only needs to be written once
+            if *previous_state.get(&key).unwrap_or(&1) != 2 {
+                previous_state.insert(key, 2);
+                streamdeck.write_button_image(key, &to_device_image(core, missing.clone(), settings)).ok();
+
+                if retain {
+                    rendered.push((key, Some(Arc::new(missing.clone()))));
+                }
+            }
+
+            continue;
+        }
+
         if let Some((component, button, modules)) = renderer_map.get(&key) {
+            let pressed_component = if pressed_keys.contains(&key) {
+                component.pressed_variant()
+            } else {
+                None
+            };
+            let component = pressed_component.as_ref().unwrap_or(component);
+            let blink_component = component.blink_variant(blink_clock());
+            let component = blink_component.as_ref().unwrap_or(component);
+            let to_cache = component.to_cache && !skip_cache;
+
             if !component.renderer.is_empty() {
                 // Custom renderer detected
                 let lock = core.core.render_manager.read_renderers().await;
@@ -102,12 +231,13 @@ pub async fn process_frame(
                     // Stopping any further process if custom renderer is found
                     renderer.render(key, button, core, &mut DeviceReference::new(streamdeck, key)).await;
                     previous_state.insert(key, 1);
+                    rendered.push((key, None));
                     continue;
                 }
             }
 
 
-            if let ButtonBackground::ExistingImage(identifier) = &component.background {
+            if let ButtonBackground::ExistingImage(identifier) = component.active_background() {
                 let counter = if let Some(counter) = counters.get_mut(identifier) {
                     Some(counter)
                 } else {
@@ -127,6 +257,13 @@ pub async fn process_frame(
 
                     component.hash(&mut hasher);
                     frame.index.hash(&mut hasher);
+                    settings.color_mode.hash(&mut hasher);
+                    settings.jpeg_quality.hash(&mut hasher);
+                    settings.text_supersampling.hash(&mut hasher);
+                    settings.default_font.hash(&mut hasher);
+                    settings.font_fallbacks.hash(&mut hasher);
+                    ((settings.default_scale.0 * 100.0) as i32).hash(&mut hasher);
+                    ((settings.default_scale.1 * 100.0) as i32).hash(&mut hasher);
 
                     for module in modules {
                         module.render_hash(core.clone_for(module), &button, &mut hasher);
@@ -135,27 +272,48 @@ pub async fn process_frame(
                     let hash = hasher.finish();
 
                     if counter.new_frame || (hash != *previous_state.get(&key).unwrap_or(&1)) {
-                        let variant = cache.get_mut(&hash);
+                        let variant = cache.get_mut(&hash)
+                            .filter(|x| !retain || x.rendered.is_some());
 
-                        if component.to_cache && variant.is_some() {
-                            let (variant, time_to_die) = variant.unwrap();
-                            *time_to_die = time + 20000;
+                        if to_cache && variant.is_some() {
+                            let variant = variant.unwrap();
+                            variant.expires_at = time + 20000;
 
                             let previous = previous_state.get(&key).unwrap_or(&1);
                             if hash != *previous {
-                                streamdeck.write_button_image(key, variant.deref()).ok();
+                                stats.cache_hits += 1;
+                                streamdeck.write_button_image(key, variant.image.deref()).ok();
+                                rendered.push((key, variant.rendered.clone()));
+                                last_good_images.insert(key, variant.image.clone());
                             }
 
                         } else {
-                            let device_image = convert_image(&core.core.kind, draw_foreground(&component, &button, modules,frame.image.clone(), core).await);
-
-                            let arc = Arc::new(device_image);
-
-                            if component.to_cache {
-                                cache.insert(hash, (arc.clone(), time + 20000));
+                            let image = render_button_safely(key, async {
+                                let frame_image = if component.autocrop {
+                                    fill_image(&frame.image, core.core.image_size, true)
+                                } else {
+                                    frame.image.clone()
+                                };
+                                let frame_image = offset_image(frame_image, component.image_offset);
+
+                                draw_foreground(&component, &button, modules, frame_image, core).await
+                            }).await;
+
+                            if let Some(image) = image {
+                                let entry = prepare_image(core, image, settings, time);
+                                stats.buttons_rendered += 1;
+
+                                streamdeck.write_button_image(key, entry.image.deref()).ok();
+                                rendered.push((key, entry.rendered.clone()));
+                                last_good_images.insert(key, entry.image.clone());
+
+                                if to_cache {
+                                    stats.cache_misses += 1;
+                                    cache.insert(hash, entry);
+                                }
+                            } else {
+                                write_fallback_image(core, streamdeck, key, last_good_images, missing, settings);
                             }
-
-                            streamdeck.write_button_image(key, arc.deref()).ok();
                         }
 
                         previous_state.insert(key, hash);
@@ -167,35 +325,60 @@ pub async fn process_frame(
             }
 
             // If not animated, continuing with normal process of rendering a button
+            let loading = is_background_loading(component, core).await;
+            let to_cache = to_cache && !loading;
+
             let mut hasher: Box<dyn Hasher> = Box::new(DefaultHasher::new());
 
             component.hash(&mut hasher);
+            loading.hash(&mut hasher);
+            settings.color_mode.hash(&mut hasher);
+            settings.jpeg_quality.hash(&mut hasher);
+            settings.text_supersampling.hash(&mut hasher);
+            settings.default_font.hash(&mut hasher);
+            settings.font_fallbacks.hash(&mut hasher);
+            ((settings.default_scale.0 * 100.0) as i32).hash(&mut hasher);
+            ((settings.default_scale.1 * 100.0) as i32).hash(&mut hasher);
             for module in modules {
                 module.render_hash(core.clone_for(module), &button, &mut hasher);
             }
 
             let hash = hasher.finish();
 
-            let variant = cache.get_mut(&hash);
+            let variant = cache.get_mut(&hash)
+                .filter(|x| !retain || x.rendered.is_some());
 
-            if component.to_cache && variant.is_some() {
-                let (variant, time_to_die) = variant.unwrap();
-                *time_to_die = time + 20000;
+            if to_cache && variant.is_some() {
+                let variant = variant.unwrap();
+                variant.expires_at = time + 20000;
 
                 let previous = previous_state.get(&key).unwrap_or(&1);
                 if hash != *previous {
-                    streamdeck.write_button_image(key, variant.deref()).ok();
+                    stats.cache_hits += 1;
+                    streamdeck.write_button_image(key, variant.image.deref()).ok();
+                    rendered.push((key, variant.rendered.clone()));
+                    last_good_images.insert(key, variant.image.clone());
                 }
             } else {
-                let device_image = convert_image(&core.core.kind, draw_foreground(&component, &button, modules, draw_background(component, core, missing).await, core).await);
+                let image = render_button_safely(key, async {
+                    draw_foreground(&component, &button, modules, draw_device_background(component, core, missing).await, core).await
+                }).await;
 
-                let arc = Arc::new(device_image);
+                if let Some(image) = image {
+                    let entry = prepare_image(core, image, settings, time);
+                    stats.buttons_rendered += 1;
 
-                if component.to_cache {
-                    cache.insert(hash, (arc.clone(), time + 20000));
-                }
+                    streamdeck.write_button_image(key, entry.image.deref()).ok();
+                    rendered.push((key, entry.rendered.clone()));
+                    last_good_images.insert(key, entry.image.clone());
 
-                streamdeck.write_button_image(key, arc.deref()).ok();
+                    if to_cache {
+                        stats.cache_misses += 1;
+                        cache.insert(hash, entry);
+                    }
+                } else {
+                    write_fallback_image(core, streamdeck, key, last_good_images, missing, settings);
+                }
             }
 
             previous_state.insert(key, hash);
@@ -209,8 +392,26 @@ pub async fn process_frame(
                     g: 0,
                     b: 0
                 }).ok();
+                rendered.push((key, None));
+            }
+        }
+    }
+
+    // Keeping last rendered images, so current state of the device can be retrieved without rendering again
+    if retain {
+        if !rendered.is_empty() {
+            let mut last_rendered = core.core.last_rendered.write().await;
+
+            for (key, image) in rendered {
+                if let Some(image) = image {
+                    last_rendered.insert(key, image);
+                } else {
+                    last_rendered.remove(&key);
+                }
             }
         }
+    } else if !core.core.last_rendered.read().await.is_empty() {
+        core.core.last_rendered.write().await.clear();
     }
 
     for (_, counter) in counters {
@@ -219,9 +420,60 @@ pub async fn process_frame(
     };
 }
 
-/// Draws background for static images
+/// Runs rendering of a button, catching any panic that happens while drawing it
+async fn render_button_safely<F: Future<Output=DynamicImage>>(key: u8, future: F) -> Option<DynamicImage> {
+    match AssertUnwindSafe(future).catch_unwind().await {
+        Ok(image) => Some(image),
+        Err(_) => {
+            log::error!("Rendering of button {} panicked, keeping previous image", key);
+            None
+        }
+    }
+}
+
+/// Writes last successfully rendered image of the key to the device, or missing texture if key wasn't rendered before
+fn write_fallback_image(core: &CoreHandle, streamdeck: &mut StreamDeck, key: u8, last_good_images: &HashMap<u8, Arc<DeviceImage>>, missing: &DynamicImage, settings: &RendererSettings) {
+    if let Some(image) = last_good_images.get(&key) {
+        streamdeck.write_button_image(key, image.deref()).ok();
+    } else {
+        streamdeck.write_button_image(key, &to_device_image(core, missing.clone(), settings)).ok();
+    }
+}
+
+/// Draws background for static images, new images are always decoded right away
 pub async fn draw_background(renderer: &RendererComponent, core: &CoreHandle, missing: &DynamicImage) -> DynamicImage {
-    match &renderer.background {
+    draw_background_with_loader(renderer, core, missing, false).await
+}
+
+/// Draws background on device thread, leaving decoding of new images to image loader if it's enabled
+async fn draw_device_background(renderer: &RendererComponent, core: &CoreHandle, missing: &DynamicImage) -> DynamicImage {
+    draw_background_with_loader(renderer, core, missing, true).await
+}
+
+async fn draw_background_with_loader(renderer: &RendererComponent, core: &CoreHandle, missing: &DynamicImage, use_loader: bool) -> DynamicImage {
+    let background = renderer.active_background();
+    let image = draw_background_image(renderer, background, core, missing, use_loader).await;
+
+    match background {
+        ButtonBackground::ExistingImage(_) | ButtonBackground::NewImage(_) | ButtonBackground::ImageStack(_) => offset_image(image, renderer.image_offset),
+        _ => image
+    }
+}
+
+/// Moves image by offset within its own bounds, uncovered area is filled with black
+fn offset_image(image: DynamicImage, offset: (i32, i32)) -> DynamicImage {
+    if offset == (0, 0) {
+        return image;
+    }
+
+    let mut moved = image_from_solid((image.width() as usize, image.height() as usize), Rgba([0, 0, 0, 255]));
+    overlay_image(&mut moved, &image, offset, 1.0);
+
+    moved
+}
+
+async fn draw_background_image(renderer: &RendererComponent, background: &ButtonBackground, core: &CoreHandle, missing: &DynamicImage, use_loader: bool) -> DynamicImage {
+    match background {
         ButtonBackground::Solid(color) => {
             image_from_solid(core.core.image_size, Rgba([color.0, color.1, color.2, 255]))
         }
@@ -238,11 +490,11 @@ pub async fn draw_background(renderer: &RendererComponent, core: &CoreHandle, mi
             if let Some(image) = core.core.image_collection.read().await.get(identifier) {
                 match image {
                     SDImage::SingleImage(image) => {
-                        image.resize_to_fill(core.core.image_size.0 as u32, core.core.image_size.1 as u32, FilterType::Triangle)
+                        fill_image(image, core.core.image_size, renderer.autocrop)
                     }
 
                     SDImage::AnimatedImage(frames) => {
-                        frames[0].image.clone().resize_to_fill(core.core.image_size.0 as u32, core.core.image_size.1 as u32, FilterType::Triangle)
+                        fill_image(&frames[0].image, core.core.image_size, renderer.autocrop)
                     }
                 }
             } else {
@@ -251,12 +503,83 @@ pub async fn draw_background(renderer: &RendererComponent, core: &CoreHandle, mi
         }
 
         ButtonBackground::NewImage(blob) => {
+            let identifier = hash_str(blob);
+
+            // Image collection is keyed by hash of image contents, so same image might be already decoded
+            if let Some(image) = core.core.image_collection.read().await.get(&identifier) {
+                return fill_image(&image.get_image(), core.core.image_size, renderer.autocrop);
+            }
+
+            if let Some(image) = core.core.image_loader.get_decoded(&identifier) {
+                return fill_image(&image.get_image(), core.core.image_size, renderer.autocrop);
+            }
+
+            // Leaving decoding to image loader, button will be redrawn once image is decoded
+            if use_loader && core.core.image_loader.is_enabled() {
+                return if core.core.image_loader.has_failed(&identifier) {
+                    missing.clone()
+                } else {
+                    core.core.image_loader.load_base64(core.core.clone(), blob);
+                    image_from_solid(core.core.image_size, Rgba([0, 0, 0, 255]))
+                };
+            }
+
             if let Ok(image) = SDImage::from_base64(blob, core.core.image_size).await {
-                image.get_image()
+                if renderer.autocrop {
+                    fill_image(&image.get_image(), core.core.image_size, true)
+                } else {
+                    image.get_image()
+                }
             } else {
                 missing.clone()
             }
         }
+
+        ButtonBackground::ImageStack(layers) => {
+            let (width, height) = core.core.image_size;
+            let mut background = image_from_solid(core.core.image_size, Rgba([0, 0, 0, 255]));
+
+            let collection = core.core.image_collection.read().await;
+
+            for layer in layers {
+                let layer_width = ((width as f32 * layer.scale) as u32).max(1);
+                let layer_height = ((height as f32 * layer.scale) as u32).max(1);
+
+                let image = if let Some(image) = collection.get(&layer.image) {
+                    image.get_image()
+                } else {
+                    missing.clone()
+                };
+
+                overlay_image(&mut background, &fill_image(&image, (layer_width as usize, layer_height as usize), layer.autocrop), layer.position, layer.opacity);
+            }
+
+            background
+        }
+    }
+}
+
+/// Checks if background image of the component is still being decoded by image loader
+async fn is_background_loading(renderer: &RendererComponent, core: &CoreHandle) -> bool {
+    if let ButtonBackground::NewImage(blob) = renderer.active_background() {
+        if core.core.image_loader.is_enabled() {
+            let identifier = hash_str(blob);
+
+            return !core.core.image_collection.read().await.contains_key(&identifier)
+                && !core.core.image_loader.is_decoded(&identifier)
+                && !core.core.image_loader.has_failed(&identifier);
+        }
+    }
+
+    false
+}
+
+/// Resizes image to fill provided size, trimming transparent borders first if autocrop is enabled
+fn fill_image(image: &DynamicImage, size: (usize, usize), autocrop: bool) -> DynamicImage {
+    if autocrop {
+        autocrop_image(image).resize_to_fill(size.0 as u32, size.1 as u32, FilterType::Triangle)
+    } else {
+        image.resize_to_fill(size.0 as u32, size.1 as u32, FilterType::Triangle)
     }
 }
 
@@ -267,49 +590,193 @@ pub async fn draw_foreground(renderer: &RendererComponent, button: &UniqueButton
         module.render(core.clone_for(module), button, &mut background).await;
     }
 
+    let renderer_settings = if renderer.active_text().is_empty() {
+        RendererSettings::default()
+    } else {
+        let core_settings: CoreSettings = core.core.config.get_plugin_settings().await.unwrap_or_default();
+        core_settings.renderer
+    };
 
-    for button_text in &renderer.text {
-        let text = button_text.text.as_str();
-        let scale = Scale { x: button_text.scale.0, y: button_text.scale.1 };
+    // Text is drawn on enlarged image and then scaled back down for smoother edges
+    let supersampling = renderer_settings.text_supersampling.clamp(1, MAX_TEXT_SUPERSAMPLING);
+    let factor = supersampling as f32;
+    let text_size = (core.core.image_size.0 * supersampling as usize, core.core.image_size.1 * supersampling as usize);
+    let supersample_scale = |scale: Scale| Scale { x: scale.x * factor, y: scale.y * factor };
+
+    let mut canvas = if supersampling > 1 {
+        upscale_image(&background, supersampling)
+    } else {
+        background
+    };
+
+    let clock = blink_clock();
+
+    for button_text in renderer.active_text() {
+        if let Some(blink) = &button_text.blink {
+            if !blink.is_visible_at(clock) {
+                continue;
+            }
+        }
+
+        let (scale_x, scale_y) = if button_text.scale == (0.0, 0.0) { renderer_settings.default_scale } else { button_text.scale };
+        let mut scale = Scale { x: scale_x, y: scale_y };
         let align = button_text.alignment.clone();
-        let padding = button_text.padding;
-        let offset = button_text.offset.clone();
-        let color = button_text.color.clone();
-
-        if let Some(font) = get_font_from_collection(&button_text.font) {
-            if let Some(shadow) = &button_text.shadow {
-                render_aligned_shadowed_text_on_image(
-                    core.core.image_size,
-                    &mut background,
-                    font.as_ref(),
-                    text,
+        let padding = button_text.padding * supersampling;
+        let offset = (button_text.offset.0 * factor, button_text.offset.1 * factor);
+        let color = match &button_text.fill {
+            TextFill::Solid(color) => *color,
+            TextFill::Gradient { start, .. } => *start
+        };
+        let shadow = button_text.shadow.as_ref()
+            .map(|shadow| ((shadow.offset.0 * supersampling as i32, shadow.offset.1 * supersampling as i32), shadow.color));
+        let letter_spacing = button_text.letter_spacing;
+
+        if let Some(fonts) = get_text_fonts(&button_text.font, &renderer_settings) {
+            let text = button_text.truncated_text(core.core.image_size, |text| measure_text(&fonts, text, scale, letter_spacing, &renderer_settings).0);
+            let text = text.as_str();
+
+            if button_text.auto_fit {
+                scale = fit_scale_to_bounds(
+                    |scale| measure_text(&fonts, text, scale, letter_spacing, &renderer_settings),
                     scale,
-                    align,
-                    padding,
-                    offset,
-                    color,
-                    shadow.offset.clone(),
-                    shadow.color.clone(),
-                )
+                    button_text.fit_bounds(core.core.image_size)
+                );
+            }
+
+            // Glyphs are laid out manually if there's fallback fonts or letter spacing
+            if uses_manual_layout(letter_spacing, &renderer_settings) {
+                if let fill @ TextFill::Gradient { .. } = &button_text.fill {
+                    render_aligned_filled_text_with_fallback_on_image(
+                        text_size,
+                        &mut canvas,
+                        &fonts,
+                        text,
+                        supersample_scale(scale),
+                        letter_spacing * factor,
+                        align,
+                        padding,
+                        offset,
+                        fill,
+                        shadow,
+                    )
+                } else if let Some(shadow) = &button_text.shadow {
+                    render_aligned_shadowed_text_with_fallback_on_image(
+                        text_size,
+                        &mut canvas,
+                        &fonts,
+                        text,
+                        supersample_scale(scale),
+                        letter_spacing * factor,
+                        align,
+                        padding,
+                        offset,
+                        color,
+                        (shadow.offset.0 * supersampling as i32, shadow.offset.1 * supersampling as i32),
+                        shadow.color.clone(),
+                    )
+                } else {
+                    render_aligned_text_with_fallback_on_image(
+                        text_size,
+                        &mut canvas,
+                        &fonts,
+                        text,
+                        supersample_scale(scale),
+                        letter_spacing * factor,
+                        align,
+                        padding,
+                        offset,
+                        color,
+                    )
+                }
             } else {
-                render_aligned_text_on_image(
-                    core.core.image_size,
-                    &mut background,
-                    font.as_ref(),
-                    text,
-                    scale,
-                    align,
-                    padding,
-                    offset,
-                    color,
-                )
+                let font = &fonts[0];
+
+                if let fill @ TextFill::Gradient { .. } = &button_text.fill {
+                    render_aligned_filled_text_on_image(
+                        text_size,
+                        &mut canvas,
+                        font.as_ref(),
+                        text,
+                        supersample_scale(scale),
+                        align,
+                        padding,
+                        offset,
+                        fill,
+                        shadow,
+                    )
+                } else if let Some(shadow) = &button_text.shadow {
+                    render_aligned_shadowed_text_on_image(
+                        text_size,
+                        &mut canvas,
+                        font.as_ref(),
+                        text,
+                        supersample_scale(scale),
+                        align,
+                        padding,
+                        offset,
+                        color,
+                        (shadow.offset.0 * supersampling as i32, shadow.offset.1 * supersampling as i32),
+                        shadow.color.clone(),
+                    )
+                } else {
+                    render_aligned_text_on_image(
+                        text_size,
+                        &mut canvas,
+                        font.as_ref(),
+                        text,
+                        supersample_scale(scale),
+                        align,
+                        padding,
+                        offset,
+                        color,
+                    )
+                }
             }
         }
     }
 
+    let mut background = if supersampling > 1 {
+        downsample_image(&canvas, supersampling)
+    } else {
+        canvas
+    };
+
+    if renderer.opacity < 1.0 {
+        apply_opacity_on_image(&mut background, renderer.opacity);
+    }
+
     background
 }
 
+/// Gets fonts that text with provided font name is drawn with, font of the text goes first followed by loaded fallback fonts.
+/// Empty font name or font that isn't loaded is replaced with default font of renderer settings
+pub fn get_text_fonts(font_name: &str, settings: &RendererSettings) -> Option<Vec<Arc<Font<'static>>>> {
+    let font_name = if font_name.is_empty() { &settings.default_font } else { font_name };
+
+    let mut fonts = vec![get_font_or_default(font_name, &settings.default_font)?];
+    fonts.extend(settings.font_fallbacks.iter()
+        .filter(|x| *x != font_name)
+        .filter_map(|x| get_font_from_collection(x)));
+
+    Some(fonts)
+}
+
+/// Checks if glyphs of text are laid out manually, which is done if there's fallback fonts or letter spacing
+pub fn uses_manual_layout(letter_spacing: f32, settings: &RendererSettings) -> bool {
+    !settings.font_fallbacks.is_empty() || letter_spacing != 0.0
+}
+
+/// Measures text in pixels the same way renderer lays it out, fonts are expected to be from [get_text_fonts]
+pub fn measure_text(fonts: &[Arc<Font<'static>>], text: &str, scale: Scale, letter_spacing: f32, settings: &RendererSettings) -> (u32, u32) {
+    if uses_manual_layout(letter_spacing, settings) {
+        calculate_bounds_for_glyphs(&layout_text_with_fallback(fonts, text, scale, point(0.0, 0.0), letter_spacing))
+    } else if let Some(font) = fonts.first() {
+        calculate_bounds_for_text(font.as_ref(), text, scale)
+    } else {
+        (0, 0)
+    }
+}
+
 /// Draws missing texture from HL2
 pub fn draw_missing_texture(size: (usize, usize)) -> DynamicImage {
     let mut pattern = RgbaImage::new(16, 16);
@@ -385,22 +852,31 @@ pub fn draw_custom_renderer_texture(size: (usize, usize)) -> DynamicImage {
     frame
 }
 
-/// Definition for color format
+/// Definition for color format, can also be deserialized from hex strings in renderer definitions
 pub type Color = (u8, u8, u8, u8);
 
 /// Button Background definition for button renderer
 #[derive(Serialize, Deserialize, Debug, Clone, Hash)]
 pub enum ButtonBackground {
     /// Solid color background
-    Solid(Color),
+    Solid(#[serde(deserialize_with = "crate::util::color::deserialize")] Color),
     /// Horizontal color gradient
-    HorizontalGradient(Color, Color),
+    HorizontalGradient(
+        #[serde(deserialize_with = "crate::util::color::deserialize")] Color,
+        #[serde(deserialize_with = "crate::util::color::deserialize")] Color
+    ),
     /// Vertical color gradient
-    VerticalGradient(Color, Color),
-    /// Existing image that was already loaded into the image collection
+    VerticalGradient(
+        #[serde(deserialize_with = "crate::util::color::deserialize")] Color,
+        #[serde(deserialize_with = "crate::util::color::deserialize")] Color
+    ),
+    /// Existing image that was already loaded into the image collection, referenced by identifier that adding the image returned.
+    /// Missing texture is drawn if image collection doesn't have the identifier
     ExistingImage(String),
     /// New image as a base64 blob
     NewImage(String),
+    /// Images from image collection composited on top of each other in order
+    ImageStack(Vec<ImageLayer>),
 }
 
 impl Default for ButtonBackground {
@@ -409,6 +885,39 @@ impl Default for ButtonBackground {
     }
 }
 
+/// Layer of [ButtonBackground::ImageStack]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ImageLayer {
+    /// Identifier of an image in the image collection
+    pub image: String,
+    /// Position of top left corner of the layer in pixels
+    #[serde(default)]
+    pub position: (i32, i32),
+    /// Size of the layer relative to button size
+    #[serde(default = "default_layer_value")]
+    pub scale: f32,
+    /// Opacity of the layer
+    #[serde(default = "default_layer_value")]
+    pub opacity: f32,
+    /// If fully transparent borders of the image should be trimmed before scaling
+    #[serde(default)]
+    pub autocrop: bool,
+}
+
+fn default_layer_value() -> f32 {
+    1.0
+}
+
+impl Hash for ImageLayer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.image.hash(state);
+        self.position.hash(state);
+        ((self.scale * 100.0) as i32).hash(state);
+        self.opacity.to_bits().hash(state);
+        self.autocrop.hash(state);
+    }
+}
+
 /// Button Text definition for button renderer
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ButtonText {
@@ -424,10 +933,181 @@ pub struct ButtonText {
     pub padding: u32,
     /// Offset of the text from the alignment point
     pub offset: (f32, f32),
-    /// Color of the text
-    pub color: Color,
+    /// Fill of the text, plain color under `color` key is also accepted as solid fill
+    #[serde(alias = "color", deserialize_with = "deserialize_fill")]
+    pub fill: TextFill,
     /// Text shadow
     pub shadow: Option<ButtonTextShadow>,
+    /// If scale should be reduced until text fits into max width and height
+    #[serde(default)]
+    pub auto_fit: bool,
+    /// Max width of the text in pixels when auto fitting, 0 means button width minus padding
+    #[serde(default)]
+    pub max_width: u32,
+    /// Max height of the text in pixels when auto fitting, 0 means button height minus padding
+    #[serde(default)]
+    pub max_height: u32,
+    /// Extra space in pixels added to advance of each character
+    #[serde(default)]
+    pub letter_spacing: f32,
+    /// If set, text will be periodically hidden and shown
+    #[serde(default)]
+    pub blink: Option<BlinkConfig>,
+    /// If set, text that is too long will be shortened and end with an ellipsis
+    #[serde(default)]
+    pub truncate: Option<TruncateMode>,
+}
+
+fn deserialize_fill<'de, D: Deserializer<'de>>(deserializer: D) -> Result<TextFill, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum FillRepr {
+        Color(#[serde(deserialize_with = "crate::util::color::deserialize")] Color),
+        Fill(TextFill)
+    }
+
+    Ok(match FillRepr::deserialize(deserializer)? {
+        FillRepr::Color(color) => TextFill::Solid(color),
+        FillRepr::Fill(fill) => fill
+    })
+}
+
+/// Fill of button text
+#[derive(Serialize, Deserialize, Debug, Clone, Hash)]
+pub enum TextFill {
+    /// Text filled with single color
+    Solid(#[serde(deserialize_with = "crate::util::color::deserialize")] Color),
+    /// Text filled with gradient that spans bounding box of the text
+    Gradient {
+        /// Color at the start of the gradient
+        #[serde(deserialize_with = "crate::util::color::deserialize")]
+        start: Color,
+        /// Color at the end of the gradient
+        #[serde(deserialize_with = "crate::util::color::deserialize")]
+        end: Color,
+        /// Direction of the gradient
+        direction: GradientDirection,
+    },
+}
+
+impl TextFill {
+    /// Gets color of the fill at provided pixel, bounds are min x, min y, max x and max y of the filled text
+    pub fn color_at(&self, bounds: (i32, i32, i32, i32), x: u32, y: u32) -> Color {
+        match self {
+            TextFill::Solid(color) => *color,
+
+            TextFill::Gradient { start, end, direction } => {
+                let progress = |pos: u32, min: i32, max: i32| if max > min {
+                    ((pos as f32 - min as f32) / (max - min) as f32).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+
+                let t = match direction {
+                    GradientDirection::Horizontal => progress(x, bounds.0, bounds.2),
+                    GradientDirection::Vertical => progress(y, bounds.1, bounds.3),
+                    GradientDirection::Diagonal => (progress(x, bounds.0, bounds.2) + progress(y, bounds.1, bounds.3)) / 2.0,
+                };
+
+                let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+
+                (lerp(start.0, end.0), lerp(start.1, end.1), lerp(start.2, end.2), lerp(start.3, end.3))
+            }
+        }
+    }
+}
+
+/// Direction of [TextFill::Gradient]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Hash, PartialEq)]
+pub enum GradientDirection {
+    /// From left to right
+    Horizontal,
+    /// From top to bottom
+    Vertical,
+    /// From top left to bottom right
+    Diagonal,
+}
+
+/// Blinking configuration of button text
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Hash)]
+pub struct BlinkConfig {
+    /// Time in milliseconds during which text is shown
+    pub on_interval: u64,
+    /// Time in milliseconds during which text is hidden
+    pub off_interval: u64,
+}
+
+impl BlinkConfig {
+    /// Checks if text should be visible at provided point of blink clock
+    pub fn is_visible_at(&self, clock: Duration) -> bool {
+        let period = self.on_interval + self.off_interval;
+
+        if period == 0 {
+            return true;
+        }
+
+        (clock.as_millis() % period as u128) < self.on_interval as u128
+    }
+}
+
+/// How text that is too long should be shortened
+#[derive(Serialize, Deserialize, Debug, Clone, Hash)]
+pub enum TruncateMode {
+    /// Keeps at most provided amount of characters, including the ellipsis
+    Chars {
+        /// Max amount of characters
+        max: usize,
+        /// Text that is added to the end of shortened text
+        ellipsis: String
+    },
+    /// Keeps text narrower than provided amount of pixels, including the ellipsis
+    Width {
+        /// Max width in pixels, 0 means button width minus padding
+        max: u32,
+        /// Text that is added to the end of shortened text
+        ellipsis: String
+    },
+}
+
+/// Monotonic clock that all blinking text derives its phase from, so all blinking buttons stay in sync
+pub fn blink_clock() -> Duration {
+    lazy_static! {
+        static ref START: Instant = Instant::now();
+    }
+
+    START.elapsed()
+}
+
+impl ButtonText {
+    /// Returns max bounds for auto fitting the text on image of provided size
+    pub fn fit_bounds(&self, size: (usize, usize)) -> (u32, u32) {
+        let pick = |max: u32, side: usize| if max == 0 {
+            (side as u32).saturating_sub(self.padding * 2)
+        } else {
+            max
+        };
+
+        (pick(self.max_width, size.0), pick(self.max_height, size.1))
+    }
+
+    /// Returns text shortened according to truncate mode, measure function is used to get width of the text for width based truncation
+    pub fn truncated_text<F: Fn(&str) -> u32>(&self, size: (usize, usize), measure: F) -> String {
+        match &self.truncate {
+            Some(TruncateMode::Chars { max, ellipsis }) => truncate_text_to_chars(&self.text, *max, ellipsis),
+
+            Some(TruncateMode::Width { max, ellipsis }) => {
+                let max = if *max == 0 {
+                    (size.0 as u32).saturating_sub(self.padding * 2)
+                } else {
+                    *max
+                };
+
+                truncate_text_to_width(&self.text, max, ellipsis, measure)
+            }
+
+            None => self.text.clone()
+        }
+    }
 }
 
 impl Hash for ButtonText {
@@ -440,8 +1120,14 @@ impl Hash for ButtonText {
         self.padding.hash(state);
         ((self.offset.0 * 100.0) as i32).hash(state);
         ((self.offset.1 * 100.0) as i32).hash(state);
-        self.color.hash(state);
+        self.fill.hash(state);
         self.shadow.hash(state);
+        self.auto_fit.hash(state);
+        self.max_width.hash(state);
+        self.max_height.hash(state);
+        ((self.letter_spacing * 100.0) as i32).hash(state);
+        self.blink.hash(state);
+        self.truncate.hash(state);
     }
 }
 
@@ -451,6 +1137,7 @@ pub struct ButtonTextShadow {
     /// Shadow offset in pixels
     pub offset: (i32, i32),
     /// Color of the shadow
+    #[serde(deserialize_with = "crate::util::color::deserialize")]
     pub color: Color,
 }
 
@@ -475,10 +1162,109 @@ pub struct RendererComponent {
     /// Anything that custom renderers might want to remember
     #[serde(default)]
     pub custom_data: Value,
+    /// Opacity of the whole button, 0.0 to 1.0
+    #[serde(default = "make_one")]
+    pub opacity: f32,
+    /// Alternative sets of background and text, if not empty, state at state index will be drawn instead
+    #[serde(default)]
+    pub states: Vec<RendererComponentState>,
+    /// Index of the state that should be drawn, wraps around amount of states
+    #[serde(default)]
+    pub state_index: usize,
+    /// Background that should be used while the key is held down
+    #[serde(default)]
+    pub pressed_background: Option<ButtonBackground>,
+    /// Text objects that should be used while the key is held down
+    #[serde(default)]
+    pub pressed_text: Option<Vec<ButtonText>>,
+    /// If fully transparent borders of image backgrounds should be trimmed before scaling
+    #[serde(default)]
+    pub autocrop: bool,
+    /// Offset in pixels of image backgrounds within the button, parts moved outside of the button get clipped
+    #[serde(default)]
+    pub image_offset: (i32, i32),
+}
+
+impl RendererComponent {
+    /// Returns currently active state if component has any states
+    pub fn active_state(&self) -> Option<&RendererComponentState> {
+        if self.states.is_empty() {
+            None
+        } else {
+            self.states.get(self.state_index % self.states.len())
+        }
+    }
+
+    /// Returns background of currently active state, or component's background if there are no states
+    pub fn active_background(&self) -> &ButtonBackground {
+        if let Some(state) = self.active_state() {
+            &state.background
+        } else {
+            &self.background
+        }
+    }
+
+    /// Returns component with pressed overrides applied, or None if component doesn't have any
+    pub fn pressed_variant(&self) -> Option<RendererComponent> {
+        if self.pressed_background.is_none() && self.pressed_text.is_none() {
+            return None;
+        }
+
+        let mut component = self.clone();
+
+        component.background = self.pressed_background.clone().unwrap_or_else(|| self.active_background().clone());
+        component.text = self.pressed_text.clone().unwrap_or_else(|| self.active_text().clone());
+        component.states.clear();
+
+        Some(component)
+    }
+
+    /// Returns component with text hidden by blinking removed at provided point of blink clock, or None if component doesn't have any blinking text
+    pub fn blink_variant(&self, clock: Duration) -> Option<RendererComponent> {
+        if !self.active_text().iter().any(|x| x.blink.is_some()) {
+            return None;
+        }
+
+        let mut component = self.clone();
+
+        component.background = self.active_background().clone();
+        component.text = self.active_text().iter()
+            .filter(|x| x.blink.map_or(true, |blink| blink.is_visible_at(clock)))
+            .map(|x| ButtonText {
+                blink: None,
+                ..x.clone()
+            })
+            .collect();
+        component.states.clear();
+
+        Some(component)
+    }
+
+    /// Returns text objects of currently active state, or component's text objects if there are no states
+    pub fn active_text(&self) -> &Vec<ButtonText> {
+        if let Some(state) = self.active_state() {
+            &state.text
+        } else {
+            &self.text
+        }
+    }
+}
+
+/// Alternative set of background and text objects for renderer component
+#[derive(Serialize, Deserialize, Clone, Debug, Hash, Default)]
+pub struct RendererComponentState {
+    /// Background that should be used
+    #[serde(default)]
+    pub background: ButtonBackground,
+    /// Text objects
+    #[serde(default)]
+    pub text: Vec<ButtonText>,
 }
 
 fn make_true() -> bool { true }
 
+fn make_one() -> f32 { 1.0 }
+
 impl Default for RendererComponent {
     fn default() -> Self {
         Self {
@@ -487,7 +1273,14 @@ impl Default for RendererComponent {
             text: vec![],
             plugin_blacklist: vec![],
             to_cache: true,
-            custom_data: Default::default()
+            custom_data: Default::default(),
+            opacity: 1.0,
+            states: vec![],
+            state_index: 0,
+            pressed_background: None,
+            pressed_text: None,
+            autocrop: false,
+            image_offset: (0, 0)
         }
     }
 }
@@ -500,6 +1293,13 @@ impl Hash for RendererComponent {
         self.to_cache.hash(state);
         self.background.hash(state);
         hash_value(&self.custom_data, state);
+        self.opacity.to_bits().hash(state);
+        self.states.hash(state);
+        self.state_index.hash(state);
+        self.pressed_background.hash(state);
+        self.pressed_text.hash(state);
+        self.autocrop.hash(state);
+        self.image_offset.hash(state);
     }
 }
 
@@ -544,6 +1344,36 @@ impl RendererComponentBuilder {
         self.component.to_cache = cache; self
     }
 
+    /// Sets opacity of the button
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.component.opacity = opacity; self
+    }
+
+    /// Adds an alternative state
+    pub fn add_state(mut self, state: RendererComponentState) -> Self {
+        self.component.states.push(state); self
+    }
+
+    /// Sets offset of image backgrounds within the button
+    pub fn image_offset(mut self, x: i32, y: i32) -> Self {
+        self.component.image_offset = (x, y); self
+    }
+
+    /// Sets index of the state that should be drawn
+    pub fn state_index(mut self, index: usize) -> Self {
+        self.component.state_index = index; self
+    }
+
+    /// Sets background that should be used while the key is held down
+    pub fn pressed_background(mut self, background: ButtonBackground) -> Self {
+        self.component.pressed_background = Some(background); self
+    }
+
+    /// Sets text objects that should be used while the key is held down
+    pub fn pressed_text(mut self, text: Vec<ButtonText>) -> Self {
+        self.component.pressed_text = Some(text); self
+    }
+
     /// Builds the component
     pub fn build(self) -> RendererComponent {
         self.into()
@@ -556,11 +1386,96 @@ impl From<RendererComponentBuilder> for RendererComponent {
     }
 }
 
+/// Applies color mode to finished button image and encodes it for the device
+fn to_device_image(core: &CoreHandle, mut image: DynamicImage, settings: &RendererSettings) -> DeviceImage {
+    apply_color_mode_on_image(&mut image, settings.color_mode);
+    convert_image_with_quality(&core.core.kind, image, settings.jpeg_quality)
+}
+
+/// Encodes rendered image for the device into a cache entry, keeping the rendered image if renderer retains last rendered images
+fn prepare_image(core: &CoreHandle, mut image: DynamicImage, settings: &RendererSettings, time: u64) -> CachedImage {
+    apply_color_mode_on_image(&mut image, settings.color_mode);
+
+    let rendered = if settings.retain_rendered_images {
+        Some(Arc::new(image.clone()))
+    } else {
+        None
+    };
+
+    let bytes = encode_image_with_quality(&core.core.kind, image, settings.jpeg_quality);
+
+    CachedImage {
+        size: bytes.len(),
+        image: Arc::new(DeviceImage::from(bytes)),
+        expires_at: time + 20000,
+        rendered
+    }
+}
+
 /// Renderer settings
-#[derive(Serialize, Deserialize, Default)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct RendererSettings {
     /// Blacklist of plugins that aren't allowed to render
-    pub plugin_blacklist: Vec<String>
+    pub plugin_blacklist: Vec<String>,
+    /// Fonts that will be used in order for glyphs that are missing in font of the text
+    #[serde(default)]
+    pub font_fallbacks: Vec<String>,
+    /// Color mode of images sent to devices, for hardware that only supports grayscale or 1-bit images
+    #[serde(default)]
+    pub color_mode: ColorMode,
+    /// Font that will be used for text that doesn't specify a font, or if specified font wasn't found
+    #[serde(default = "default_font_name")]
+    pub default_font: String,
+    /// Scale that will be used for text that has scale of zero
+    #[serde(default = "default_text_scale")]
+    pub default_scale: (f32, f32),
+    /// Quality from 1 to 100 of images sent to devices that expect JPEG images, lower quality is faster to transfer
+    #[serde(default = "default_jpeg_quality")]
+    pub jpeg_quality: u8,
+    /// If last rendered image of each key should be kept in memory, so it can be retrieved without rendering the button again
+    #[serde(default = "default_retain_rendered_images")]
+    pub retain_rendered_images: bool,
+    /// How many times larger text is drawn before it's scaled down to button size, higher values give smoother text at a CPU cost
+    #[serde(default = "default_text_supersampling")]
+    pub text_supersampling: u32,
+}
+
+/// Max supported text supersampling factor
+pub const MAX_TEXT_SUPERSAMPLING: u32 = 4;
+
+fn default_font_name() -> String {
+    "default".to_string()
+}
+
+fn default_text_scale() -> (f32, f32) {
+    (15.0, 15.0)
+}
+
+fn default_jpeg_quality() -> u8 {
+    DEFAULT_JPEG_QUALITY
+}
+
+fn default_retain_rendered_images() -> bool {
+    true
+}
+
+fn default_text_supersampling() -> u32 {
+    1
+}
+
+impl Default for RendererSettings {
+    fn default() -> Self {
+        Self {
+            plugin_blacklist: vec![],
+            font_fallbacks: vec![],
+            color_mode: ColorMode::default(),
+            default_font: default_font_name(),
+            default_scale: default_text_scale(),
+            jpeg_quality: default_jpeg_quality(),
+            retain_rendered_images: default_retain_rendered_images(),
+            text_supersampling: default_text_supersampling()
+        }
+    }
 }
 
 #[allow(dead_code)]
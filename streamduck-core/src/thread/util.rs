@@ -1,8 +1,10 @@
+use std::sync::Arc;
 use serde::{Serialize, Deserialize};
 use strum_macros::{EnumVariantNames, EnumString, Display};
-use image::{DynamicImage, GenericImage, GenericImageView, Rgba};
-use image::imageops::{FilterType, horizontal_gradient, vertical_gradient};
-use rusttype::{Font, Point, point, Scale};
+use image::{DynamicImage, GenericImage, GenericImageView, Rgba, RgbaImage};
+use image::imageops::{FilterType, horizontal_gradient, overlay, vertical_gradient};
+use rusttype::{Font, GlyphId, Point, point, PositionedGlyph, Scale};
+use crate::thread::rendering::TextFill;
 
 /// Resizes image to specified size
 pub fn resize_for_streamdeck(size: (usize, usize), image: DynamicImage) -> DynamicImage {
@@ -10,6 +12,38 @@ pub fn resize_for_streamdeck(size: (usize, usize), image: DynamicImage) -> Dynam
     DynamicImage::from(image.to_rgba8()).resize_to_fill(sx as u32, sy as u32, FilterType::Lanczos3)
 }
 
+/// Enlarges image by integer factor without any smoothing
+pub fn upscale_image(image: &DynamicImage, factor: u32) -> DynamicImage {
+    image.resize_exact(image.width() * factor, image.height() * factor, FilterType::Nearest)
+}
+
+/// Shrinks image by integer factor, averaging each block of pixels into a single pixel
+pub fn downsample_image(image: &DynamicImage, factor: u32) -> DynamicImage {
+    let source = image.to_rgba8();
+    let (width, height) = (source.width() / factor, source.height() / factor);
+    let samples = factor * factor;
+
+    let mut result = RgbaImage::new(width, height);
+
+    for (x, y, pixel) in result.enumerate_pixels_mut() {
+        let mut sum = [0u32; 4];
+
+        for sample_y in 0..factor {
+            for sample_x in 0..factor {
+                let sample = source.get_pixel(x * factor + sample_x, y * factor + sample_y);
+
+                for channel in 0..4 {
+                    sum[channel] += sample.0[channel] as u32;
+                }
+            }
+        }
+
+        *pixel = Rgba(sum.map(|x| (x / samples) as u8));
+    }
+
+    DynamicImage::ImageRgba8(result)
+}
+
 /// Generates solid color image of specified size
 pub fn image_from_solid(size: (usize, usize), color: Rgba<u8>) -> DynamicImage {
     let (sx, sy) = size;
@@ -40,10 +74,49 @@ pub fn image_from_vert_gradient(size: (usize, usize), start: Rgba<u8>, end: Rgba
     image
 }
 
-/// Renders text from font and parameters onto provided image
-pub fn render_text_on_image(image: &mut DynamicImage, font: &Font, text: &str, scale: Scale, point: Point<f32>, color: (u8, u8, u8, u8)) {
+/// Lays out text with provided fonts, glyphs missing in first font will be taken from next font in the list that has them.
+///
+/// Letter spacing is added to advance of each glyph
+pub fn layout_text_with_fallback(fonts: &[Arc<Font<'static>>], text: &str, scale: Scale, start: Point<f32>, letter_spacing: f32) -> Vec<PositionedGlyph<'static>> {
+    let mut glyphs = vec![];
+
+    if fonts.is_empty() {
+        return glyphs;
+    }
+
+    let mut caret = 0.0;
+    let mut last_glyph: Option<(usize, GlyphId)> = None;
+
+    for character in text.chars() {
+        let index = fonts.iter()
+            .position(|font| font.glyph(character).id().0 != 0)
+            .unwrap_or(0);
+        let font = &fonts[index];
+
+        let glyph = font.glyph(character).scaled(scale);
+
+        if let Some((last_index, last_id)) = last_glyph {
+            if last_index == index {
+                caret += font.pair_kerning(scale, last_id, glyph.id());
+            }
+        }
+
+        let advance = glyph.h_metrics().advance_width;
+        let glyph = glyph.positioned(point(start.x + caret, start.y));
+
+        caret += advance + letter_spacing;
+        last_glyph = Some((index, glyph.id()));
+
+        glyphs.push(glyph);
+    }
+
+    glyphs
+}
+
+/// Draws already positioned glyphs onto provided image
+pub fn render_glyphs_on_image(image: &mut DynamicImage, glyphs: &[PositionedGlyph], color: (u8, u8, u8, u8)) {
     let (size_x, size_y) = image.dimensions();
-    for glyph in font.layout(text, scale, point) {
+    for glyph in glyphs {
         if let Some(bounding_box) = glyph.pixel_bounding_box() {
             glyph.draw(|x, y, v| {
                 let bound_x = (x as i32 + bounding_box.min.x) as u32;
@@ -65,10 +138,47 @@ pub fn render_text_on_image(image: &mut DynamicImage, font: &Font, text: &str, s
     }
 }
 
-/// Renders text with shadows from font and parameters onto provided image
-pub fn render_shadowed_text_on_image(image: &mut DynamicImage, font: &Font, text: &str, scale: Scale, point: Point<f32>, color: (u8, u8, u8, u8), shadow_offset: (i32, i32), shadow_color: (u8, u8, u8, u8)) {
+/// Draws already positioned glyphs filled with provided text fill onto provided image, shadow is drawn underneath the text if provided
+pub fn render_filled_glyphs_on_image(image: &mut DynamicImage, glyphs: &[PositionedGlyph], fill: &TextFill, shadow: Option<((i32, i32), (u8, u8, u8, u8))>) {
+    let bounds = calculate_pixel_bounds_for_glyphs(glyphs);
+
+    if let Some((shadow_offset, shadow_color)) = shadow {
+        draw_glyph_coverage(image, glyphs, shadow_offset, |_, _| shadow_color);
+    }
+
+    draw_glyph_coverage(image, glyphs, (0, 0), |x, y| fill.color_at(bounds, x, y));
+}
+
+/// Blends glyph coverage onto image with color provided for each pixel
+fn draw_glyph_coverage<F: Fn(u32, u32) -> (u8, u8, u8, u8)>(image: &mut DynamicImage, glyphs: &[PositionedGlyph], offset: (i32, i32), color: F) {
+    let (size_x, size_y) = image.dimensions();
+    for glyph in glyphs {
+        if let Some(bounding_box) = glyph.pixel_bounding_box() {
+            glyph.draw(|x, y, v| {
+                let bound_x = (x as i32 + bounding_box.min.x + offset.0) as u32;
+                let bound_y = (y as i32 + bounding_box.min.y + offset.1) as u32;
+
+                if (bound_x < size_x) && (bound_y < size_y) {
+                    let color = color(bound_x, bound_y);
+                    let pixel = image.get_pixel(bound_x, bound_y);
+                    let color_mul = (v * (color.3 as f32 / 255.0)).clamp(0.0, 1.0);
+
+                    image.put_pixel(
+                        bound_x,
+                        bound_y,
+                        // Turn the coverage into an alpha value
+                        Rgba([(pixel.0[0] as f32 * (1.0 - color_mul) + color.0 as f32 * color_mul) as u8, (pixel.0[1] as f32 * (1.0 - color_mul) + color.1 as f32 * color_mul) as u8, (pixel.0[2] as f32 * (1.0 - color_mul) + color.2 as f32 * color_mul) as u8, 255]),
+                    )
+                }
+            })
+        }
+    }
+}
+
+/// Draws already positioned glyphs with shadows onto provided image
+pub fn render_shadowed_glyphs_on_image(image: &mut DynamicImage, glyphs: &[PositionedGlyph], color: (u8, u8, u8, u8), shadow_offset: (i32, i32), shadow_color: (u8, u8, u8, u8)) {
     let (size_x, size_y) = image.dimensions();
-    for glyph in font.layout(text, scale, point) {
+    for glyph in glyphs {
         if let Some(bounding_box) = glyph.pixel_bounding_box() {
             glyph.draw(|x, y, v| {
                 let bound_x = (x as i32 + bounding_box.min.x) as u32;
@@ -107,7 +217,45 @@ pub fn render_shadowed_text_on_image(image: &mut DynamicImage, font: &Font, text
     }
 }
 
-/// Renders box with provided parameters onto provided image
+/// Renders text from font and parameters onto provided image
+pub fn render_text_on_image(image: &mut DynamicImage, font: &Font, text: &str, scale: Scale, point: Point<f32>, color: (u8, u8, u8, u8)) {
+    let glyphs: Vec<PositionedGlyph> = font.layout(text, scale, point).collect();
+    render_glyphs_on_image(image, &glyphs, color);
+}
+
+/// Renders text with shadows from font and parameters onto provided image
+pub fn render_shadowed_text_on_image(image: &mut DynamicImage, font: &Font, text: &str, scale: Scale, point: Point<f32>, color: (u8, u8, u8, u8), shadow_offset: (i32, i32), shadow_color: (u8, u8, u8, u8)) {
+    let glyphs: Vec<PositionedGlyph> = font.layout(text, scale, point).collect();
+    render_shadowed_glyphs_on_image(image, &glyphs, color, shadow_offset, shadow_color);
+}
+
+/// Blends color onto pixel of the image using alpha of the color, fully opaque color replaces the pixel
+pub fn blend_pixel_on_image(image: &mut DynamicImage, x: u32, y: u32, color: (u8, u8, u8, u8)) {
+    if color.3 == 255 {
+        image.put_pixel(x, y, Rgba([color.0, color.1, color.2, 255]));
+        return;
+    }
+
+    let pixel = image.get_pixel(x, y);
+    let alpha = color.3 as f32 / 255.0;
+    let pixel_alpha = pixel.0[3] as f32 / 255.0;
+    let out_alpha = alpha + pixel_alpha * (1.0 - alpha);
+
+    if out_alpha <= 0.0 {
+        return;
+    }
+
+    let blend = |source: u8, destination: u8| ((source as f32 * alpha + destination as f32 * pixel_alpha * (1.0 - alpha)) / out_alpha) as u8;
+
+    image.put_pixel(x, y, Rgba([
+        blend(color.0, pixel.0[0]),
+        blend(color.1, pixel.0[1]),
+        blend(color.2, pixel.0[2]),
+        (out_alpha * 255.0) as u8
+    ]));
+}
+
+/// Renders box with provided parameters onto provided image, blending it using alpha of the color
 pub fn render_box_on_image(image: &mut DynamicImage, scale: Scale, point: Point<f32>, color: (u8, u8, u8, u8)) {
     let (size_x, size_y) = image.dimensions();
     for x in 0..(scale.x as isize) {
@@ -116,22 +264,95 @@ pub fn render_box_on_image(image: &mut DynamicImage, scale: Scale, point: Point<
             let offset_y = (point.y - y as f32 - 1.0) as u32;
 
             if (offset_x < size_x) && (offset_y < size_y) {
-                image.put_pixel(
-                    offset_x,
-                    offset_y,
-                    Rgba([color.0, color.1, color.2, 255]),
-                )
+                blend_pixel_on_image(image, offset_x, offset_y, color);
             }
         }
     }
 }
 
+/// Fades image towards black by provided opacity, as that's what device shows behind the buttons
+pub fn apply_opacity_on_image(image: &mut DynamicImage, opacity: f32) {
+    let opacity = opacity.clamp(0.0, 1.0);
+    let mut buffer = image.to_rgba8();
+
+    for pixel in buffer.pixels_mut() {
+        *pixel = Rgba([(pixel.0[0] as f32 * opacity) as u8, (pixel.0[1] as f32 * opacity) as u8, (pixel.0[2] as f32 * opacity) as u8, 255]);
+    }
+
+    *image = DynamicImage::ImageRgba8(buffer);
+}
+
+/// Trims fully transparent borders off the image, returns image as is if it's fully transparent
+pub fn autocrop_image(image: &DynamicImage) -> DynamicImage {
+    let rgba = image.to_rgba8();
+
+    let mut min = (u32::MAX, u32::MAX);
+    let mut max = (0, 0);
+
+    for (x, y, pixel) in rgba.enumerate_pixels() {
+        if pixel.0[3] != 0 {
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        }
+    }
+
+    if min.0 > max.0 || min.1 > max.1 {
+        return image.clone();
+    }
+
+    image.crop_imm(min.0, min.1, max.0 - min.0 + 1, max.1 - min.1 + 1)
+}
+
+/// Draws image on top of base image at provided position, alpha of the image is multiplied by opacity
+pub fn overlay_image(base: &mut DynamicImage, image: &DynamicImage, position: (i32, i32), opacity: f32) {
+    let opacity = opacity.clamp(0.0, 1.0);
+    let mut top = image.to_rgba8();
+
+    if opacity < 1.0 {
+        for pixel in top.pixels_mut() {
+            pixel.0[3] = (pixel.0[3] as f32 * opacity) as u8;
+        }
+    }
+
+    overlay(base, &DynamicImage::ImageRgba8(top), position.0 as i64, position.1 as i64);
+}
+
+/// Threshold of luminance above which pixel becomes white in [ColorMode::Mono1Bit]
+pub const MONO_THRESHOLD: u8 = 128;
+
+/// Converts image into format expected by color mode of the device
+pub fn apply_color_mode_on_image(image: &mut DynamicImage, mode: ColorMode) {
+    match mode {
+        ColorMode::Color => {}
+
+        ColorMode::Grayscale => {
+            *image = DynamicImage::ImageLumaA8(image.to_luma_alpha8());
+        }
+
+        ColorMode::Mono1Bit => {
+            let mut buffer = image.to_luma_alpha8();
+
+            for pixel in buffer.pixels_mut() {
+                pixel.0[0] = if pixel.0[0] >= MONO_THRESHOLD { 255 } else { 0 };
+            }
+
+            *image = DynamicImage::ImageLumaA8(buffer);
+        }
+    }
+}
+
 /// Calculates bounds for text with provided font and parameters
 pub fn calculate_bounds_for_text(font: &Font, text: &str, scale: Scale) -> (u32, u32) {
+    let glyphs: Vec<PositionedGlyph> = font.layout(text, scale, point(0.0, 0.0)).collect();
+    calculate_bounds_for_glyphs(&glyphs)
+}
+
+/// Calculates bounds for glyphs that were positioned at 0, 0
+pub fn calculate_bounds_for_glyphs(glyphs: &[PositionedGlyph]) -> (u32, u32) {
     let mut w: u32 = 0;
     let mut h: u32 = 0;
 
-    for glyph in font.layout(text, scale, point(0.0, 0.0)) {
+    for glyph in glyphs {
         if let Some(bounding) = glyph.pixel_bounding_box() {
             h = h.max(bounding.height() as u32);
             w = w.max(bounding.max.x as u32);
@@ -141,6 +362,95 @@ pub fn calculate_bounds_for_text(font: &Font, text: &str, scale: Scale) -> (u32,
     (w, h)
 }
 
+/// Calculates min x, min y, max x and max y in pixels of already positioned glyphs
+pub fn calculate_pixel_bounds_for_glyphs(glyphs: &[PositionedGlyph]) -> (i32, i32, i32, i32) {
+    let mut bounds: Option<(i32, i32, i32, i32)> = None;
+
+    for glyph in glyphs {
+        if let Some(bounding) = glyph.pixel_bounding_box() {
+            bounds = Some(match bounds {
+                Some((min_x, min_y, max_x, max_y)) => (min_x.min(bounding.min.x), min_y.min(bounding.min.y), max_x.max(bounding.max.x), max_y.max(bounding.max.y)),
+                None => (bounding.min.x, bounding.min.y, bounding.max.x, bounding.max.y)
+            });
+        }
+    }
+
+    bounds.unwrap_or((0, 0, 0, 0))
+}
+
+/// Shortens text to max amount of characters including the ellipsis, ellipsis is only added if text was shortened
+pub fn truncate_text_to_chars(text: &str, max: usize, ellipsis: &str) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+
+    let ellipsis_length = ellipsis.chars().count();
+
+    if max <= ellipsis_length {
+        return ellipsis.chars().take(max).collect();
+    }
+
+    let mut truncated: String = text.chars().take(max - ellipsis_length).collect();
+    truncated.push_str(ellipsis);
+    truncated
+}
+
+/// Shortens text until its width returned by measure function fits into max width including the ellipsis, ellipsis is only added if text was shortened
+pub fn truncate_text_to_width<F: Fn(&str) -> u32>(text: &str, max_width: u32, ellipsis: &str, measure: F) -> String {
+    if measure(text) <= max_width {
+        return text.to_string();
+    }
+
+    let chars: Vec<char> = text.chars().collect();
+    let with_ellipsis = |length: usize| {
+        let mut truncated: String = chars[..length].iter().collect::<String>().trim_end().to_string();
+        truncated.push_str(ellipsis);
+        truncated
+    };
+
+    // Searching for longest part of the text that fits together with ellipsis
+    let mut low = 0;
+    let mut high = chars.len();
+
+    while low < high {
+        let middle = (low + high + 1) / 2;
+
+        if measure(&with_ellipsis(middle)) <= max_width {
+            low = middle;
+        } else {
+            high = middle - 1;
+        }
+    }
+
+    with_ellipsis(low)
+}
+
+/// Reduces scale until bounds returned by measure function fit into max bounds, keeping aspect of the scale
+pub fn fit_scale_to_bounds<F: Fn(Scale) -> (u32, u32)>(measure: F, scale: Scale, max_bounds: (u32, u32)) -> Scale {
+    let (max_w, max_h) = max_bounds;
+    let (w, h) = measure(scale);
+
+    if w <= max_w && h <= max_h {
+        return scale;
+    }
+
+    // Jumping close to fitting scale first, then shrinking in small steps to account for glyph rounding
+    let ratio = (max_w as f32 / w.max(1) as f32).min(max_h as f32 / h.max(1) as f32);
+    let mut scale = Scale { x: scale.x * ratio, y: scale.y * ratio };
+
+    for _ in 0..20 {
+        let (w, h) = measure(scale);
+
+        if (w <= max_w && h <= max_h) || scale.y <= 1.0 {
+            break;
+        }
+
+        scale = Scale { x: scale.x * 0.95, y: scale.y * 0.95 };
+    }
+
+    scale
+}
+
 /// Alignment enumeration
 #[derive(Debug, Clone, Hash, Serialize, Deserialize, EnumVariantNames, EnumString, Display)]
 #[strum(serialize_all = "title_case")]
@@ -165,10 +475,33 @@ pub enum TextAlignment {
     BottomRight,
 }
 
+/// Color mode of images sent to the device
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Serialize, Deserialize, EnumVariantNames, EnumString, Display)]
+#[strum(serialize_all = "title_case")]
+pub enum ColorMode {
+    /// Images are sent as is
+    Color,
+    /// Images are converted to grayscale by luminance
+    Grayscale,
+    /// Images are converted to black and white by luminance threshold
+    Mono1Bit,
+}
+
+impl Default for ColorMode {
+    fn default() -> Self {
+        ColorMode::Color
+    }
+}
+
 /// Calculates where text should be rendered for specified alignment and other parameters
 pub fn get_alignment_position_for_text(size: (usize, usize), font: &Font, text: &str, scale: Scale, align: TextAlignment, padding: u32, offset: (f32, f32)) -> Point<f32> {
+    get_alignment_position_for_bounds(size, calculate_bounds_for_text(font, text, scale), align, padding, offset)
+}
+
+/// Calculates where text of specified bounds should be rendered for specified alignment and other parameters
+pub fn get_alignment_position_for_bounds(size: (usize, usize), bounds: (u32, u32), align: TextAlignment, padding: u32, offset: (f32, f32)) -> Point<f32> {
     let (sw, sh) = size;
-    let (tw, th) = calculate_bounds_for_text(font, text, scale);
+    let (tw, th) = bounds;
 
     point(
         match align {
@@ -210,4 +543,32 @@ pub fn render_aligned_text_on_image(size: (usize, usize), image: &mut DynamicIma
 pub fn render_aligned_shadowed_text_on_image(size: (usize, usize), image: &mut DynamicImage, font: &Font, text: &str, scale: Scale, align: TextAlignment, padding: u32, offset: (f32, f32), color: (u8, u8, u8, u8), shadow_offset: (i32, i32), shadow_color: (u8, u8, u8, u8)) {
     let point = get_alignment_position_for_text(size, font, text, scale.clone(), align, padding, offset);
     render_shadowed_text_on_image(image, &font, text, scale, point, color, shadow_offset, shadow_color);
-}
\ No newline at end of file
+}
+
+/// Renders aligned text onto provided image with specified parameters, using fallback fonts for missing glyphs
+pub fn render_aligned_text_with_fallback_on_image(size: (usize, usize), image: &mut DynamicImage, fonts: &[Arc<Font<'static>>], text: &str, scale: Scale, letter_spacing: f32, align: TextAlignment, padding: u32, offset: (f32, f32), color: (u8, u8, u8, u8)) {
+    let bounds = calculate_bounds_for_glyphs(&layout_text_with_fallback(fonts, text, scale, point(0.0, 0.0), letter_spacing));
+    let point = get_alignment_position_for_bounds(size, bounds, align, padding, offset);
+    render_glyphs_on_image(image, &layout_text_with_fallback(fonts, text, scale, point, letter_spacing), color);
+}
+
+/// Renders aligned text filled with provided text fill onto provided image
+pub fn render_aligned_filled_text_on_image(size: (usize, usize), image: &mut DynamicImage, font: &Font, text: &str, scale: Scale, align: TextAlignment, padding: u32, offset: (f32, f32), fill: &TextFill, shadow: Option<((i32, i32), (u8, u8, u8, u8))>) {
+    let point = get_alignment_position_for_text(size, font, text, scale, align, padding, offset);
+    let glyphs: Vec<PositionedGlyph> = font.layout(text, scale, point).collect();
+    render_filled_glyphs_on_image(image, &glyphs, fill, shadow);
+}
+
+/// Renders aligned text filled with provided text fill onto provided image, using fallback fonts for missing glyphs
+pub fn render_aligned_filled_text_with_fallback_on_image(size: (usize, usize), image: &mut DynamicImage, fonts: &[Arc<Font<'static>>], text: &str, scale: Scale, letter_spacing: f32, align: TextAlignment, padding: u32, offset: (f32, f32), fill: &TextFill, shadow: Option<((i32, i32), (u8, u8, u8, u8))>) {
+    let bounds = calculate_bounds_for_glyphs(&layout_text_with_fallback(fonts, text, scale, point(0.0, 0.0), letter_spacing));
+    let point = get_alignment_position_for_bounds(size, bounds, align, padding, offset);
+    render_filled_glyphs_on_image(image, &layout_text_with_fallback(fonts, text, scale, point, letter_spacing), fill, shadow);
+}
+
+/// Renders aligned shadowed text onto provided image with specified parameters, using fallback fonts for missing glyphs
+pub fn render_aligned_shadowed_text_with_fallback_on_image(size: (usize, usize), image: &mut DynamicImage, fonts: &[Arc<Font<'static>>], text: &str, scale: Scale, letter_spacing: f32, align: TextAlignment, padding: u32, offset: (f32, f32), color: (u8, u8, u8, u8), shadow_offset: (i32, i32), shadow_color: (u8, u8, u8, u8)) {
+    let bounds = calculate_bounds_for_glyphs(&layout_text_with_fallback(fonts, text, scale, point(0.0, 0.0), letter_spacing));
+    let point = get_alignment_position_for_bounds(size, bounds, align, padding, offset);
+    render_shadowed_glyphs_on_image(image, &layout_text_with_fallback(fonts, text, scale, point, letter_spacing), color, shadow_offset, shadow_color);
+}
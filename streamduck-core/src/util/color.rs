@@ -0,0 +1,111 @@
+//! Helpers for converting [Color] to and from hex strings
+use serde::{Deserialize, Deserializer};
+use crate::thread::rendering::Color;
+
+/// Parses hex color in `#RGB`, `#RRGGBB` or `#RRGGBBAA` format, leading `#` is optional
+pub fn parse_hex(hex: &str) -> Option<Color> {
+    let hex = hex.trim();
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+
+    if !hex.is_ascii() {
+        return None;
+    }
+
+    let channel = |index: usize, width: usize| -> Option<u8> {
+        let value = u8::from_str_radix(&hex[index * width..index * width + width], 16).ok()?;
+
+        // Short form channels are repeated, F becomes FF
+        Some(if width == 1 { value * 17 } else { value })
+    };
+
+    match hex.len() {
+        3 => Some((channel(0, 1)?, channel(1, 1)?, channel(2, 1)?, 255)),
+        6 => Some((channel(0, 2)?, channel(1, 2)?, channel(2, 2)?, 255)),
+        8 => Some((channel(0, 2)?, channel(1, 2)?, channel(2, 2)?, channel(3, 2)?)),
+        _ => None
+    }
+}
+
+/// Converts color to `#RRGGBBAA` hex string
+pub fn to_hex(color: Color) -> String {
+    format!("#{:02X}{:02X}{:02X}{:02X}", color.0, color.1, color.2, color.3)
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ColorRepr {
+    Tuple(Color),
+    Hex(String)
+}
+
+/// Deserializes color from either a tuple or a hex string, for use with `#[serde(deserialize_with)]`
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    match ColorRepr::deserialize(deserializer)? {
+        ColorRepr::Tuple(color) => Ok(color),
+        ColorRepr::Hex(hex) => parse_hex(&hex)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid hex color '{}'", hex)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::thread::rendering::{ButtonBackground, ButtonText, TextFill};
+
+    #[test]
+    fn color_parse_hex_formats() {
+        assert_eq!(parse_hex("#F80"), Some((255, 136, 0, 255)));
+        assert_eq!(parse_hex("#FF8800"), Some((255, 136, 0, 255)));
+        assert_eq!(parse_hex("#FF880080"), Some((255, 136, 0, 128)));
+        assert_eq!(parse_hex("ff8800"), Some((255, 136, 0, 255)));
+        assert_eq!(parse_hex(" #ff8800 "), Some((255, 136, 0, 255)));
+    }
+
+    #[test]
+    fn color_parse_hex_invalid() {
+        assert_eq!(parse_hex(""), None);
+        assert_eq!(parse_hex("#"), None);
+        assert_eq!(parse_hex("#FF88"), None);
+        assert_eq!(parse_hex("#GG8800"), None);
+        assert_eq!(parse_hex("#FF8800FF00"), None);
+        // multi-byte characters must not panic on slicing
+        assert_eq!(parse_hex("#ÿÿÿ"), None);
+        assert_eq!(parse_hex("#€F"), None);
+    }
+
+    #[test]
+    fn color_to_hex_round_trip() {
+        let color = (18, 52, 86, 120);
+
+        assert_eq!(to_hex(color), "#12345678");
+        assert_eq!(parse_hex(&to_hex(color)), Some(color));
+    }
+
+    #[test]
+    fn color_deserialize_background() {
+        let tuple: ButtonBackground = serde_json::from_str(r#"{"Solid": [255, 136, 0, 255]}"#).unwrap();
+        let hex: ButtonBackground = serde_json::from_str(r##"{"Solid": "#FF8800"}"##).unwrap();
+
+        assert!(matches!(tuple, ButtonBackground::Solid((255, 136, 0, 255))));
+        assert!(matches!(hex, ButtonBackground::Solid((255, 136, 0, 255))));
+
+        let gradient: ButtonBackground = serde_json::from_str(r##"{"HorizontalGradient": ["#000", [255, 255, 255, 255]]}"##).unwrap();
+        assert!(matches!(gradient, ButtonBackground::HorizontalGradient((0, 0, 0, 255), (255, 255, 255, 255))));
+
+        assert!(serde_json::from_str::<ButtonBackground>(r##"{"Solid": "#XYZ"}"##).is_err());
+    }
+
+    #[test]
+    fn color_deserialize_text_fill() {
+        let text = |fill: &str| format!(r#"{{"text": "", "font": "", "scale": [0.0, 0.0], "alignment": "Center", "padding": 0, "offset": [0.0, 0.0], "shadow": null, {}}}"#, fill);
+
+        let legacy: ButtonText = serde_json::from_str(&text(r##""color": "#FF8800""##)).unwrap();
+        assert!(matches!(legacy.fill, TextFill::Solid((255, 136, 0, 255))));
+
+        let solid: ButtonText = serde_json::from_str(&text(r#""fill": {"Solid": [255, 136, 0, 255]}"#)).unwrap();
+        assert!(matches!(solid.fill, TextFill::Solid((255, 136, 0, 255))));
+
+        let gradient: ButtonText = serde_json::from_str(&text(r##""fill": {"Gradient": {"start": "#000", "end": "#FFF", "direction": "Vertical"}}"##)).unwrap();
+        assert!(matches!(gradient.fill, TextFill::Gradient { start: (0, 0, 0, 255), end: (255, 255, 255, 255), .. }));
+    }
+}
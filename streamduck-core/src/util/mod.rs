@@ -4,6 +4,7 @@ use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 use std::sync::{Arc};
 use serde_json::{Error, Value};
+use streamdeck::Kind;
 use tokio::sync::RwLock;
 use crate::core::button::Button;
 use crate::core::{ButtonPanel, Panel, RawButtonPanel, UniqueButton, UniqueButtonMap};
@@ -13,11 +14,45 @@ use crate::modules::components::{UIFieldType, UIFieldValue, UIPathValue, UIValue
 
 pub use rusttype;
 
+/// Color conversion helpers
+pub mod color;
+
 /// Wraps button in [Arc] and [RwLock], but packed into a more convenient function
 pub fn make_button_unique(button: Button) -> UniqueButton {
     Arc::new(RwLock::new(button))
 }
 
+/// Returns key grid dimensions of the device kind as (rows, columns)
+pub fn get_key_grid(kind: &Kind) -> (u8, u8) {
+    match kind {
+        Kind::Original | Kind::OriginalV2 | Kind::Mk2 => (3, 5),
+        Kind::Mini => (2, 3),
+        Kind::Xl => (4, 8),
+    }
+}
+
+/// Converts row and column into key index using provided key grid, returns None if coordinates are out of the grid
+pub fn key_from_coords(grid: (u8, u8), row: u8, column: u8) -> Option<u8> {
+    let (rows, columns) = grid;
+
+    if row >= rows || column >= columns {
+        return None;
+    }
+
+    Some(row * columns + column)
+}
+
+/// Converts key index into row and column using provided key grid, returns None if key is out of the grid
+pub fn coords_from_key(grid: (u8, u8), key: u8) -> Option<(u8, u8)> {
+    let (rows, columns) = grid;
+
+    if key >= rows.saturating_mul(columns) {
+        return None;
+    }
+
+    Some((key / columns, key % columns))
+}
+
 /// Parses button panel to Value, serializing all the unique buttons in process
 pub async fn serialize_panel(panel: ButtonPanel) -> Result<Value, Error> {
     let panel = panel_to_raw(&panel).await;
@@ -44,6 +79,7 @@ pub fn deserialize_panel_raw(value: Value) -> Result<RawButtonPanel, Error> {
 pub fn make_panel_unique(raw_panel: RawButtonPanel) -> ButtonPanel {
     Arc::new(RwLock::new(
         Panel::<UniqueButtonMap> {
+            id: raw_panel.id,
             display_name: raw_panel.display_name,
             data: raw_panel.data,
             buttons: raw_panel.buttons.into_iter().map(|(key, button)| (key, make_button_unique(button))).collect()
@@ -63,6 +99,7 @@ pub async fn panel_to_raw(panel: &ButtonPanel) -> RawButtonPanel {
     }
 
     RawButtonPanel {
+        id: panel.id,
         display_name: panel.display_name,
         data: panel.data,
         buttons
@@ -433,4 +470,44 @@ pub fn set_value_function(value: UIPathValue) -> Box<dyn Fn(&mut UIValue) -> boo
             }
         }
     })
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::core::{CoreHandle, SDCore};
+    use crate::modules::ModuleManager;
+    use crate::socket::SocketManager;
+    use crate::thread::rendering::custom::RenderingManager;
+
+    #[test]
+    fn key_coords_corners() {
+        let grid = get_key_grid(&Kind::Original);
+
+        assert_eq!(key_from_coords(grid, 0, 0), Some(0));
+        assert_eq!(key_from_coords(grid, 2, 4), Some(14));
+        assert_eq!(coords_from_key(grid, 0), Some((0, 0)));
+        assert_eq!(coords_from_key(grid, 14), Some((2, 4)));
+    }
+
+    #[test]
+    fn key_coords_out_of_range() {
+        let grid = get_key_grid(&Kind::Original);
+
+        assert_eq!(key_from_coords(grid, 3, 0), None);
+        assert_eq!(key_from_coords(grid, 0, 5), None);
+        assert_eq!(key_from_coords(grid, u8::MAX, u8::MAX), None);
+        assert_eq!(coords_from_key(grid, 15), None);
+        assert_eq!(coords_from_key(grid, u8::MAX), None);
+    }
+
+    #[tokio::test]
+    async fn key_coords_blank_core() {
+        // blank core has no device, so its grid is empty
+        let config = Arc::new(Config::get(None, None).await);
+        let core = CoreHandle::wrap(SDCore::blank(ModuleManager::new(), RenderingManager::new(), SocketManager::new(), config, Default::default(), Default::default()).await);
+
+        assert_eq!(core.key_from_coords(0, 0), None);
+        assert_eq!(core.coords_from_key(0), None);
+    }
+}
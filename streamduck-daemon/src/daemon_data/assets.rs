@@ -1,22 +1,38 @@
 //! Requests related to images and fonts
 use std::collections::HashMap;
+use std::io::Cursor;
 use serde::{Deserialize, Serialize};
-use streamduck_core::font::get_font_names;
+use streamduck_core::font::{get_font_from_collection, get_font_names};
+use streamduck_core::image::io::Reader;
+use streamduck_core::images::{ImageMetadata, SDSerializedImage};
+use streamduck_core::util::rusttype::Scale;
+use streamduck_core::modules::core_module::CoreSettings;
+use streamduck_core::thread::rendering::{get_text_fonts, measure_text};
 use streamduck_core::socket::{check_packet_for_data, parse_packet_to_data, send_packet, SocketData, SocketHandle, SocketPacket};
 use crate::daemon_data::{DaemonListener, DaemonRequest};
 use streamduck_core::async_trait;
 
 /// Request for getting all images currently saved on device
+///
+/// If offset or limit are set, images are sorted by identifier and only requested page of images is returned,
+/// requests without them are answered with [ListImagesResult::Images] same as before paging was added
 #[derive(Serialize, Deserialize)]
 pub struct ListImages {
-    pub serial_number: String
+    pub serial_number: String,
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default)]
+    pub limit: Option<usize>,
 }
 
 /// Struct that keeps information about SDImage
 #[derive(Serialize, Deserialize)]
 pub struct SocketImage {
     pub image_blob: String,
-    pub animated: bool
+    pub animated: bool,
+    /// Dimensions, format and size of the image as it was added
+    #[serde(default)]
+    pub metadata: Option<ImageMetadata>,
 }
 
 /// Response for [ListImages] request
@@ -26,7 +42,14 @@ pub enum ListImagesResult {
     DeviceNotFound,
 
     /// Sent if successfully retrieved image list from device config
-    Images(HashMap<String, SocketImage>)
+    Images(HashMap<String, SocketImage>),
+
+    /// Sent if successfully retrieved page of image list from device config, contains total amount of images on the device.
+    /// Only sent for requests that set offset or limit
+    Page {
+        images: Vec<(String, SocketImage)>,
+        total: usize,
+    },
 }
 
 impl SocketData for ListImages {
@@ -42,14 +65,38 @@ impl DaemonRequest for ListImages {
     async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
         if let Ok(request) = parse_packet_to_data::<ListImages>(packet) {
             if let Some(images) = listener.config.get_images(&request.serial_number).await {
-                send_packet(handle, packet, &ListImagesResult::Images(
-                    images.into_iter()
-                        .map(|(id, image)| (id, SocketImage {
-                            image_blob: image.as_image_blob().unwrap_or("failed".to_string()),
-                            animated: image.is_animated()
-                        }))
-                        .collect()
-                )).await.ok();
+                let metadata = listener.config.get_image_metadata(&request.serial_number).await.unwrap_or_default();
+
+                let to_socket_image = |id: &String, image: SDSerializedImage| SocketImage {
+                    image_blob: image.as_image_blob().unwrap_or("failed".to_string()),
+                    animated: image.is_animated(),
+                    metadata: metadata.get(id).cloned()
+                };
+
+                if request.offset == 0 && request.limit.is_none() {
+                    send_packet(handle, packet, &ListImagesResult::Images(
+                        images.into_iter()
+                            .map(|(id, image)| (id.clone(), to_socket_image(&id, image)))
+                            .collect()
+                    )).await.ok();
+                } else {
+                    let total = images.len();
+
+                    let mut images = images.into_iter().collect::<Vec<(String, SDSerializedImage)>>();
+                    images.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+                    // Only encoding images of the requested page
+                    let images = images.into_iter()
+                        .skip(request.offset)
+                        .take(request.limit.unwrap_or(usize::MAX))
+                        .map(|(id, image)| (id.clone(), to_socket_image(&id, image)))
+                        .collect();
+
+                    send_packet(handle, packet, &ListImagesResult::Page {
+                        images,
+                        total
+                    }).await.ok();
+                }
             } else {
                 send_packet(handle, packet, &ListImagesResult::DeviceNotFound).await.ok();
             }
@@ -73,6 +120,12 @@ pub enum AddImageResult {
     /// Sent if image data is invalid
     InvalidData,
 
+    /// Sent if decoded image data exceeds max image size set in config
+    TooLarge,
+
+    /// Sent if decoded data isn't an image in a supported format
+    InvalidImage,
+
     /// Sent if successfully added image, contains identifier for the image
     Added(String)
 }
@@ -90,6 +143,30 @@ impl DaemonRequest for AddImage {
     async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
         if let Ok(request) = parse_packet_to_data::<AddImage>(packet) {
             if let Some(_) = listener.core_manager.get_device(&request.serial_number).await {
+                // Size is checked before decoding, every 4 base64 characters encode 3 bytes
+                let padding = request.image_data.bytes().rev().take_while(|x| *x == b'=').count();
+
+                if (request.image_data.len() * 3 / 4).saturating_sub(padding) > listener.config.max_image_size() {
+                    send_packet(handle, packet, &AddImageResult::TooLarge).await.ok();
+                    return;
+                }
+
+                let bytes = if let Ok(bytes) = base64::decode(&request.image_data) {
+                    bytes
+                } else {
+                    send_packet(handle, packet, &AddImageResult::InvalidData).await.ok();
+                    return;
+                };
+
+                let is_image = Reader::new(Cursor::new(bytes)).with_guessed_format()
+                    .map(|reader| reader.format().is_some() && reader.into_dimensions().is_ok())
+                    .unwrap_or(false);
+
+                if !is_image {
+                    send_packet(handle, packet, &AddImageResult::InvalidImage).await.ok();
+                    return;
+                }
+
                 if let Some(identifier) = listener.config.add_image(&request.serial_number, request.image_data).await {
                     send_packet(handle, packet, &AddImageResult::Added(identifier)).await.ok();
                 } else {
@@ -160,4 +237,73 @@ impl DaemonRequest for ListFonts {
             }).await.ok();
         }
     }
-}
\ No newline at end of file
+}
+
+/// Request for measuring how big text would be when rendered with specified font,
+/// text is measured with font fallbacks, default font and default scale from renderer settings like button text is
+#[derive(Serialize, Deserialize)]
+pub struct MeasureText {
+    pub text: String,
+    /// Font of the text, empty means default font
+    pub font: String,
+    /// Scale of the text, zero means default scale
+    pub scale: (f32, f32),
+    /// Extra space in pixels added to advance of each character
+    #[serde(default)]
+    pub letter_spacing: f32,
+    /// Width in pixels the text should fit into, used to fill in `fits` of the response
+    pub max_width: Option<u32>,
+}
+
+/// Response for [MeasureText] request
+#[derive(Serialize, Deserialize)]
+pub enum MeasureTextResult {
+    /// Sent if font wasn't found in font collection
+    FontNotFound,
+
+    /// Sent with dimensions of the text in pixels
+    Measured {
+        width: u32,
+        height: u32,
+        /// If text fits into max width, None if max width wasn't specified
+        fits: Option<bool>,
+    }
+}
+
+impl SocketData for MeasureText {
+    const NAME: &'static str = "measure_text";
+}
+
+impl SocketData for MeasureTextResult {
+    const NAME: &'static str = "measure_text";
+}
+
+#[async_trait]
+impl DaemonRequest for MeasureText {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<MeasureText>(packet) {
+            let core_settings: CoreSettings = listener.config.get_plugin_settings().await.unwrap_or_default();
+            let settings = core_settings.renderer;
+
+            // Renderer would silently use default font for unknown fonts, but measuring reports them
+            let fonts = if request.font.is_empty() || get_font_from_collection(&request.font).is_some() {
+                get_text_fonts(&request.font, &settings)
+            } else {
+                None
+            };
+
+            if let Some(fonts) = fonts {
+                let (scale_x, scale_y) = if request.scale == (0.0, 0.0) { settings.default_scale } else { request.scale };
+                let (width, height) = measure_text(&fonts, &request.text, Scale { x: scale_x, y: scale_y }, request.letter_spacing, &settings);
+
+                send_packet(handle, packet, &MeasureTextResult::Measured {
+                    width,
+                    height,
+                    fits: request.max_width.map(|max| width <= max)
+                }).await.ok();
+            } else {
+                send_packet(handle, packet, &MeasureTextResult::FontNotFound).await.ok();
+            }
+        }
+    }
+}
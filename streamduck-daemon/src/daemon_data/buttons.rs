@@ -6,6 +6,7 @@ use streamduck_core::modules::components::UIPathValue;
 use streamduck_core::socket::{check_packet_for_data, parse_packet_to_data, send_packet, SocketData, SocketHandle, SocketPacket};
 use streamduck_core::util::{button_to_raw, make_button_unique};
 use crate::daemon_data::{DaemonListener, DaemonRequest};
+use std::collections::HashMap;
 use std::ops::Deref;
 use streamduck_core::async_trait;
 
@@ -313,6 +314,48 @@ impl DaemonRequest for AddComponent {
     }
 }
 
+/// Request for adding a component onto buttons on many keys at once
+#[derive(Serialize, Deserialize)]
+pub struct AddComponentToKeys {
+    pub serial_number: String,
+    pub keys: Vec<u8>,
+    pub component_name: String,
+}
+
+/// Response of [AddComponentToKeys] request
+#[derive(Serialize, Deserialize)]
+pub enum AddComponentToKeysResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent with keys that component was added to, keys without a button or that already had the component are left out
+    Added(Vec<u8>),
+}
+
+impl SocketData for AddComponentToKeys {
+    const NAME: &'static str = "add_component_to_keys";
+}
+
+impl SocketData for AddComponentToKeysResult {
+    const NAME: &'static str = "add_component_to_keys";
+}
+
+#[async_trait]
+impl DaemonRequest for AddComponentToKeys {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<AddComponentToKeys>(packet) {
+            if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                let wrapped_core = CoreHandle::wrap(device.core);
+
+                let keys = wrapped_core.add_component_to_keys(request.keys, &request.component_name).await;
+                send_packet(handle, packet, &AddComponentToKeysResult::Added(keys)).await.ok();
+            } else {
+                send_packet(handle, packet, &AddComponentToKeysResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
+}
+
 /// Request for adding components onto buttons
 #[derive(Serialize, Deserialize)]
 pub struct GetComponentValues {
@@ -363,6 +406,53 @@ impl DaemonRequest for GetComponentValues {
     }
 }
 
+/// Request for getting values of all components on a button
+#[derive(Serialize, Deserialize)]
+pub struct GetAllComponentValues {
+    pub serial_number: String,
+    pub key: u8,
+}
+
+/// Response of [GetAllComponentValues] request
+#[derive(Serialize, Deserialize)]
+pub enum GetAllComponentValuesResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if button wasn't found
+    NoButton,
+
+    /// Sent if component values were successfully retrieved, mapped by component name
+    Values(HashMap<String, Vec<UIPathValue>>),
+}
+
+impl SocketData for GetAllComponentValues {
+    const NAME: &'static str = "get_all_component_values";
+}
+
+impl SocketData for GetAllComponentValuesResult {
+    const NAME: &'static str = "get_all_component_values";
+}
+
+#[async_trait]
+impl DaemonRequest for GetAllComponentValues {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<GetAllComponentValues>(packet) {
+            if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                let wrapped_core = CoreHandle::wrap(device.core);
+
+                if let Some(values) = wrapped_core.get_all_component_values(request.key).await {
+                    send_packet(handle, packet, &GetAllComponentValuesResult::Values(values)).await.ok();
+                } else {
+                    send_packet(handle, packet, &GetAllComponentValuesResult::NoButton).await.ok();
+                }
+            } else {
+                send_packet(handle, packet, &GetAllComponentValuesResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
+}
+
 /// Request for adding element into component value array
 #[derive(Serialize, Deserialize)]
 pub struct AddComponentValue {
@@ -514,6 +604,106 @@ impl DaemonRequest for SetComponentValue {
     }
 }
 
+/// Request for setting same component value on buttons on many keys at once
+#[derive(Serialize, Deserialize)]
+pub struct SetComponentValueForKeys {
+    pub serial_number: String,
+    pub keys: Vec<u8>,
+    pub component_name: String,
+    pub value: UIPathValue,
+}
+
+/// Response of [SetComponentValueForKeys] request
+#[derive(Serialize, Deserialize)]
+pub enum SetComponentValueForKeysResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent with keys that value was set on, keys without a button or the component are left out
+    Set(Vec<u8>),
+}
+
+impl SocketData for SetComponentValueForKeys {
+    const NAME: &'static str = "set_component_value_for_keys";
+}
+
+impl SocketData for SetComponentValueForKeysResult {
+    const NAME: &'static str = "set_component_value_for_keys";
+}
+
+#[async_trait]
+impl DaemonRequest for SetComponentValueForKeys {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<SetComponentValueForKeys>(packet) {
+            if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                let wrapped_core = CoreHandle::wrap(device.core);
+
+                let keys = wrapped_core.set_component_value_for_keys(request.keys, &request.component_name, request.value).await;
+
+                if !keys.is_empty() {
+                    listener.config.sync_images(&request.serial_number).await;
+                }
+
+                send_packet(handle, packet, &SetComponentValueForKeysResult::Set(keys)).await.ok();
+            } else {
+                send_packet(handle, packet, &SetComponentValueForKeysResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
+}
+
+/// Request for setting multiple component values at once
+#[derive(Serialize, Deserialize)]
+pub struct SetComponentValues {
+    pub serial_number: String,
+    pub key: u8,
+    pub component_name: String,
+    pub values: Vec<UIPathValue>,
+}
+
+/// Response of [SetComponentValues] request
+#[derive(Serialize, Deserialize)]
+pub enum SetComponentValuesResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if none of the values were set
+    FailedToSet,
+
+    /// Sent if at least one of the values was set, contains success state for each value in order they were provided
+    Set(Vec<bool>),
+}
+
+impl SocketData for SetComponentValues {
+    const NAME: &'static str = "set_component_values";
+}
+
+impl SocketData for SetComponentValuesResult {
+    const NAME: &'static str = "set_component_values";
+}
+
+#[async_trait]
+impl DaemonRequest for SetComponentValues {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<SetComponentValues>(packet) {
+            if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                let wrapped_core = CoreHandle::wrap(device.core);
+
+                let results = wrapped_core.set_component_values_by_paths(request.key, &request.component_name, request.values).await;
+
+                if results.contains(&true) {
+                    listener.config.sync_images(&request.serial_number).await;
+                    send_packet(handle, packet, &SetComponentValuesResult::Set(results)).await.ok();
+                } else {
+                    send_packet(handle, packet, &SetComponentValuesResult::FailedToSet).await.ok();
+                }
+            } else {
+                send_packet(handle, packet, &SetComponentValuesResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
+}
+
 /// Request for adding components onto buttons
 #[derive(Serialize, Deserialize)]
 pub struct RemoveComponent {
@@ -588,6 +778,32 @@ impl DaemonRequest for ClipboardStatusResult {
 }
 
 
+/// Request for getting button that's currently in clipboard
+#[derive(Serialize, Deserialize)]
+pub enum GetClipboardButtonResult {
+    /// Sent if clipboard is empty
+    Empty,
+
+    /// Sent if clipboard has a button, contains the button
+    Button(Button),
+}
+
+impl SocketData for GetClipboardButtonResult {
+    const NAME: &'static str = "get_clipboard_button";
+}
+
+#[async_trait]
+impl DaemonRequest for GetClipboardButtonResult {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if check_packet_for_data::<GetClipboardButtonResult>(packet) {
+            let lock = listener.clipboard.lock().await;
+
+            send_packet(handle, packet, &if let Some(button) = lock.as_ref() { GetClipboardButtonResult::Button(button.clone()) } else { GetClipboardButtonResult::Empty }).await.ok();
+        }
+    }
+}
+
+
 /// Request to copy a button
 #[derive(Serialize, Deserialize)]
 pub struct CopyButton {
@@ -687,4 +903,165 @@ impl DaemonRequest for PasteButton {
             }
         }
     }
-}
\ No newline at end of file
+}
+/// Request for listing names of all button templates
+#[derive(Serialize, Deserialize)]
+pub struct ListButtonTemplates {
+    pub templates: Vec<String>
+}
+
+impl SocketData for ListButtonTemplates {
+    const NAME: &'static str = "list_button_templates";
+}
+
+#[async_trait]
+impl DaemonRequest for ListButtonTemplates {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if check_packet_for_data::<ListButtonTemplates>(packet) {
+            send_packet(handle, packet, &ListButtonTemplates {
+                templates: listener.config.list_button_templates().await
+            }).await.ok();
+        }
+    }
+}
+
+/// Request for saving existing button as a template
+#[derive(Serialize, Deserialize)]
+pub struct SaveButtonAsTemplate {
+    pub serial_number: String,
+    pub key: u8,
+    pub template_name: String,
+}
+
+/// Response of [SaveButtonAsTemplate] request
+#[derive(Serialize, Deserialize)]
+pub enum SaveButtonAsTemplateResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if there's no button to save
+    NoButton,
+
+    /// Sent if successfully saved the template
+    Saved
+}
+
+impl SocketData for SaveButtonAsTemplate {
+    const NAME: &'static str = "save_button_as_template";
+}
+
+impl SocketData for SaveButtonAsTemplateResult {
+    const NAME: &'static str = "save_button_as_template";
+}
+
+#[async_trait]
+impl DaemonRequest for SaveButtonAsTemplate {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<SaveButtonAsTemplate>(packet) {
+            if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                let wrapped_core = CoreHandle::wrap(device.core);
+
+                if let Some(button) = wrapped_core.get_button(request.key).await {
+                    listener.config.set_button_template(&request.template_name, button_to_raw(&button).await).await;
+                    send_packet(handle, packet, &SaveButtonAsTemplateResult::Saved).await.ok();
+                } else {
+                    send_packet(handle, packet, &SaveButtonAsTemplateResult::NoButton).await.ok();
+                }
+            } else {
+                send_packet(handle, packet, &SaveButtonAsTemplateResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
+}
+
+/// Request for removing a button template
+#[derive(Serialize, Deserialize)]
+pub struct RemoveButtonTemplate {
+    pub template_name: String,
+}
+
+/// Response of [RemoveButtonTemplate] request
+#[derive(Serialize, Deserialize)]
+pub enum RemoveButtonTemplateResult {
+    /// Sent if template wasn't found
+    TemplateNotFound,
+
+    /// Sent if successfully removed the template
+    Removed
+}
+
+impl SocketData for RemoveButtonTemplate {
+    const NAME: &'static str = "remove_button_template";
+}
+
+impl SocketData for RemoveButtonTemplateResult {
+    const NAME: &'static str = "remove_button_template";
+}
+
+#[async_trait]
+impl DaemonRequest for RemoveButtonTemplate {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<RemoveButtonTemplate>(packet) {
+            if listener.config.remove_button_template(&request.template_name).await {
+                send_packet(handle, packet, &RemoveButtonTemplateResult::Removed).await.ok();
+            } else {
+                send_packet(handle, packet, &RemoveButtonTemplateResult::TemplateNotFound).await.ok();
+            }
+        }
+    }
+}
+
+/// Request for adding a new button from specified template
+#[derive(Serialize, Deserialize)]
+pub struct NewButtonFromTemplate {
+    pub serial_number: String,
+    pub key: u8,
+    pub template_name: String,
+}
+
+/// Response of [NewButtonFromTemplate] request
+#[derive(Serialize, Deserialize)]
+pub enum NewButtonFromTemplateResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if template wasn't found
+    TemplateNotFound,
+
+    /// Sent if button failed to be created on specified spot
+    FailedToCreate,
+
+    /// Sent if button was successfully created
+    Created,
+}
+
+impl SocketData for NewButtonFromTemplate {
+    const NAME: &'static str = "new_button_from_template";
+}
+
+impl SocketData for NewButtonFromTemplateResult {
+    const NAME: &'static str = "new_button_from_template";
+}
+
+#[async_trait]
+impl DaemonRequest for NewButtonFromTemplate {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<NewButtonFromTemplate>(packet) {
+            if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                let wrapped_core = CoreHandle::wrap(device.core);
+
+                if let Some(template) = listener.config.get_button_template(&request.template_name).await {
+                    if wrapped_core.paste_button(request.key, &template).await {
+                        send_packet(handle, packet, &NewButtonFromTemplateResult::Created).await.ok();
+                    } else {
+                        send_packet(handle, packet, &NewButtonFromTemplateResult::FailedToCreate).await.ok();
+                    }
+                } else {
+                    send_packet(handle, packet, &NewButtonFromTemplateResult::TemplateNotFound).await.ok();
+                }
+            } else {
+                send_packet(handle, packet, &NewButtonFromTemplateResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
+}
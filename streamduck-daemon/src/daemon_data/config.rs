@@ -1,11 +1,12 @@
 //! Requests related to configs
 use std::io::Read;
 use std::ops::Deref;
+use std::path::PathBuf;
 use flate2::Compression;
 use flate2::read::GzDecoder;
 use flate2::write::GzEncoder;
 use serde::{Deserialize, Serialize};
-use streamduck_core::config::{ConfigError, DeviceConfig};
+use streamduck_core::config::{ConfigError, DeviceBundle, DeviceConfig, DeviceConfigFile};
 use streamduck_core::socket::{check_packet_for_data, parse_packet_to_data, send_packet, SocketData, SocketHandle, SocketPacket};
 use streamduck_core::util::make_panel_unique;
 use crate::daemon_data::{DaemonListener, DaemonRequest};
@@ -55,6 +56,37 @@ impl DaemonRequest for ReloadDeviceConfigsResult {
     }
 }
 
+/// Request for listing device configs that are stored on filesystem
+#[derive(Serialize, Deserialize)]
+pub enum ListDeviceConfigsResult {
+    /// Sent if error happened while reading config folder
+    ConfigError,
+
+    /// Sent if successfully read config folder
+    Configs(Vec<DeviceConfigFile>),
+}
+
+impl SocketData for ListDeviceConfigsResult {
+    const NAME: &'static str = "list_device_configs";
+}
+
+#[async_trait]
+impl DaemonRequest for ListDeviceConfigsResult {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if check_packet_for_data::<ListDeviceConfigsResult>(packet) {
+            match listener.config.list_device_config_files().await {
+                Ok(configs) => {
+                    send_packet(handle, packet, &ListDeviceConfigsResult::Configs(configs)).await.ok();
+                }
+                Err(err) => {
+                    log::error!("Error encountered while listing configs: {:?}", err);
+                    send_packet(handle, packet, &ListDeviceConfigsResult::ConfigError).await.ok();
+                }
+            }
+        }
+    }
+}
+
 /// Request for reloading device config for specific device
 #[derive(Serialize, Deserialize)]
 pub struct ReloadDeviceConfig {
@@ -366,4 +398,289 @@ impl DaemonRequest for ImportDeviceConfig {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Request for exporting device config as a bundle that can be imported on another machine
+#[derive(Serialize, Deserialize)]
+pub struct ExportDeviceBundle {
+    pub serial_number: String,
+}
+
+/// Response of [ExportDeviceBundle] request
+#[derive(Serialize, Deserialize)]
+pub enum ExportDeviceBundleResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if error happened during compression
+    FailedToCompress,
+
+    /// Sent if successfully exported
+    Exported(String),
+}
+
+impl SocketData for ExportDeviceBundle {
+    const NAME: &'static str = "export_device_bundle";
+}
+
+impl SocketData for ExportDeviceBundleResult {
+    const NAME: &'static str = "export_device_bundle";
+}
+
+#[async_trait]
+impl DaemonRequest for ExportDeviceBundle {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<ExportDeviceBundle>(packet) {
+            if let Some(config) = listener.config.get_device_config(&request.serial_number).await {
+                let bundle = DeviceBundle::from_config(config.read().await.deref());
+                let bundle = serde_json::to_string(&bundle).unwrap();
+
+                // Compressing data
+                let mut encoder = GzEncoder::new(vec![], Compression::default());
+                write!(encoder, "{}", bundle).ok();
+
+                if let Ok(byte_array) = encoder.finish() {
+                    send_packet(handle, packet, &ExportDeviceBundleResult::Exported(base64::encode(byte_array))).await.ok();
+                } else {
+                    send_packet(handle, packet, &ExportDeviceBundleResult::FailedToCompress).await.ok();
+                }
+            } else {
+                send_packet(handle, packet, &ExportDeviceBundleResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
+}
+
+/// Request for importing bundle exported with [ExportDeviceBundle] into specific device
+#[derive(Serialize, Deserialize)]
+pub struct ImportDeviceBundle {
+    pub serial_number: String,
+    pub bundle: String,
+}
+
+/// Response of [ImportDeviceBundle] request
+#[derive(Serialize, Deserialize)]
+pub enum ImportDeviceBundleResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if bundle was invalid
+    InvalidBundle,
+
+    /// Sent if config failed to save
+    FailedToSave,
+
+    /// Sent if successfully imported, contains fonts used by the bundle that aren't installed
+    Imported {
+        missing_fonts: Vec<String>
+    },
+}
+
+impl SocketData for ImportDeviceBundle {
+    const NAME: &'static str = "import_device_bundle";
+}
+
+impl SocketData for ImportDeviceBundleResult {
+    const NAME: &'static str = "import_device_bundle";
+}
+
+#[async_trait]
+impl DaemonRequest for ImportDeviceBundle {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<ImportDeviceBundle>(packet) {
+            let bundle = base64::decode(&request.bundle).ok()
+                .and_then(|byte_array| {
+                    let mut decoder = GzDecoder::new(&byte_array[..]);
+                    let mut bundle = String::new();
+
+                    decoder.read_to_string(&mut bundle).ok()?;
+                    serde_json::from_str::<DeviceBundle>(&bundle).ok()
+                });
+
+            if let Some(bundle) = bundle {
+                if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                    let missing_fonts = bundle.missing_fonts();
+                    let mut config = bundle.config;
+
+                    config.serial = device.serial.clone();
+                    config.vid = device.vid;
+                    config.pid = device.pid;
+
+                    listener.config.set_device_config(&request.serial_number, config.clone()).await;
+
+                    match listener.config.save_device_config(&request.serial_number).await {
+                        Ok(_) => {
+                            listener.config.sync_images(&request.serial_number).await;
+
+                            let wrapped_core = CoreHandle::wrap(device.core);
+
+                            wrapped_core.reset_stack(make_panel_unique(config.layout)).await;
+                            wrapped_core.set_brightness(config.brightness).await;
+
+                            if !missing_fonts.is_empty() {
+                                log::warn!("Imported bundle for {} uses fonts that aren't installed: {}", request.serial_number, missing_fonts.join(", "));
+                            }
+
+                            send_packet(handle, packet, &ImportDeviceBundleResult::Imported {
+                                missing_fonts
+                            }).await.ok();
+                        }
+
+                        Err(err) => {
+                            match err {
+                                ConfigError::IoError(_) | ConfigError::ParseError(_) => {
+                                    send_packet(handle, packet, &ImportDeviceBundleResult::FailedToSave).await.ok();
+                                }
+
+                                ConfigError::DeviceNotFound => {
+                                    send_packet(handle, packet, &ImportDeviceBundleResult::DeviceNotFound).await.ok();
+                                }
+                            }
+                        }
+                    }
+                } else {
+                    send_packet(handle, packet, &ImportDeviceBundleResult::DeviceNotFound).await.ok();
+                }
+            } else {
+                send_packet(handle, packet, &ImportDeviceBundleResult::InvalidBundle).await.ok();
+            }
+        }
+    }
+}
+
+/// Request for copying device config of one device onto another device
+#[derive(Serialize, Deserialize)]
+pub struct CloneDeviceConfig {
+    pub from_serial: String,
+    pub to_serial: String,
+    /// If existing config of the target device should be overwritten
+    pub force: bool,
+}
+
+/// Response of [CloneDeviceConfig] request
+#[derive(Serialize, Deserialize)]
+pub enum CloneDeviceConfigResult {
+    /// Sent if source device config wasn't found
+    SourceNotFound,
+
+    /// Sent if target device already has a config and force wasn't set
+    TargetHasConfig,
+
+    /// Sent if config failed to save
+    FailedToSave,
+
+    /// Sent if successfully cloned, contains if config was also loaded onto connected target device
+    Cloned {
+        loaded: bool
+    },
+}
+
+impl SocketData for CloneDeviceConfig {
+    const NAME: &'static str = "clone_device_config";
+}
+
+impl SocketData for CloneDeviceConfigResult {
+    const NAME: &'static str = "clone_device_config";
+}
+
+#[async_trait]
+impl DaemonRequest for CloneDeviceConfig {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<CloneDeviceConfig>(packet) {
+            let mut config = if let Some(config) = listener.config.get_device_config(&request.from_serial).await {
+                config.read().await.clone()
+            } else {
+                send_packet(handle, packet, &CloneDeviceConfigResult::SourceNotFound).await.ok();
+                return;
+            };
+
+            if !request.force && listener.config.get_device_config(&request.to_serial).await.is_some() {
+                send_packet(handle, packet, &CloneDeviceConfigResult::TargetHasConfig).await.ok();
+                return;
+            }
+
+            let device = listener.core_manager.get_device(&request.to_serial).await;
+
+            config.serial = request.to_serial.clone();
+            config.commit_time = None;
+            config.dirty_state = false;
+
+            if let Some(device) = &device {
+                config.vid = device.vid;
+                config.pid = device.pid;
+            }
+
+            listener.config.set_device_config(&request.to_serial, config.clone()).await;
+
+            match listener.config.save_device_config(&request.to_serial).await {
+                Ok(_) => {
+                    let loaded = if let Some(device) = device {
+                        listener.config.sync_images(&request.to_serial).await;
+
+                        let wrapped_core = CoreHandle::wrap(device.core);
+
+                        wrapped_core.reset_stack(make_panel_unique(config.layout)).await;
+                        wrapped_core.set_brightness(config.brightness).await;
+
+                        true
+                    } else {
+                        false
+                    };
+
+                    send_packet(handle, packet, &CloneDeviceConfigResult::Cloned {
+                        loaded
+                    }).await.ok();
+                }
+
+                Err(_) => {
+                    send_packet(handle, packet, &CloneDeviceConfigResult::FailedToSave).await.ok();
+                }
+            }
+        }
+    }
+}
+
+/// Request for getting resolved paths of config file and folders used by the daemon
+#[derive(Serialize, Deserialize)]
+pub struct GetConfigPaths {
+    /// Path of the loaded config file
+    pub config_file: PathBuf,
+    /// Config folder
+    pub config_dir: PathBuf,
+    /// Data folder
+    pub data_dir: PathBuf,
+    /// Folder with device configs
+    pub device_config_path: PathBuf,
+    /// Folder with plugins
+    pub plugin_path: PathBuf,
+    /// Folder with fonts
+    pub font_path: PathBuf,
+    /// Plugin settings file
+    pub plugin_settings_path: PathBuf,
+    /// Button templates file
+    pub button_templates_path: PathBuf,
+}
+
+impl SocketData for GetConfigPaths {
+    const NAME: &'static str = "get_config_paths";
+}
+
+#[async_trait]
+impl DaemonRequest for GetConfigPaths {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if check_packet_for_data::<GetConfigPaths>(packet) {
+            let config = &listener.config;
+
+            send_packet(handle, packet, &GetConfigPaths {
+                config_file: config.config_path(),
+                config_dir: config.config_dir().clone(),
+                data_dir: config.data_dir().clone(),
+                device_config_path: config.device_config_path(),
+                plugin_path: config.plugin_path(),
+                font_path: config.font_path(),
+                plugin_settings_path: config.plugin_settings_path(),
+                button_templates_path: config.button_templates_path()
+            }).await.ok();
+        }
+    }
+}
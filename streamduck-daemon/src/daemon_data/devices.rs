@@ -2,9 +2,13 @@
 use serde::{Deserialize, Serialize};
 use strum_macros::Display;
 use streamduck_core::core::CoreHandle;
+use streamduck_core::modules::events::SDGlobalEvent;
+use streamduck_core::thread::rendering::{RenderCacheDump, RenderStats};
 use streamduck_core::socket::{check_packet_for_data, parse_packet_to_data, send_packet, SocketData, SocketHandle, SocketPacket};
 use crate::daemon_data::{DaemonListener, DaemonRequest};
 use streamduck_core::streamdeck;
+use streamduck_core::streamdeck::Kind;
+use streamduck_core::util::get_key_grid;
 use streamduck_core::async_trait;
 
 /// Request for getting device list
@@ -25,22 +29,17 @@ impl DaemonRequest for ListDevices {
 
             // Connected devices
             for device in listener.core_manager.list_added_devices().await.values() {
-                devices.push(Device {
-                    device_type: DeviceType::from_pid(device.pid),
-                    serial_number: device.serial.clone(),
-                    managed: true,
-                    online: !device.core.is_closed().await
-                })
+                let mut entry = Device::new(device.pid, device.serial.clone(), true, !device.core.is_closed().await);
+                let device_config = device.core.device_config.read().await;
+                entry.locked = device_config.locked;
+                entry.nickname = device_config.nickname.clone();
+                drop(device_config);
+                devices.push(entry)
             }
 
             // Available devices
             for (_, pid, serial) in listener.core_manager.list_available_devices().await {
-                devices.push(Device {
-                    device_type: DeviceType::from_pid(pid),
-                    serial_number: serial,
-                    managed: false,
-                    online: true
-                })
+                devices.push(Device::new(pid, serial, false, true))
             }
 
             send_packet(handle, &packet, &ListDevices {
@@ -61,10 +60,94 @@ pub struct Device {
     pub managed: bool,
     /// If the device is online
     pub online: bool,
+    /// Human-readable model name
+    #[serde(default)]
+    pub model: String,
+    /// Amount of key rows on the device
+    #[serde(default)]
+    pub rows: u8,
+    /// Amount of key columns on the device
+    #[serde(default)]
+    pub columns: u8,
+    /// Resolution of button images
+    #[serde(default)]
+    pub image_size: (usize, usize),
+    /// If device rejects changes to its layout
+    #[serde(default)]
+    pub locked: bool,
+    /// User assigned name of the device, empty if device doesn't have one
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub nickname: String,
+}
+
+impl Device {
+    /// Creates device struct, filling in layout information from PID of the device
+    pub fn new(pid: u16, serial_number: String, managed: bool, online: bool) -> Device {
+        let layout = DeviceLayout::from_type(DeviceType::from_pid(pid));
+
+        Device {
+            device_type: layout.device_type,
+            serial_number,
+            managed,
+            online,
+            model: layout.model,
+            rows: layout.rows,
+            columns: layout.columns,
+            image_size: layout.image_size,
+            locked: false,
+            nickname: String::new()
+        }
+    }
+}
+
+/// Layout information of a device kind
+#[derive(Serialize, Deserialize)]
+pub struct DeviceLayout {
+    /// Device type
+    pub device_type: DeviceType,
+    /// Human-readable model name
+    pub model: String,
+    /// Amount of keys on the device
+    pub key_count: u8,
+    /// Amount of key rows on the device
+    pub rows: u8,
+    /// Amount of key columns on the device
+    pub columns: u8,
+    /// Resolution of button images
+    pub image_size: (usize, usize),
+}
+
+impl DeviceLayout {
+    /// Gets layout information for specified device type
+    pub fn from_type(device_type: DeviceType) -> DeviceLayout {
+        let model = device_type.model().to_string();
+
+        if let Some(kind) = device_type.kind() {
+            let (rows, columns) = get_key_grid(&kind);
+
+            DeviceLayout {
+                device_type,
+                model,
+                key_count: kind.keys(),
+                rows,
+                columns,
+                image_size: kind.image_size()
+            }
+        } else {
+            DeviceLayout {
+                device_type,
+                model,
+                key_count: 0,
+                rows: 0,
+                columns: 0,
+                image_size: (0, 0)
+            }
+        }
+    }
 }
 
 /// Streamdeck types
-#[derive(Serialize, Deserialize, Display)]
+#[derive(Serialize, Deserialize, Display, Clone, Copy)]
 pub enum DeviceType {
     Unknown,
     Mini,
@@ -86,6 +169,261 @@ impl DeviceType {
             _ => DeviceType::Unknown,
         }
     }
+
+    /// Gets device kind that's used by streamdeck library
+    pub fn kind(&self) -> Option<Kind> {
+        match self {
+            DeviceType::Unknown => None,
+            DeviceType::Mini => Some(Kind::Mini),
+            DeviceType::Original => Some(Kind::Original),
+            DeviceType::OriginalV2 => Some(Kind::OriginalV2),
+            DeviceType::XL => Some(Kind::Xl),
+            DeviceType::MK2 => Some(Kind::Mk2),
+        }
+    }
+
+    /// Gets human-readable model name of the device type
+    pub fn model(&self) -> &'static str {
+        match self {
+            DeviceType::Unknown => "Unknown Device",
+            DeviceType::Mini => "Stream Deck Mini",
+            DeviceType::Original => "Stream Deck",
+            DeviceType::OriginalV2 => "Stream Deck V2",
+            DeviceType::XL => "Stream Deck XL",
+            DeviceType::MK2 => "Stream Deck MK.2",
+        }
+    }
+
+    /// Lists all known device types
+    pub fn all() -> Vec<DeviceType> {
+        vec![DeviceType::Mini, DeviceType::Original, DeviceType::OriginalV2, DeviceType::XL, DeviceType::MK2]
+    }
+}
+
+/// Request for getting layouts of all supported device kinds
+#[derive(Serialize, Deserialize)]
+pub struct ListDeviceKinds {
+    pub kinds: Vec<DeviceLayout>
+}
+
+impl SocketData for ListDeviceKinds {
+    const NAME: &'static str = "list_device_kinds";
+}
+
+#[async_trait]
+impl DaemonRequest for ListDeviceKinds {
+    async fn process(_listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if check_packet_for_data::<ListDeviceKinds>(&packet) {
+            send_packet(handle, &packet, &ListDeviceKinds {
+                kinds: DeviceType::all().into_iter()
+                    .map(DeviceLayout::from_type)
+                    .collect()
+            }).await.ok();
+        }
+    }
+}
+
+/// Request for getting layout of a device
+#[derive(Serialize, Deserialize)]
+pub struct GetDeviceLayout {
+    pub serial_number: String
+}
+
+/// Response of [GetDeviceLayout] request
+#[derive(Serialize, Deserialize)]
+pub enum GetDeviceLayoutResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if device was found
+    Layout(DeviceLayout)
+}
+
+impl SocketData for GetDeviceLayout {
+    const NAME: &'static str = "get_device_layout";
+}
+
+impl SocketData for GetDeviceLayoutResult {
+    const NAME: &'static str = "get_device_layout";
+}
+
+#[async_trait]
+impl DaemonRequest for GetDeviceLayout {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<GetDeviceLayout>(packet) {
+            let pid = if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                Some(device.pid)
+            } else {
+                listener.core_manager.list_available_devices().await.into_iter()
+                    .find(|(_, _, serial)| *serial == request.serial_number)
+                    .map(|(_, pid, _)| pid)
+            };
+
+            if let Some(pid) = pid {
+                send_packet(handle, packet, &GetDeviceLayoutResult::Layout(DeviceLayout::from_type(DeviceType::from_pid(pid)))).await.ok();
+            } else {
+                send_packet(handle, packet, &GetDeviceLayoutResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
+}
+
+/// Request for getting render statistics of a device
+#[derive(Serialize, Deserialize)]
+pub struct GetRenderStats {
+    pub serial_number: String
+}
+
+/// Response of [GetRenderStats] request
+#[derive(Serialize, Deserialize)]
+pub enum GetRenderStatsResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if device was found
+    Stats(RenderStats)
+}
+
+impl SocketData for GetRenderStats {
+    const NAME: &'static str = "get_render_stats";
+}
+
+impl SocketData for GetRenderStatsResult {
+    const NAME: &'static str = "get_render_stats";
+}
+
+#[async_trait]
+impl DaemonRequest for GetRenderStats {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<GetRenderStats>(packet) {
+            if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                send_packet(handle, packet, &GetRenderStatsResult::Stats(device.core.render_stats().await)).await.ok();
+            } else {
+                send_packet(handle, packet, &GetRenderStatsResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
+}
+
+/// Request for getting contents of render cache of a device, meant for debugging render hashes
+#[derive(Serialize, Deserialize)]
+pub struct DumpRenderCache {
+    pub serial_number: String
+}
+
+/// Response of [DumpRenderCache] request
+#[derive(Serialize, Deserialize)]
+pub enum DumpRenderCacheResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if device thread didn't respond
+    Unavailable,
+
+    /// Sent if device thread responded with cache contents
+    Dump(RenderCacheDump)
+}
+
+impl SocketData for DumpRenderCache {
+    const NAME: &'static str = "dump_render_cache";
+}
+
+impl SocketData for DumpRenderCacheResult {
+    const NAME: &'static str = "dump_render_cache";
+}
+
+#[async_trait]
+impl DaemonRequest for DumpRenderCache {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<DumpRenderCache>(packet) {
+            if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                if let Some(dump) = device.core.dump_render_cache().await {
+                    send_packet(handle, packet, &DumpRenderCacheResult::Dump(dump)).await.ok();
+                } else {
+                    send_packet(handle, packet, &DumpRenderCacheResult::Unavailable).await.ok();
+                }
+            } else {
+                send_packet(handle, packet, &DumpRenderCacheResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
+}
+
+/// Request for getting keys that are currently held down on a device
+#[derive(Serialize, Deserialize)]
+pub struct GetPressedKeys {
+    pub serial_number: String
+}
+
+/// Response of [GetPressedKeys] request
+#[derive(Serialize, Deserialize)]
+pub enum GetPressedKeysResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if device was found, keys are sorted by key index
+    Keys(Vec<u8>)
+}
+
+impl SocketData for GetPressedKeys {
+    const NAME: &'static str = "get_pressed_keys";
+}
+
+impl SocketData for GetPressedKeysResult {
+    const NAME: &'static str = "get_pressed_keys";
+}
+
+#[async_trait]
+impl DaemonRequest for GetPressedKeys {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<GetPressedKeys>(packet) {
+            if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                let wrapped_core = CoreHandle::wrap(device.core);
+                send_packet(handle, packet, &GetPressedKeysResult::Keys(wrapped_core.currently_pressed_keys().await)).await.ok();
+            } else {
+                send_packet(handle, packet, &GetPressedKeysResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
+}
+
+/// Request for getting recent events of a device, event log has to be enabled with event_log_size in daemon config
+#[derive(Serialize, Deserialize)]
+pub struct GetRecentEvents {
+    pub serial_number: String,
+    /// Max amount of events to return, 0 returns all logged events
+    pub limit: usize
+}
+
+/// Response of [GetRecentEvents] request
+#[derive(Serialize, Deserialize)]
+pub enum GetRecentEventsResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if device was found, events are ordered from oldest to newest
+    Events(Vec<SDGlobalEvent>)
+}
+
+impl SocketData for GetRecentEvents {
+    const NAME: &'static str = "get_recent_events";
+}
+
+impl SocketData for GetRecentEventsResult {
+    const NAME: &'static str = "get_recent_events";
+}
+
+#[async_trait]
+impl DaemonRequest for GetRecentEvents {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<GetRecentEvents>(packet) {
+            if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                send_packet(handle, packet, &GetRecentEventsResult::Events(device.core.recent_events(request.limit).await)).await.ok();
+            } else {
+                send_packet(handle, packet, &GetRecentEventsResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
 }
 
 /// Request for getting a device
@@ -117,12 +455,13 @@ impl DaemonRequest for GetDevice {
     async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
         if let Ok(get_request) = parse_packet_to_data::<GetDevice>(&packet) {
             let result = if let Some(device) = listener.core_manager.get_device(&get_request.serial_number).await {
-                GetDeviceResult::Found(Device {
-                    device_type: DeviceType::from_pid(device.pid),
-                    serial_number: device.serial,
-                    managed: true,
-                    online: !device.core.is_closed().await
-                })
+                let mut entry = Device::new(device.pid, device.serial, true, !device.core.is_closed().await);
+                let device_config = device.core.device_config.read().await;
+                entry.locked = device_config.locked;
+                entry.nickname = device_config.nickname.clone();
+                drop(device_config);
+
+                GetDeviceResult::Found(entry)
             } else {
                 GetDeviceResult::NotFound
             };
@@ -231,7 +570,7 @@ pub enum GetBrightnessResult {
     /// Sent if device wasn't found
     DeviceNotFound,
 
-    /// Sent if brightness was successfully set
+    /// Sent if device was found, contains brightness that was last set to the device
     Brightness(u8),
 }
 
@@ -248,9 +587,9 @@ impl DaemonRequest for GetBrightness {
     async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
         if let Ok(request) = parse_packet_to_data::<GetBrightness>(packet) {
             if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
-                let brightness = device.core.device_config.read().await.brightness;
+                let wrapped_core = CoreHandle::wrap(device.core);
 
-                send_packet(handle, packet, &GetBrightnessResult::Brightness(brightness)).await.ok();
+                send_packet(handle, packet, &GetBrightnessResult::Brightness(wrapped_core.get_brightness().await)).await.ok();
             } else {
                 send_packet(handle, packet, &GetBrightnessResult::DeviceNotFound).await.ok();
             }
@@ -298,4 +637,126 @@ impl DaemonRequest for SetBrightness {
             }
         }
     }
-}
\ No newline at end of file
+}
+/// Request for locking or unlocking a device, locked devices reject changes to their layout
+#[derive(Serialize, Deserialize)]
+pub struct SetDeviceLocked {
+    pub serial_number: String,
+    pub locked: bool,
+}
+
+/// Response of [SetDeviceLocked] request
+#[derive(Serialize, Deserialize)]
+pub enum SetDeviceLockedResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if lock state was successfully set
+    Set,
+}
+
+impl SocketData for SetDeviceLocked {
+    const NAME: &'static str = "set_device_locked";
+}
+
+impl SocketData for SetDeviceLockedResult {
+    const NAME: &'static str = "set_device_locked";
+}
+
+#[async_trait]
+impl DaemonRequest for SetDeviceLocked {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<SetDeviceLocked>(packet) {
+            if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                let wrapped_core = CoreHandle::wrap(device.core);
+                wrapped_core.set_locked(request.locked).await;
+
+                send_packet(handle, packet, &SetDeviceLockedResult::Set).await.ok();
+            } else {
+                send_packet(handle, packet, &SetDeviceLockedResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
+}
+
+/// Request for disabling or enabling render cache of a device
+#[derive(Serialize, Deserialize)]
+pub struct SetRenderCacheDisabled {
+    pub serial_number: String,
+    pub disabled: bool,
+}
+
+/// Response of [SetRenderCacheDisabled] request
+#[derive(Serialize, Deserialize)]
+pub enum SetRenderCacheDisabledResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if setting was successfully set
+    Set,
+}
+
+impl SocketData for SetRenderCacheDisabled {
+    const NAME: &'static str = "set_render_cache_disabled";
+}
+
+impl SocketData for SetRenderCacheDisabledResult {
+    const NAME: &'static str = "set_render_cache_disabled";
+}
+
+#[async_trait]
+impl DaemonRequest for SetRenderCacheDisabled {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<SetRenderCacheDisabled>(packet) {
+            if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                let wrapped_core = CoreHandle::wrap(device.core);
+                wrapped_core.set_render_cache_disabled(request.disabled).await;
+
+                send_packet(handle, packet, &SetRenderCacheDisabledResult::Set).await.ok();
+            } else {
+                send_packet(handle, packet, &SetRenderCacheDisabledResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
+}
+
+/// Request for setting user assigned name of a device
+#[derive(Serialize, Deserialize)]
+pub struct SetDeviceNickname {
+    pub serial_number: String,
+    pub nickname: String,
+}
+
+/// Response of [SetDeviceNickname] request
+#[derive(Serialize, Deserialize)]
+pub enum SetDeviceNicknameResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if nickname was successfully set
+    Set,
+}
+
+impl SocketData for SetDeviceNickname {
+    const NAME: &'static str = "set_device_nickname";
+}
+
+impl SocketData for SetDeviceNicknameResult {
+    const NAME: &'static str = "set_device_nickname";
+}
+
+#[async_trait]
+impl DaemonRequest for SetDeviceNickname {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<SetDeviceNickname>(packet) {
+            if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                let wrapped_core = CoreHandle::wrap(device.core);
+                wrapped_core.set_nickname(&request.nickname).await;
+
+                send_packet(handle, packet, &SetDeviceNicknameResult::Set).await.ok();
+            } else {
+                send_packet(handle, packet, &SetDeviceNicknameResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
+}
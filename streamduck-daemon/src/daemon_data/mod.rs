@@ -8,22 +8,24 @@ pub mod buttons;
 pub mod ops;
 
 use std::sync::{Arc};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
 use tokio::sync::Mutex;
 use streamduck_core::versions::SOCKET_API;
 use streamduck_core::core::manager::CoreManager;
-use streamduck_core::socket::{check_packet_for_data, send_packet, SocketData, SocketHandle, SocketListener, SocketPacket};
+use streamduck_core::socket::{check_packet_for_data, parse_packet_to_data, send_packet, SocketData, SocketHandle, SocketListener, SocketPacket};
 use streamduck_core::modules::ModuleManager;
 use streamduck_core::config::Config;
 use streamduck_core::core::button::Button;
 use streamduck_core::async_trait;
-use crate::daemon_data::assets::{AddImage, ListFonts, ListImages, RemoveImage};
-use crate::daemon_data::buttons::{AddComponent, AddComponentValue, ClearButton, ClipboardStatusResult, CopyButton, GetButton, GetComponentValues, NewButton, NewButtonFromComponent, PasteButton, RemoveComponent, RemoveComponentValue, SetButton, SetComponentValue};
-use crate::daemon_data::config::{ExportDeviceConfig, GetDeviceConfig, ImportDeviceConfig, ReloadDeviceConfig, ReloadDeviceConfigsResult, SaveDeviceConfig, SaveDeviceConfigsResult};
-use crate::daemon_data::devices::{AddDevice, GetBrightness, GetDevice, ListDevices, RemoveDevice, SetBrightness};
-use crate::daemon_data::modules::{AddModuleValue, GetModuleValues, ListComponents, ListModules, RemoveModuleValue, SetModuleValue};
-use crate::daemon_data::ops::{CommitChangesToConfig, DoButtonAction};
-use crate::daemon_data::panels::{DropStackToRoot, ForciblyPopScreen, GetButtonImage, GetButtonImages, GetCurrentScreen, GetStack, GetStackNames, PopScreen, PushScreen, ReplaceScreen, ResetStack};
+use crate::daemon_data::assets::{AddImage, ListFonts, ListImages, MeasureText, RemoveImage};
+use crate::daemon_data::buttons::{AddComponent, AddComponentToKeys, AddComponentValue, ClearButton, ClipboardStatusResult, CopyButton, GetAllComponentValues, GetButton, GetClipboardButtonResult, GetComponentValues, ListButtonTemplates, NewButton, NewButtonFromComponent, NewButtonFromTemplate, PasteButton, RemoveButtonTemplate, RemoveComponent, RemoveComponentValue, SaveButtonAsTemplate, SetButton, SetComponentValue, SetComponentValueForKeys, SetComponentValues};
+use crate::daemon_data::config::{CloneDeviceConfig, ExportDeviceBundle, ExportDeviceConfig, GetConfigPaths, GetDeviceConfig, ImportDeviceBundle, ImportDeviceConfig, ListDeviceConfigsResult, ReloadDeviceConfig, ReloadDeviceConfigsResult, SaveDeviceConfig, SaveDeviceConfigsResult};
+use crate::daemon_data::devices::{AddDevice, DumpRenderCache, GetBrightness, GetDevice, GetDeviceLayout, GetPressedKeys, GetRecentEvents, GetRenderStats, ListDeviceKinds, ListDevices, RemoveDevice, SetBrightness, SetDeviceLocked, SetDeviceNickname, SetRenderCacheDisabled};
+use crate::daemon_data::modules::{AddModuleValue, GetComponentDefinition, GetComponentOwner, GetModuleCompatibility, GetModuleValues, ListComponents, ListModules, ListModuleStatus, RemoveModuleValue, SetModuleValue};
+use crate::daemon_data::ops::{CommitAllChanges, CommitChangesToConfig, DoButtonAction};
+use crate::daemon_data::panels::{DropStackToRoot, ExportScreen, ForciblyPopScreen, GetButtonImage, GetButtonImages, GetComponentRender, GetCurrentScreen, GetDeckComposite, GetLastRendered, GetStack, GetStackNames, ImportScreen, ListExampleLayouts, LoadExampleLayout, PopScreen, PushScreen, RenderPreview, ReplaceScreen, ResetStack};
 
 /// Listener for daemon types
 pub struct DaemonListener {
@@ -31,6 +33,8 @@ pub struct DaemonListener {
     pub module_manager: Arc<ModuleManager>,
     pub config: Arc<Config>,
     pub clipboard: Mutex<Option<Button>>,
+    /// Time when daemon was started
+    pub start_time: Instant,
 }
 
 #[async_trait]
@@ -38,10 +42,18 @@ impl SocketListener for DaemonListener {
     async fn message(&self, socket: SocketHandle<'_>, packet: SocketPacket) {
         // Version
         process_for_type::<SocketAPIVersion>(self,socket, &packet).await;
+        process_for_type::<PingResult>(self, socket, &packet).await;
+        process_for_type::<BatchRequest>(self, socket, &packet).await;
 
         // Device management
         process_for_type::<ListDevices>(self,socket, &packet).await;
+        process_for_type::<ListDeviceKinds>(self, socket, &packet).await;
         process_for_type::<GetDevice>(self,socket, &packet).await;
+        process_for_type::<GetDeviceLayout>(self, socket, &packet).await;
+        process_for_type::<GetRenderStats>(self, socket, &packet).await;
+        process_for_type::<DumpRenderCache>(self, socket, &packet).await;
+        process_for_type::<GetPressedKeys>(self, socket, &packet).await;
+        process_for_type::<GetRecentEvents>(self, socket, &packet).await;
         process_for_type::<AddDevice>(self,socket, &packet).await;
         process_for_type::<RemoveDevice>(self,socket, &packet).await;
 
@@ -52,22 +64,35 @@ impl SocketListener for DaemonListener {
         process_for_type::<SaveDeviceConfig>(self, socket, &packet).await;
 
         process_for_type::<GetDeviceConfig>(self, socket, &packet).await;
+        process_for_type::<ListDeviceConfigsResult>(self, socket, &packet).await;
 
         process_for_type::<ImportDeviceConfig>(self, socket, &packet).await;
+        process_for_type::<ExportDeviceBundle>(self, socket, &packet).await;
+        process_for_type::<ImportDeviceBundle>(self, socket, &packet).await;
+        process_for_type::<CloneDeviceConfig>(self, socket, &packet).await;
         process_for_type::<ExportDeviceConfig>(self, socket, &packet).await;
+        process_for_type::<GetConfigPaths>(self, socket, &packet).await;
 
         process_for_type::<GetBrightness>(self, socket, &packet).await;
         process_for_type::<SetBrightness>(self, socket, &packet).await;
+        process_for_type::<SetDeviceLocked>(self, socket, &packet).await;
+        process_for_type::<SetDeviceNickname>(self, socket, &packet).await;
+        process_for_type::<SetRenderCacheDisabled>(self, socket, &packet).await;
 
         process_for_type::<ListImages>(self, socket, &packet).await;
         process_for_type::<AddImage>(self, socket, &packet).await;
         process_for_type::<RemoveImage>(self, socket, &packet).await;
 
         process_for_type::<ListFonts>(self,socket, &packet).await;
+        process_for_type::<MeasureText>(self, socket, &packet).await;
 
         // Module management
         process_for_type::<ListModules>(self,socket, &packet).await;
+        process_for_type::<ListModuleStatus>(self, socket, &packet).await;
         process_for_type::<ListComponents>(self,socket, &packet).await;
+        process_for_type::<GetComponentDefinition>(self, socket, &packet).await;
+        process_for_type::<GetComponentOwner>(self, socket, &packet).await;
+        process_for_type::<GetModuleCompatibility>(self, socket, &packet).await;
 
         process_for_type::<GetModuleValues>(self,socket, &packet).await;
         process_for_type::<AddModuleValue>(self,socket, &packet).await;
@@ -79,36 +104,54 @@ impl SocketListener for DaemonListener {
         process_for_type::<GetStackNames>(self, socket, &packet).await;
         process_for_type::<GetCurrentScreen>(self, socket, &packet).await;
         process_for_type::<GetButtonImage>(self, socket, &packet).await;
+        process_for_type::<GetComponentRender>(self, socket, &packet).await;
+        process_for_type::<GetLastRendered>(self, socket, &packet).await;
+        process_for_type::<RenderPreview>(self, socket, &packet).await;
         process_for_type::<GetButtonImages>(self, socket, &packet).await;
+        process_for_type::<GetDeckComposite>(self, socket, &packet).await;
 
         process_for_type::<GetButton>(self, socket, &packet).await;
         process_for_type::<SetButton>(self, socket, &packet).await;
         process_for_type::<ClearButton>(self, socket, &packet).await;
 
         process_for_type::<ClipboardStatusResult>(self, socket, &packet).await;
+        process_for_type::<GetClipboardButtonResult>(self, socket, &packet).await;
         process_for_type::<CopyButton>(self, socket, &packet).await;
         process_for_type::<PasteButton>(self, socket, &packet).await;
+        process_for_type::<ListButtonTemplates>(self, socket, &packet).await;
+        process_for_type::<SaveButtonAsTemplate>(self, socket, &packet).await;
+        process_for_type::<RemoveButtonTemplate>(self, socket, &packet).await;
+        process_for_type::<NewButtonFromTemplate>(self, socket, &packet).await;
 
         process_for_type::<NewButton>(self, socket, &packet).await;
         process_for_type::<NewButtonFromComponent>(self, socket, &packet).await;
 
         process_for_type::<AddComponent>(self, socket, &packet).await;
+        process_for_type::<AddComponentToKeys>(self, socket, &packet).await;
 
         process_for_type::<GetComponentValues>(self, socket, &packet).await;
+        process_for_type::<GetAllComponentValues>(self, socket, &packet).await;
         process_for_type::<AddComponentValue>(self, socket, &packet).await;
         process_for_type::<RemoveComponentValue>(self, socket, &packet).await;
         process_for_type::<SetComponentValue>(self, socket, &packet).await;
+        process_for_type::<SetComponentValueForKeys>(self, socket, &packet).await;
+        process_for_type::<SetComponentValues>(self, socket, &packet).await;
 
         process_for_type::<RemoveComponent>(self, socket, &packet).await;
 
         process_for_type::<PushScreen>(self, socket, &packet).await;
+        process_for_type::<ExportScreen>(self, socket, &packet).await;
+        process_for_type::<ImportScreen>(self, socket, &packet).await;
         process_for_type::<PopScreen>(self, socket, &packet).await;
         process_for_type::<ForciblyPopScreen>(self, socket, &packet).await;
         process_for_type::<ReplaceScreen>(self, socket, &packet).await;
         process_for_type::<ResetStack>(self, socket, &packet).await;
+        process_for_type::<ListExampleLayouts>(self, socket, &packet).await;
+        process_for_type::<LoadExampleLayout>(self, socket, &packet).await;
         process_for_type::<DropStackToRoot>(self, socket, &packet).await;
 
         process_for_type::<CommitChangesToConfig>(self, socket, &packet).await;
+        process_for_type::<CommitAllChanges>(self, socket, &packet).await;
 
         process_for_type::<DoButtonAction>(self, socket, &packet).await;
     }
@@ -146,4 +189,149 @@ impl DaemonRequest for SocketAPIVersion {
             }).await.ok();
         }
     }
-}
\ No newline at end of file
+}
+
+/// Request for checking if daemon is alive, doesn't touch any state
+#[derive(Serialize, Deserialize)]
+pub struct PingResult {
+    /// Current time of the daemon in milliseconds since unix epoch
+    pub timestamp: u64,
+    /// Time since daemon was started in milliseconds
+    pub uptime: u64,
+}
+
+impl SocketData for PingResult {
+    const NAME: &'static str = "ping";
+}
+
+#[async_trait]
+impl DaemonRequest for PingResult {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if check_packet_for_data::<PingResult>(&packet) {
+            send_packet(handle, &packet, &PingResult {
+                timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |x| x.as_millis() as u64),
+                uptime: listener.start_time.elapsed().as_millis() as u64
+            }).await.ok();
+        }
+    }
+}
+
+/// Request for processing multiple requests in order within a single round-trip
+#[derive(Serialize, Deserialize)]
+pub struct BatchRequest {
+    pub requests: Vec<BatchItem>
+}
+
+/// Request inside of [BatchRequest]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BatchItem {
+    /// Packet of the request
+    pub packet: SocketPacket,
+    /// If rest of the batch should be skipped when this request fails
+    #[serde(default)]
+    pub critical: bool,
+}
+
+impl BatchItem {
+    /// Creates batch item out of a request
+    pub fn new<T: SocketData + Serialize>(request: &T, critical: bool) -> Result<BatchItem, serde_json::Error> {
+        Ok(BatchItem {
+            packet: SocketPacket {
+                ty: T::NAME.to_string(),
+                requester: None,
+                data: Some(serde_json::to_value(request)?)
+            },
+            critical
+        })
+    }
+
+    /// Creates batch item out of a request that doesn't have any data
+    pub fn without_data<T: SocketData>(critical: bool) -> BatchItem {
+        BatchItem {
+            packet: SocketPacket {
+                ty: T::NAME.to_string(),
+                requester: None,
+                data: None
+            },
+            critical
+        }
+    }
+}
+
+/// Response of [BatchRequest], contains results in the same order as the requests
+#[derive(Serialize, Deserialize)]
+pub struct BatchResponse {
+    pub results: Vec<BatchItemResult>
+}
+
+/// Result of a single request in [BatchRequest]
+#[derive(Serialize, Deserialize)]
+pub enum BatchItemResult {
+    /// Sent if request was processed, contains response packet of the request
+    Response(SocketPacket),
+
+    /// Sent if daemon didn't respond to the request, happens if request type is unknown, data of the request is invalid or request is another batch
+    Failed,
+
+    /// Sent if request wasn't processed because a critical request before it failed
+    Skipped,
+}
+
+impl BatchItemResult {
+    /// Attempts to parse response of the request into specified type
+    pub fn parse<T: SocketData + DeserializeOwned>(&self) -> Option<T> {
+        if let BatchItemResult::Response(packet) = self {
+            parse_packet_to_data(packet).ok()
+        } else {
+            None
+        }
+    }
+}
+
+impl SocketData for BatchRequest {
+    const NAME: &'static str = "batch";
+}
+
+impl SocketData for BatchResponse {
+    const NAME: &'static str = "batch";
+}
+
+#[async_trait]
+impl DaemonRequest for BatchRequest {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<BatchRequest>(packet) {
+            let mut results = vec![];
+            let mut skipping = false;
+
+            for item in request.requests {
+                if skipping {
+                    results.push(BatchItemResult::Skipped);
+                    continue;
+                }
+
+                // Nested batches are not processed
+                let response = if item.packet.ty != BatchRequest::NAME {
+                    let mut buffer: Vec<u8> = vec![];
+                    listener.message(&mut buffer, item.packet).await;
+
+                    String::from_utf8_lossy(&buffer)
+                        .split('\u{0004}')
+                        .find_map(|x| serde_json::from_str::<SocketPacket>(x).ok())
+                } else {
+                    None
+                };
+
+                if let Some(response) = response {
+                    results.push(BatchItemResult::Response(response));
+                } else {
+                    results.push(BatchItemResult::Failed);
+                    skipping = item.critical;
+                }
+            }
+
+            send_packet(handle, packet, &BatchResponse {
+                results
+            }).await.ok();
+        }
+    }
+}
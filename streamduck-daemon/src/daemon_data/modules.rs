@@ -2,7 +2,8 @@
 use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use streamduck_core::modules::components::{ComponentDefinition, UIPathValue};
-use streamduck_core::modules::{add_element_module_setting, PluginMetadata, remove_element_module_setting, set_module_setting};
+use streamduck_core::modules::{add_element_module_setting, FailedPlugin, PluginMetadata, remove_element_module_setting, set_module_setting};
+use streamduck_core::modules::plugins::{FeatureCompatibility, get_feature_compatibility, get_missing_essential_features};
 use streamduck_core::socket::{check_packet_for_data, parse_packet_to_data, send_packet, SocketData, SocketHandle, SocketPacket};
 use streamduck_core::util::convert_value_to_path;
 use crate::daemon_data::{DaemonListener, DaemonRequest};
@@ -34,6 +35,46 @@ impl DaemonRequest for ListModules {
     }
 }
 
+/// Load status of a module or a plugin
+#[derive(Serialize, Deserialize)]
+pub enum ModuleStatus {
+    /// Module that was loaded
+    Loaded(PluginMetadata),
+
+    /// Plugin that was found, but failed to load
+    Failed(FailedPlugin),
+}
+
+/// Request for getting all loaded modules together with plugins that failed to load
+#[derive(Serialize, Deserialize)]
+pub struct ListModuleStatus {
+    pub modules: Vec<ModuleStatus>
+}
+
+impl SocketData for ListModuleStatus {
+    const NAME: &'static str = "list_module_status";
+}
+
+#[async_trait]
+impl DaemonRequest for ListModuleStatus {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if check_packet_for_data::<ListModuleStatus>(&packet) {
+            let mut modules = listener.module_manager.get_module_list().await
+                .iter()
+                .map(|m| ModuleStatus::Loaded(m.metadata()))
+                .collect::<Vec<ModuleStatus>>();
+
+            modules.extend(listener.module_manager.get_failed_plugins().await
+                .into_iter()
+                .map(ModuleStatus::Failed));
+
+            send_packet(handle, &packet, &ListModuleStatus {
+                modules
+            }).await.ok();
+        }
+    }
+}
+
 /// Request for getting all components defined by all modules
 #[derive(Serialize, Deserialize)]
 pub struct ListComponents {
@@ -61,6 +102,143 @@ impl DaemonRequest for ListComponents {
     }
 }
 
+/// Request for getting definition of a single component
+#[derive(Serialize, Deserialize)]
+pub struct GetComponentDefinition {
+    pub component_name: String,
+}
+
+/// Response of [GetComponentDefinition] request
+#[derive(Serialize, Deserialize)]
+pub enum GetComponentDefinitionResult {
+    /// Sent if no module defines component with such name
+    ComponentNotFound,
+
+    /// Sent if component was found, contains map of module name to definition, has more than one entry if multiple modules define component with same name
+    Found(HashMap<String, ComponentDefinition>),
+}
+
+impl SocketData for GetComponentDefinition {
+    const NAME: &'static str = "get_component_definition";
+}
+
+impl SocketData for GetComponentDefinitionResult {
+    const NAME: &'static str = "get_component_definition";
+}
+
+#[async_trait]
+impl DaemonRequest for GetComponentDefinition {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<GetComponentDefinition>(packet) {
+            let definitions: HashMap<String, ComponentDefinition> = listener.module_manager.read_module_component_map().await
+                .iter()
+                .filter_map(|(module, components)| components.get(&request.component_name).map(|definition| (module.clone(), definition.clone())))
+                .collect();
+
+            if definitions.is_empty() {
+                send_packet(handle, packet, &GetComponentDefinitionResult::ComponentNotFound).await.ok();
+            } else {
+                send_packet(handle, packet, &GetComponentDefinitionResult::Found(definitions)).await.ok();
+            }
+        }
+    }
+}
+
+/// Request for getting modules that define a component
+#[derive(Serialize, Deserialize)]
+pub struct GetComponentOwner {
+    pub component_name: String,
+}
+
+/// Response of [GetComponentOwner] request
+#[derive(Serialize, Deserialize)]
+pub enum GetComponentOwnerResult {
+    /// Sent if no module defines component with such name
+    ComponentNotFound,
+
+    /// Sent if component was found, contains metadata of every module that defines the component
+    Found(Vec<PluginMetadata>),
+}
+
+impl SocketData for GetComponentOwner {
+    const NAME: &'static str = "get_component_owner";
+}
+
+impl SocketData for GetComponentOwnerResult {
+    const NAME: &'static str = "get_component_owner";
+}
+
+#[async_trait]
+impl DaemonRequest for GetComponentOwner {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<GetComponentOwner>(packet) {
+            let owners: Vec<String> = listener.module_manager.read_module_component_map().await
+                .iter()
+                .filter(|(_, components)| components.contains_key(&request.component_name))
+                .map(|(module, _)| module.clone())
+                .collect();
+
+            let mut metadata: Vec<PluginMetadata> = listener.module_manager.get_modules_from_list(&owners).await
+                .into_iter()
+                .map(|module| module.metadata())
+                .collect();
+
+            metadata.sort_by(|a, b| a.name.cmp(&b.name));
+
+            if metadata.is_empty() {
+                send_packet(handle, packet, &GetComponentOwnerResult::ComponentNotFound).await.ok();
+            } else {
+                send_packet(handle, packet, &GetComponentOwnerResult::Found(metadata)).await.ok();
+            }
+        }
+    }
+}
+
+/// Request for checking compatibility of features used by a module with the core
+#[derive(Serialize, Deserialize)]
+pub struct GetModuleCompatibility {
+    pub module_name: String,
+}
+
+/// Response of [GetModuleCompatibility] request
+#[derive(Serialize, Deserialize)]
+pub enum GetModuleCompatibilityResult {
+    /// Sent if module wasn't found
+    ModuleNotFound,
+
+    /// Sent if module was found, contains compatibility of each feature module reported and essential features module didn't report
+    Compatibility {
+        features: Vec<FeatureCompatibility>,
+        missing_essential_features: Vec<String>,
+    },
+}
+
+impl SocketData for GetModuleCompatibility {
+    const NAME: &'static str = "get_module_compatibility";
+}
+
+impl SocketData for GetModuleCompatibilityResult {
+    const NAME: &'static str = "get_module_compatibility";
+}
+
+#[async_trait]
+impl DaemonRequest for GetModuleCompatibility {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<GetModuleCompatibility>(packet) {
+            if let Some(module) = listener.module_manager.get_module(&request.module_name).await {
+                let metadata = module.metadata();
+
+                send_packet(handle, packet, &GetModuleCompatibilityResult::Compatibility {
+                    features: get_feature_compatibility(&metadata.used_features),
+                    missing_essential_features: get_missing_essential_features(&metadata)
+                }).await.ok();
+            } else {
+                send_packet(handle, packet, &GetModuleCompatibilityResult::ModuleNotFound).await.ok();
+            }
+        }
+    }
+}
+
 /// Request for getting module settings
 #[derive(Serialize, Deserialize)]
 pub struct GetModuleValues {
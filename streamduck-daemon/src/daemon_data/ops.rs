@@ -1,4 +1,5 @@
 //! Requests for various operations
+use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use streamduck_core::core::CoreHandle;
 use streamduck_core::socket::{parse_packet_to_data, send_packet, SocketData, SocketHandle, SocketPacket};
@@ -45,6 +46,71 @@ impl DaemonRequest for CommitChangesToConfig {
     }
 }
 
+/// Request for committing changes of all connected devices to their device configs
+#[derive(Serialize, Deserialize)]
+pub struct CommitAllChanges {
+    /// If device configs should also be saved after committing
+    pub save: bool
+}
+
+/// Result of committing changes of a single device
+#[derive(Serialize, Deserialize, Debug)]
+pub enum DeviceCommitResult {
+    /// Sent if changes were committed
+    Committed,
+
+    /// Sent if changes were committed and device config was saved
+    Saved,
+
+    /// Sent if changes were committed, but error happened while saving device config
+    ConfigError,
+}
+
+/// Response of [CommitAllChanges] request
+#[derive(Serialize, Deserialize)]
+pub enum CommitAllChangesResult {
+    /// Sent after all devices were processed, contains result for each device by serial number
+    Committed(HashMap<String, DeviceCommitResult>)
+}
+
+impl SocketData for CommitAllChanges {
+    const NAME: &'static str = "commit_all_changes";
+}
+
+impl SocketData for CommitAllChangesResult {
+    const NAME: &'static str = "commit_all_changes";
+}
+
+#[async_trait]
+impl DaemonRequest for CommitAllChanges {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<CommitAllChanges>(packet) {
+            let mut results = HashMap::new();
+
+            for (serial, device) in listener.core_manager.list_added_devices().await {
+                let wrapped_core = CoreHandle::wrap(device.core);
+                wrapped_core.commit_changes().await;
+
+                let result = if request.save {
+                    match listener.config.save_device_config(&serial).await {
+                        Ok(_) => DeviceCommitResult::Saved,
+                        Err(err) => {
+                            log::error!("Error encountered while saving config for {}: {:?}", serial, err);
+                            DeviceCommitResult::ConfigError
+                        }
+                    }
+                } else {
+                    DeviceCommitResult::Committed
+                };
+
+                results.insert(serial, result);
+            }
+
+            send_packet(handle, packet, &CommitAllChangesResult::Committed(results)).await.ok();
+        }
+    }
+}
+
 /// Request for simulating a press on a button on current screen for a device
 #[derive(Serialize, Deserialize)]
 pub struct DoButtonAction {
@@ -2,10 +2,11 @@
 use std::collections::HashMap;
 use std::io::Cursor;
 use serde::{Deserialize, Serialize};
-use streamduck_core::core::{CoreHandle, RawButtonPanel};
+use streamduck_core::core::{CoreHandle, generate_panel_id, RawButtonPanel};
+use streamduck_core::core::button::Button;
 use streamduck_core::image::ImageOutputFormat;
-use streamduck_core::socket::{parse_packet_to_data, send_packet, SocketData, SocketHandle, SocketPacket};
-use streamduck_core::util::{make_panel_unique, panel_to_raw};
+use streamduck_core::socket::{check_packet_for_data, parse_packet_to_data, send_packet, SocketData, SocketHandle, SocketPacket};
+use streamduck_core::util::{deserialize_panel, make_panel_unique, panel_to_raw, serialize_panel};
 use crate::daemon_data::{DaemonListener, DaemonRequest};
 use streamduck_core::async_trait;
 
@@ -86,14 +87,18 @@ impl DaemonRequest for GetStackNames {
             if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
                 let wrapped_core = CoreHandle::wrap(device.core);
 
-                let mut raw_stack = vec![];
+                let mut names = vec![];
 
-                for stack_item in wrapped_core.get_stack().await {
-                    let raw_item = panel_to_raw(&stack_item).await;
-                    raw_stack.push(raw_item.display_name);
+                // Names are read under a single stack lock, so concurrent stack changes can't mix up the list
+                let stack = wrapped_core.current_stack().await;
+
+                for stack_item in stack.iter() {
+                    names.push(stack_item.read().await.display_name.clone());
                 }
 
-                send_packet(handle, packet, &GetStackNamesResult::Stack(raw_stack)).await.ok();
+                drop(stack);
+
+                send_packet(handle, packet, &GetStackNamesResult::Stack(names)).await.ok();
             } else {
                 send_packet(handle, packet, &GetStackNamesResult::DeviceNotFound).await.ok();
             }
@@ -152,7 +157,13 @@ impl DaemonRequest for GetCurrentScreen {
 /// Request for getting current button images on a device
 #[derive(Serialize, Deserialize)]
 pub struct GetButtonImages {
-    pub serial_number: String
+    pub serial_number: String,
+    /// If images should be sent as raw RGBA pixels instead of PNG
+    #[serde(default)]
+    pub raw: bool,
+    /// Keys to get images for, all keys if not specified. Lets clients request images in smaller chunks instead of one large response
+    #[serde(default)]
+    pub keys: Option<Vec<u8>>
 }
 
 /// Response of [GetButtonImages] request
@@ -161,8 +172,22 @@ pub enum GetButtonImagesResult {
     /// Sent if device wasn't found
     DeviceNotFound,
 
-    /// Sent if successfully generated images
-    Images(HashMap<u8, String>)
+    /// Sent if successfully generated images, images are base64 encoded PNGs
+    Images(HashMap<u8, String>),
+
+    /// Sent if successfully generated images and raw images were requested
+    RawImages(HashMap<u8, RawImage>)
+}
+
+/// Image as raw RGBA pixels
+#[derive(Serialize, Deserialize)]
+pub struct RawImage {
+    /// Width of the image in pixels
+    pub width: u32,
+    /// Height of the image in pixels
+    pub height: u32,
+    /// Base64 encoded RGBA pixels, row by row
+    pub data: String
 }
 
 impl SocketData for GetButtonImages {
@@ -180,7 +205,24 @@ impl DaemonRequest for GetButtonImages {
             if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
                 let wrapped_core = CoreHandle::wrap(device.core);
 
-                if let Some(images) = wrapped_core.get_button_images().await {
+                if let Some(images) = wrapped_core.get_button_images_for_keys(request.keys.as_deref()).await {
+                    if request.raw {
+                        let images = images.into_iter()
+                            .map(|(key, image)| {
+                                let image = image.to_rgba8();
+
+                                (key, RawImage {
+                                    width: image.width(),
+                                    height: image.height(),
+                                    data: base64::encode(image.into_raw())
+                                })
+                            })
+                            .collect();
+
+                        send_packet(handle, packet, &GetButtonImagesResult::RawImages(images)).await.ok();
+                        return;
+                    }
+
                     let images = images.into_iter()
                         .map(|(key, image)| {
                             let mut buffer: Vec<u8> = vec![];
@@ -200,6 +242,52 @@ impl DaemonRequest for GetButtonImages {
 }
 
 
+/// Request for getting image of the whole device with all current button images arranged in a grid
+#[derive(Serialize, Deserialize)]
+pub struct GetDeckComposite {
+    pub serial_number: String
+}
+
+/// Response of [GetDeckComposite] request
+#[derive(Serialize, Deserialize)]
+pub enum GetDeckCompositeResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if successfully generated image, image is base64 encoded PNG
+    Image(String)
+}
+
+impl SocketData for GetDeckComposite {
+    const NAME: &'static str = "get_deck_composite";
+}
+
+impl SocketData for GetDeckCompositeResult {
+    const NAME: &'static str = "get_deck_composite";
+}
+
+#[async_trait]
+impl DaemonRequest for GetDeckComposite {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<GetDeckComposite>(packet) {
+            if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                let wrapped_core = CoreHandle::wrap(device.core);
+
+                if let Some(image) = wrapped_core.get_deck_composite().await {
+                    let mut buffer: Vec<u8> = vec![];
+                    image.write_to(&mut Cursor::new(&mut buffer), ImageOutputFormat::Png).ok();
+
+                    send_packet(handle, packet, &GetDeckCompositeResult::Image(base64::encode(buffer))).await.ok();
+                    return;
+                }
+            }
+
+            send_packet(handle, packet, &GetDeckCompositeResult::DeviceNotFound).await.ok();
+        }
+    }
+}
+
+
 /// Request for getting current button image on a device
 #[derive(Serialize, Deserialize)]
 pub struct GetButtonImage {
@@ -250,6 +338,163 @@ impl DaemonRequest for GetButtonImage {
     }
 }
 
+/// Request for getting image of what modules of a single component draw on a button, for debugging buttons with many components
+#[derive(Serialize, Deserialize)]
+pub struct GetComponentRender {
+    pub serial_number: String,
+    pub key: u8,
+    pub component_name: String,
+}
+
+/// Response of [GetComponentRender] request
+#[derive(Serialize, Deserialize)]
+pub enum GetComponentRenderResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if there's no button
+    NoButton,
+
+    /// Sent if button doesn't have the component
+    NoComponent,
+
+    /// Sent if successfully rendered the component, as base64 png with transparent background
+    Image(String)
+}
+
+impl SocketData for GetComponentRender {
+    const NAME: &'static str = "get_component_render";
+}
+
+impl SocketData for GetComponentRenderResult {
+    const NAME: &'static str = "get_component_render";
+}
+
+#[async_trait]
+impl DaemonRequest for GetComponentRender {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<GetComponentRender>(packet) {
+            if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                let wrapped_core = CoreHandle::wrap(device.core);
+
+                if let Some(button) = wrapped_core.get_button(request.key).await {
+                    if !button.read().await.component_names().contains(&request.component_name) {
+                        send_packet(handle, packet, &GetComponentRenderResult::NoComponent).await.ok();
+                        return;
+                    }
+
+                    let image = wrapped_core.render_component(&button, &request.component_name).await;
+
+                    let mut buffer: Vec<u8> = vec![];
+                    image.write_to(&mut Cursor::new(&mut buffer), ImageOutputFormat::Png).ok();
+
+                    send_packet(handle, packet, &GetComponentRenderResult::Image(base64::encode(buffer))).await.ok();
+                } else {
+                    send_packet(handle, packet, &GetComponentRenderResult::NoButton).await.ok();
+                }
+            } else {
+                send_packet(handle, packet, &GetComponentRenderResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
+}
+
+/// Request for getting image that was last rendered on a key, unlike [GetButtonImage] it doesn't render the button again
+#[derive(Serialize, Deserialize)]
+pub struct GetLastRendered {
+    pub serial_number: String,
+    pub key: u8,
+}
+
+/// Response of [GetLastRendered] request
+#[derive(Serialize, Deserialize)]
+pub enum GetLastRenderedResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if there's no rendered image for the key, or if renderer doesn't keep last rendered images
+    NoImage,
+
+    /// Sent if image was found
+    Image(String)
+}
+
+impl SocketData for GetLastRendered {
+    const NAME: &'static str = "get_last_rendered";
+}
+
+impl SocketData for GetLastRenderedResult {
+    const NAME: &'static str = "get_last_rendered";
+}
+
+#[async_trait]
+impl DaemonRequest for GetLastRendered {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<GetLastRendered>(packet) {
+            if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                if let Some(image) = device.core.last_rendered_image(request.key).await {
+                    let mut buffer: Vec<u8> = vec![];
+                    image.write_to(&mut Cursor::new(&mut buffer), ImageOutputFormat::Png).ok();
+
+                    send_packet(handle, packet, &GetLastRenderedResult::Image(base64::encode(buffer))).await.ok();
+                } else {
+                    send_packet(handle, packet, &GetLastRenderedResult::NoImage).await.ok();
+                }
+            } else {
+                send_packet(handle, packet, &GetLastRenderedResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
+}
+
+/// Request for rendering a button that isn't on the device, doesn't change anything on the device
+#[derive(Serialize, Deserialize)]
+pub struct RenderPreview {
+    pub serial_number: String,
+    /// Key that is passed to custom renderers
+    #[serde(default)]
+    pub key: u8,
+    pub button: Button,
+}
+
+/// Response of [RenderPreview] request
+#[derive(Serialize, Deserialize)]
+pub enum RenderPreviewResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if successfully rendered the button, contains base64 encoded PNG image
+    Image(String)
+}
+
+impl SocketData for RenderPreview {
+    const NAME: &'static str = "render_preview";
+}
+
+impl SocketData for RenderPreviewResult {
+    const NAME: &'static str = "render_preview";
+}
+
+#[async_trait]
+impl DaemonRequest for RenderPreview {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<RenderPreview>(packet) {
+            if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                let wrapped_core = CoreHandle::wrap(device.core);
+
+                let image = wrapped_core.render_button_preview(request.key, request.button).await;
+
+                let mut buffer: Vec<u8> = vec![];
+                image.write_to(&mut Cursor::new(&mut buffer), ImageOutputFormat::Png).ok();
+
+                send_packet(handle, packet, &RenderPreviewResult::Image(base64::encode(buffer))).await.ok();
+            } else {
+                send_packet(handle, packet, &RenderPreviewResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
+}
+
 /// Request for pushing a new screen on a device
 #[derive(Serialize, Deserialize)]
 pub struct PushScreen {
@@ -263,6 +508,9 @@ pub enum PushScreenResult {
     /// Sent if device wasn't found
     DeviceNotFound,
 
+    /// Sent if device is locked
+    Locked,
+
     /// Sent if successfully pushed a screen
     Pushed
 }
@@ -282,6 +530,11 @@ impl DaemonRequest for PushScreen {
             if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
                 let wrapped_core = CoreHandle::wrap(device.core);
 
+                if wrapped_core.is_locked().await {
+                    send_packet(handle, packet, &PushScreenResult::Locked).await.ok();
+                    return;
+                }
+
                 wrapped_core.push_screen(make_panel_unique(request.screen)).await;
                 send_packet(handle, packet, &PushScreenResult::Pushed).await.ok();
             } else {
@@ -291,6 +544,147 @@ impl DaemonRequest for PushScreen {
     }
 }
 
+/// Request for exporting a screen from the stack of a device
+#[derive(Serialize, Deserialize)]
+pub struct ExportScreen {
+    pub serial_number: String,
+    pub stack_index: usize
+}
+
+/// Response of [ExportScreen] request
+#[derive(Serialize, Deserialize)]
+pub enum ExportScreenResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if there's no screen at specified stack index
+    InvalidIndex,
+
+    /// Sent if screen failed to serialize
+    FailedToSerialize,
+
+    /// Sent if successfully exported, contains screen serialized to json
+    Exported(String)
+}
+
+impl SocketData for ExportScreen {
+    const NAME: &'static str = "export_screen";
+}
+
+impl SocketData for ExportScreenResult {
+    const NAME: &'static str = "export_screen";
+}
+
+#[async_trait]
+impl DaemonRequest for ExportScreen {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<ExportScreen>(packet) {
+            if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                let wrapped_core = CoreHandle::wrap(device.core);
+
+                if let Some(screen) = wrapped_core.peek_screen_at(request.stack_index).await {
+                    match serialize_panel(screen).await.and_then(|x| serde_json::to_string(&x)) {
+                        Ok(screen) => {
+                            send_packet(handle, packet, &ExportScreenResult::Exported(screen)).await.ok();
+                        }
+
+                        Err(err) => {
+                            log::error!("Failed to serialize screen: {}", err);
+                            send_packet(handle, packet, &ExportScreenResult::FailedToSerialize).await.ok();
+                        }
+                    }
+                } else {
+                    send_packet(handle, packet, &ExportScreenResult::InvalidIndex).await.ok();
+                }
+            } else {
+                send_packet(handle, packet, &ExportScreenResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
+}
+
+/// Request for importing a screen exported with [ExportScreen] and pushing it into the stack of a device
+#[derive(Serialize, Deserialize)]
+pub struct ImportScreen {
+    pub serial_number: String,
+    pub screen: String
+}
+
+/// Response of [ImportScreen] request
+#[derive(Serialize, Deserialize)]
+pub enum ImportScreenResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if screen couldn't be deserialized
+    InvalidScreen,
+
+    /// Sent if device is locked
+    Locked,
+
+    /// Sent if successfully imported, contains names of components that aren't provided by any loaded module
+    Imported(Vec<String>)
+}
+
+impl SocketData for ImportScreen {
+    const NAME: &'static str = "import_screen";
+}
+
+impl SocketData for ImportScreenResult {
+    const NAME: &'static str = "import_screen";
+}
+
+#[async_trait]
+impl DaemonRequest for ImportScreen {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<ImportScreen>(packet) {
+            if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                let wrapped_core = CoreHandle::wrap(device.core);
+
+                if wrapped_core.is_locked().await {
+                    send_packet(handle, packet, &ImportScreenResult::Locked).await.ok();
+                    return;
+                }
+
+                let screen = match serde_json::from_str(&request.screen).and_then(deserialize_panel) {
+                    Ok(screen) => screen,
+                    Err(err) => {
+                        log::error!("Failed to deserialize screen: {}", err);
+                        send_packet(handle, packet, &ImportScreenResult::InvalidScreen).await.ok();
+                        return;
+                    }
+                };
+
+                let mut missing_components = vec![];
+
+                {
+                    let component_map = listener.module_manager.read_component_map().await;
+
+                    for button in screen.read().await.buttons.values() {
+                        for component in button.read().await.component_names() {
+                            if !component_map.contains_key(&component) && !missing_components.contains(&component) {
+                                missing_components.push(component);
+                            }
+                        }
+                    }
+                }
+
+                if !missing_components.is_empty() {
+                    log::warn!("Imported screen uses components that aren't provided by any loaded module: {}", missing_components.join(", "));
+                }
+
+                // Same screen might be imported more than once, each import gets its own ID
+                screen.write().await.id = generate_panel_id();
+
+                wrapped_core.push_screen(screen).await;
+                send_packet(handle, packet, &ImportScreenResult::Imported(missing_components)).await.ok();
+            } else {
+                send_packet(handle, packet, &ImportScreenResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
+}
+
 /// Request for popping top-most screen on a device
 #[derive(Serialize, Deserialize)]
 pub struct PopScreen {
@@ -306,6 +700,9 @@ pub enum PopScreenResult {
     /// Sent if current screen is the only one remaining
     OnlyOneRemaining,
 
+    /// Sent if device is locked
+    Locked,
+
     /// Sent if successfully popped a screen
     Popped
 }
@@ -330,6 +727,11 @@ impl DaemonRequest for PopScreen {
 
                 let wrapped_core = CoreHandle::wrap(device.core);
 
+                if wrapped_core.is_locked().await {
+                    send_packet(handle, packet, &PopScreenResult::Locked).await.ok();
+                    return;
+                }
+
                 if count > 1 {
                     wrapped_core.pop_screen().await;
                     send_packet(handle, packet, &PopScreenResult::Popped).await.ok();
@@ -355,6 +757,9 @@ pub enum ForciblyPopScreenResult {
     /// Sent if device wasn't found
     DeviceNotFound,
 
+    /// Sent if device is locked
+    Locked,
+
     /// Sent if successfully popped a screen
     Popped
 }
@@ -374,6 +779,11 @@ impl DaemonRequest for ForciblyPopScreen {
             if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
                 let wrapped_core = CoreHandle::wrap(device.core);
 
+                if wrapped_core.is_locked().await {
+                    send_packet(handle, packet, &ForciblyPopScreenResult::Locked).await.ok();
+                    return;
+                }
+
                 wrapped_core.pop_screen().await;
                 send_packet(handle, packet, &ForciblyPopScreenResult::Popped).await.ok();
             } else {
@@ -396,6 +806,9 @@ pub enum ReplaceScreenResult {
     /// Sent if device wasn't found
     DeviceNotFound,
 
+    /// Sent if device is locked
+    Locked,
+
     /// Sent if successfully replaced the screen
     Replaced
 }
@@ -415,6 +828,11 @@ impl DaemonRequest for ReplaceScreen {
             if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
                 let wrapped_core = CoreHandle::wrap(device.core);
 
+                if wrapped_core.is_locked().await {
+                    send_packet(handle, packet, &ReplaceScreenResult::Locked).await.ok();
+                    return;
+                }
+
                 wrapped_core.replace_screen(make_panel_unique(request.screen)).await;
                 send_packet(handle, packet, &ReplaceScreenResult::Replaced).await.ok();
             } else {
@@ -465,6 +883,112 @@ impl DaemonRequest for ResetStack {
     }
 }
 
+/// Example layouts embedded into the daemon, as name, description and serialized panel
+const EXAMPLE_LAYOUTS: &[(&str, &str, &str)] = &[
+    ("welcome", "Few buttons to get started with", include_str!("examples/welcome.json")),
+    ("color_palette", "Buttons with colored backgrounds labeled with names of the colors", include_str!("examples/color_palette.json")),
+];
+
+/// Example layout that can be loaded with [LoadExampleLayout]
+#[derive(Serialize, Deserialize)]
+pub struct ExampleLayout {
+    /// Name of the example
+    pub name: String,
+    /// Description of the example
+    pub description: String,
+}
+
+/// Request for listing example layouts built into the daemon
+#[derive(Serialize, Deserialize)]
+pub struct ListExampleLayouts {
+    pub layouts: Vec<ExampleLayout>
+}
+
+impl SocketData for ListExampleLayouts {
+    const NAME: &'static str = "list_example_layouts";
+}
+
+#[async_trait]
+impl DaemonRequest for ListExampleLayouts {
+    async fn process(_listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if check_packet_for_data::<ListExampleLayouts>(&packet) {
+            let layouts = EXAMPLE_LAYOUTS.iter()
+                .map(|(name, description, _)| ExampleLayout {
+                    name: name.to_string(),
+                    description: description.to_string()
+                })
+                .collect();
+
+            send_packet(handle, &packet, &ListExampleLayouts {
+                layouts
+            }).await.ok();
+        }
+    }
+}
+
+/// Request for resetting stack of a device with one of the example layouts
+#[derive(Serialize, Deserialize)]
+pub struct LoadExampleLayout {
+    pub serial_number: String,
+    pub name: String,
+}
+
+/// Response of [LoadExampleLayout] request
+#[derive(Serialize, Deserialize)]
+pub enum LoadExampleLayoutResult {
+    /// Sent if device wasn't found
+    DeviceNotFound,
+
+    /// Sent if there's no example layout with provided name
+    LayoutNotFound,
+
+    /// Sent if example layout failed to parse
+    InvalidLayout,
+
+    /// Sent if device is locked
+    Locked,
+
+    /// Sent if successfully loaded example layout
+    Loaded
+}
+
+impl SocketData for LoadExampleLayout {
+    const NAME: &'static str = "load_example_layout";
+}
+
+impl SocketData for LoadExampleLayoutResult {
+    const NAME: &'static str = "load_example_layout";
+}
+
+#[async_trait]
+impl DaemonRequest for LoadExampleLayout {
+    async fn process(listener: &DaemonListener, handle: SocketHandle<'_>, packet: &SocketPacket) {
+        if let Ok(request) = parse_packet_to_data::<LoadExampleLayout>(packet) {
+            if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
+                let wrapped_core = CoreHandle::wrap(device.core);
+
+                if wrapped_core.is_locked().await {
+                    send_packet(handle, packet, &LoadExampleLayoutResult::Locked).await.ok();
+                    return;
+                }
+
+                if let Some((_, _, layout)) = EXAMPLE_LAYOUTS.iter().find(|(name, ..)| *name == request.name) {
+                    if let Ok(panel) = serde_json::from_str(layout).and_then(deserialize_panel) {
+                        wrapped_core.reset_stack(panel).await;
+                        send_packet(handle, packet, &LoadExampleLayoutResult::Loaded).await.ok();
+                    } else {
+                        send_packet(handle, packet, &LoadExampleLayoutResult::InvalidLayout).await.ok();
+                    }
+                } else {
+                    send_packet(handle, packet, &LoadExampleLayoutResult::LayoutNotFound).await.ok();
+                }
+            } else {
+                send_packet(handle, packet, &LoadExampleLayoutResult::DeviceNotFound).await.ok();
+            }
+        }
+    }
+}
+
 /// Request for going to root screen
 #[derive(Serialize, Deserialize)]
 pub struct DropStackToRoot {
@@ -477,6 +1001,9 @@ pub enum DropStackToRootResult {
     /// Sent if device wasn't found
     DeviceNotFound,
 
+    /// Sent if device is locked
+    Locked,
+
     /// Sent if successfully dropped to root
     Dropped
 }
@@ -496,6 +1023,11 @@ impl DaemonRequest for DropStackToRoot {
             if let Some(device) = listener.core_manager.get_device(&request.serial_number).await {
                 let wrapped_core = CoreHandle::wrap(device.core);
 
+                if wrapped_core.is_locked().await {
+                    send_packet(handle, packet, &DropStackToRootResult::Locked).await.ok();
+                    return;
+                }
+
                 let first_screen = wrapped_core.get_root_screen().await;
                 wrapped_core.reset_stack(first_screen).await;
                 send_packet(handle, packet, &DropStackToRootResult::Dropped).await.ok();
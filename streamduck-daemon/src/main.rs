@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use clap::{Arg, ArgAction, command, value_parser};
 use clap::parser::ArgMatches;
@@ -13,13 +13,15 @@ use tokio::sync::Mutex;
 
 use streamduck_core::config::Config;
 use streamduck_core::core::manager::CoreManager;
-use streamduck_core::font::{load_default_font, load_fonts_from_resources};
+use streamduck_core::font::{load_default_font, load_fonts_from_folder, load_fonts_from_resources};
 use streamduck_core::modules::{load_base_modules, ModuleManager};
 use streamduck_core::modules::plugins::load_plugins_from_folder;
 use streamduck_core::socket::SocketManager;
 use streamduck_core::thread::rendering::custom::RenderingManager;
 use streamduck_daemon::daemon_data::DaemonListener;
 
+mod watcher;
+
 #[cfg(target_family = "unix")]
 mod unix;
 #[cfg(target_family = "windows")]
@@ -58,6 +60,20 @@ fn main() {
                 .value_parser(value_parser!(String))
                 .help("Specify from where the config should be loaded")
             )
+        .arg(
+            Arg::new("safe_mode")
+                .long("safe-mode")
+                .action(ArgAction::SetTrue)
+                .value_parser(value_parser!(bool))
+                .help("Start without loading any plugins, so broken layouts can be inspected and fixed")
+            )
+        .arg(
+            Arg::new("root")
+                .short('r')
+                .long("root")
+                .value_parser(value_parser!(String))
+                .help("Relocate config and data folders into specified folder, can also be set with STREAMDUCK_ROOT environment variable")
+            )
         .get_matches();
     
     // Setting up Tokio runtime
@@ -98,6 +114,14 @@ async fn root(matches: ArgMatches) {
             }
     };
 
+    let custom_root = || -> Option<PathBuf> {
+        match matches
+            .get_one::<String>("root") {
+                Some(v) => Some(PathBuf::from(v)),
+                None => std::env::var_os("STREAMDUCK_ROOT").map(PathBuf::from)
+            }
+    };
+
     builder.default(level())
         .module("streamdeck", LevelFilter::Off);
 
@@ -121,7 +145,13 @@ async fn root(matches: ArgMatches) {
     let render_manager = RenderingManager::new();
 
     // Reading config
-    let config = Arc::new(Config::get(custom_path()).await);
+    let mut config = Config::get(custom_path(), custom_root()).await;
+
+    if *matches.get_one::<bool>("safe_mode").unwrap_or(&false) {
+        config.enable_safe_mode();
+    }
+
+    let config = Arc::new(config);
 
     // Initializing socket manager
     let socket_manager = SocketManager::new();
@@ -130,6 +160,7 @@ async fn root(matches: ArgMatches) {
     load_base_modules(module_manager.clone(), socket_manager.clone()).await;
     load_default_font();
     load_fonts_from_resources();
+    load_fonts_from_folder(config.font_path());
 
     // Initializing built-in modules
     streamduck_actions::init_module(&module_manager).await;
@@ -142,11 +173,16 @@ async fn root(matches: ArgMatches) {
         core_manager: core_manager.clone(),
         module_manager: module_manager.clone(),
         config: config.clone(),
-        clipboard: Mutex::new(None)
+        clipboard: Mutex::new(None),
+        start_time: Instant::now()
     })).await;
 
     // Loading plugins
-    load_plugins_from_folder(config.clone(), module_manager.clone(), socket_manager.clone(), render_manager.clone(), config.plugin_path()).await;
+    if config.safe_mode() {
+        log::warn!("Safe mode enabled, plugins will not be loaded");
+    } else {
+        load_plugins_from_folder(config.clone(), module_manager.clone(), socket_manager.clone(), render_manager.clone(), config.plugin_path()).await;
+    }
 
     // Announcing loaded modules
     for (module_name, _) in module_manager.get_modules().await {
@@ -172,6 +208,10 @@ async fn root(matches: ArgMatches) {
         std::process::exit(0);
     });
 
+    if config.watch_device_configs() {
+        tokio::spawn(watcher::device_config_watcher(config.clone(), core_manager.clone(), module_manager.clone()));
+    }
+
     if config.autosave() {
         tokio::spawn(autosave_task(config));
     }
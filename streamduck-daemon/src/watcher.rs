@@ -0,0 +1,110 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+use tokio::time::timeout;
+
+use streamduck_core::config::Config;
+use streamduck_core::core::CoreHandle;
+use streamduck_core::core::manager::CoreManager;
+use streamduck_core::modules::events::SDGlobalEvent;
+use streamduck_core::modules::ModuleManager;
+use streamduck_core::util::make_panel_unique;
+
+/// Watches device config folder and reloads device configs that were changed on disk
+pub async fn device_config_watcher(config: Arc<Config>, core_manager: Arc<CoreManager>, module_manager: Arc<ModuleManager>) {
+    let path = config.device_config_path();
+    tokio::fs::create_dir_all(&path).await.ok();
+
+    let (sender, mut receiver) = unbounded_channel::<Event>();
+
+    let mut watcher: RecommendedWatcher = match notify::recommended_watcher(move |result: notify::Result<Event>| {
+        match result {
+            Ok(event) => { sender.send(event).ok(); },
+            Err(err) => log::warn!("Device config watcher error: {:?}", err)
+        }
+    }) {
+        Ok(watcher) => watcher,
+        Err(err) => {
+            log::error!("Failed to create device config watcher: {:?}", err);
+            return;
+        }
+    };
+
+    if let Err(err) = watcher.watch(&path, RecursiveMode::NonRecursive) {
+        log::error!("Failed to watch device config folder: {:?}", err);
+        return;
+    }
+
+    log::debug!("Started device config watcher");
+
+    while let Some(event) = receiver.recv().await {
+        let mut serials = HashSet::new();
+        collect_serials(&event, &mut serials);
+
+        // Waiting for burst of writes to end
+        collect_until_quiet(&mut receiver, &mut serials, &config).await;
+
+        for serial in serials {
+            // Ignoring changes that were done by the daemon itself, file is compared by contents so user edits are never skipped
+            if config.is_own_write(&serial).await {
+                continue;
+            }
+
+            reload_config(&serial, &config, &core_manager, &module_manager).await;
+        }
+    }
+}
+
+async fn collect_until_quiet(receiver: &mut UnboundedReceiver<Event>, serials: &mut HashSet<String>, config: &Config) {
+    while let Ok(Some(event)) = timeout(config.watch_debounce(), receiver.recv()).await {
+        collect_serials(&event, serials);
+    }
+}
+
+fn collect_serials(event: &Event, serials: &mut HashSet<String>) {
+    match event.kind {
+        EventKind::Create(_) | EventKind::Modify(_) => {}
+        _ => return
+    }
+
+    for path in &event.paths {
+        if let Some(serial) = serial_from_path(path) {
+            serials.insert(serial);
+        }
+    }
+}
+
+fn serial_from_path(path: &Path) -> Option<String> {
+    if path.extension()?.to_str()? != "json" {
+        return None;
+    }
+
+    Some(path.file_stem()?.to_str()?.to_string())
+}
+
+async fn reload_config(serial: &str, config: &Config, core_manager: &CoreManager, module_manager: &ModuleManager) {
+    match config.reload_device_config(serial).await {
+        Ok(_) => {
+            if let Some(device) = core_manager.get_device(serial).await {
+                if !device.core.is_closed().await {
+                    if let Some(dvc_cfg) = config.get_device_config(serial).await {
+                        let handle = dvc_cfg.read().await;
+                        let wrapped_core = CoreHandle::wrap(device.core);
+
+                        wrapped_core.reset_stack(make_panel_unique(handle.layout.clone())).await;
+                    }
+                }
+            }
+
+            log::info!("Reloaded device config for {} after it was changed on disk", serial);
+
+            module_manager.send_global_event_to_modules(SDGlobalEvent::DeviceConfigReloaded {
+                serial_number: serial.to_string()
+            }).await;
+        }
+        Err(err) => log::error!("Error encountered while reloading config for {}: {:?}", serial, err)
+    }
+}
@@ -72,6 +72,7 @@ impl SDModule for ExampleModule {
         map.insert("example".to_string(), ComponentDefinition {
             display_name: "Example".to_string(),
             description: "Example component".to_string(),
+            requires: vec![],
             default_looks: RendererComponentBuilder::new()
                 .background(ButtonBackground::Solid((255, 0, 255, 255)))
                 .build()